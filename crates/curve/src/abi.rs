@@ -0,0 +1,431 @@
+//! Versioned binary ABI
+//!
+//! Function ids are composed as `Version + Operation + CURVE_ID`, the top byte
+//! selects the ABI version of the curve operations:
+//!
+//! - `0x01` legacy, pairing results are `[0]` if the product of pairings is
+//!   one and `[1]` otherwise
+//! - `0x03` v1, pairing results are SCALE encoded `bool`, `[1]` if the product
+//!   of pairings is one and `[0]` otherwise
+//!
+//! The other operations have the same layouts in every version, the target
+//! group equality is a SCALE encoded `bool` in both, and the EVM precompiles
+//! (`0x02`) keep the encodings of their EIPs.
+//!
+//! Layouts use `G1`/`G2` for arkworks `ToBytes` affine points with the
+//! trailing infinity byte, `G1c`/`G2c` for `CanonicalSerialize` compressed
+//! points, `GT` for arkworks `ToBytes` elements of the target group `Fqk`,
+//! `Fr` for little-endian scalars, `e` for a little-endian integer of
+//! the scalar length, `n` for a SCALE `Compact<u32>` count, `dst` for a SCALE
+//! `Vec<u8>` domain separation tag, `msg` for the remaining bytes, `|` for
+//! concatenation and `*` for repetition. The byte lengths of each curve are
+//! listed in the [`Abi`] description, which is returned SCALE encoded by the
+//! function id [`DESCRIBE`]. Every id of the curve operations also has ids
+//! in the `CanonicalSerialize` encodings, see [`crate::serialization`].
+//! The field elements of every curve are little-endian integers of whole
+//! 64-bit limbs, as listed in the `encoding` of the curves in [`Abi`].
+//! secp256k1 fields are `Fp320`, the 256-bit values are written as 40 bytes
+//! with 8 zero bytes on top, [`crate::curve::secp256k1::from_sec1`] and its
+//! siblings convert them from and to SEC1 points and 32-byte big-endian
+//! scalars.
+
+use ark_std::{convert::TryFrom, vec::Vec};
+use parity_scale_codec::{Decode, Encode};
+
+use crate::{
+    error::{Error, Result},
+    evm::Precompile,
+    ops::{
+        compressed_len, uncompressed_len, CanonicalSerialize, GroupOperations, PairingEngine,
+        PairingOperations, Scalar, Zero,
+    },
+    registry::{self, CurveId, Operation},
+};
+
+/// Mask of the ABI version in function ids
+pub const VERSION_MASK: u32 = 0xff000000;
+
+/// Function id of the SCALE encoded [`Abi`] description
+pub const DESCRIBE: u32 = 0x03ffffff;
+
+/// ABI versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum Version {
+    /// `0x01` ids, inverted pairing results
+    Legacy,
+    /// `0x03` ids, SCALE `bool` pairing results
+    V1,
+}
+
+impl Version {
+    /// All versions
+    pub const ALL: &'static [Version] = &[Version::Legacy, Version::V1];
+
+    /// The latest version
+    pub const LATEST: Version = Version::V1;
+
+    /// Top byte of the function ids of this version
+    pub const fn base(self) -> u32 {
+        match self {
+            Version::Legacy => 0x01000000,
+            Version::V1 => 0x03000000,
+        }
+    }
+
+    /// Function id of `op` on curve `curve_id` in this version
+    pub const fn func_id(self, op: Operation, curve_id: u32) -> u32 {
+        op.func_id(curve_id) - Version::Legacy.base() + self.base()
+    }
+
+    /// Encode the result of a pairing check
+    pub fn encode_bool(self, b: bool) -> Vec<u8> {
+        match self {
+            Version::Legacy => vec![if b { 0 } else { 1 }],
+            Version::V1 => b.encode(),
+        }
+    }
+
+    /// Decode the result of a pairing check
+    pub fn decode_bool(self, output: &[u8]) -> Result<bool> {
+        match (self, output) {
+            (Version::Legacy, [0]) | (Version::V1, [1]) => Ok(true),
+            (Version::Legacy, [1]) | (Version::V1, [0]) => Ok(false),
+            _ => Err(Error::ScaleCodecError),
+        }
+    }
+}
+
+impl TryFrom<u32> for Version {
+    type Error = Error;
+
+    fn try_from(func_id: u32) -> Result<Self> {
+        Version::ALL
+            .iter()
+            .find(|v| v.base() == func_id & VERSION_MASK)
+            .copied()
+            .ok_or(Error::InvalidFunctionId)
+    }
+}
+
+/// Split function id into version, curve and operation
+pub fn decode(func_id: u32) -> Result<(Version, CurveId, Operation)> {
+    let version = Version::try_from(func_id)?;
+    let (curve, op) = registry::decode(func_id - version.base() + Version::Legacy.base())?;
+    Ok((version, curve, op))
+}
+
+impl Operation {
+    /// Name of this operation
+    pub const fn name(self) -> &'static str {
+        match self {
+            Operation::Add => "add",
+            Operation::Mul => "mul",
+            Operation::Pairing => "pairing",
+            Operation::G2Add => "g2_add",
+            Operation::G2Mul => "g2_mul",
+            Operation::MsmG1 => "msm_g1",
+            Operation::MsmG2 => "msm_g2",
+            Operation::AddCompressed => "add_compressed",
+            Operation::MulCompressed => "mul_compressed",
+            Operation::PairingCompressed => "pairing_compressed",
+            Operation::MsmG1Trusted => "msm_g1_trusted",
+            Operation::PairingTrusted => "pairing_trusted",
+            Operation::HashToG1 => "hash_to_g1",
+            Operation::HashToG2 => "hash_to_g2",
+            Operation::FrAdd => "fr_add",
+            Operation::FrSub => "fr_sub",
+            Operation::FrMul => "fr_mul",
+            Operation::FrInverse => "fr_inverse",
+            Operation::FrPow => "fr_pow",
+            Operation::FrBatchInverse => "fr_batch_inverse",
+            Operation::GtPairing => "gt_pairing",
+            Operation::GtMul => "gt_mul",
+            Operation::GtPow => "gt_pow",
+            Operation::GtEq => "gt_eq",
+            Operation::SumG1 => "sum_g1",
+            Operation::SumG2 => "sum_g2",
+            Operation::MulGenerator => "mul_generator",
+            Operation::G2MulGenerator => "g2_mul_generator",
+        }
+    }
+
+    /// Input layout of this operation
+    pub const fn input_layout(self) -> &'static str {
+        match self {
+            Operation::Add => "G1 | G1",
+            Operation::Mul => "G1 | Fr",
+            Operation::Pairing | Operation::PairingTrusted => "(G1 | G2)*",
+            Operation::G2Add => "G2 | G2",
+            Operation::G2Mul => "G2 | Fr",
+            Operation::MsmG1 | Operation::MsmG1Trusted => "n | (G1 | Fr)*n",
+            Operation::MsmG2 => "n | (G2 | Fr)*n",
+            Operation::AddCompressed => "G1c | G1c",
+            Operation::MulCompressed => "G1c | Fr",
+            Operation::PairingCompressed => "(G1c | G2c)*",
+            Operation::HashToG1 | Operation::HashToG2 => "dst | msg",
+            Operation::FrAdd | Operation::FrSub | Operation::FrMul => "Fr | Fr",
+            Operation::FrInverse => "Fr",
+            Operation::FrPow => "Fr | e",
+            Operation::FrBatchInverse => "n | Fr*n",
+            Operation::GtPairing => "G1 | G2",
+            Operation::GtMul | Operation::GtEq => "GT | GT",
+            Operation::GtPow => "GT | Fr",
+            Operation::SumG1 => "G1*",
+            Operation::SumG2 => "G2*",
+            Operation::MulGenerator | Operation::G2MulGenerator => "Fr",
+        }
+    }
+
+    /// Output layout of this operation in `version`
+    pub const fn output_layout(self, version: Version) -> &'static str {
+        match self {
+            Operation::Add
+            | Operation::Mul
+            | Operation::MsmG1
+            | Operation::MsmG1Trusted
+            | Operation::HashToG1
+            | Operation::SumG1
+            | Operation::MulGenerator => "G1",
+            Operation::G2Add
+            | Operation::G2Mul
+            | Operation::MsmG2
+            | Operation::HashToG2
+            | Operation::SumG2
+            | Operation::G2MulGenerator => "G2",
+            Operation::FrAdd
+            | Operation::FrSub
+            | Operation::FrMul
+            | Operation::FrInverse
+            | Operation::FrPow => "Fr",
+            Operation::FrBatchInverse => "Fr*n",
+            Operation::GtPairing | Operation::GtMul | Operation::GtPow => "GT",
+            Operation::GtEq => "bool",
+            Operation::AddCompressed | Operation::MulCompressed => "G1c",
+            Operation::Pairing | Operation::PairingCompressed | Operation::PairingTrusted => {
+                match version {
+                    Version::Legacy => "u8, 0 if the product of pairings is one else 1",
+                    Version::V1 => "bool",
+                }
+            }
+        }
+    }
+}
+
+impl Precompile {
+    /// Name of this precompile in its EIP
+    pub const fn name(self) -> &'static str {
+        match self {
+            Precompile::Bls12381G1Add => "BLS12_G1ADD",
+            Precompile::Bls12381G1Mul => "BLS12_G1MUL",
+            Precompile::Bls12381G1Msm => "BLS12_G1MSM",
+            Precompile::Bls12381G2Add => "BLS12_G2ADD",
+            Precompile::Bls12381G2Mul => "BLS12_G2MUL",
+            Precompile::Bls12381G2Msm => "BLS12_G2MSM",
+            Precompile::Bls12381Pairing => "BLS12_PAIRING",
+            Precompile::Bls12381MapFpToG1 => "BLS12_MAP_FP_TO_G1",
+            Precompile::Bls12381MapFp2ToG2 => "BLS12_MAP_FP2_TO_G2",
+            Precompile::Bn254Add => "ecAdd",
+            Precompile::Bn254Mul => "ecMul",
+            Precompile::Bn254Pairing => "ecPairing",
+        }
+    }
+
+    /// Input layout of this precompile, big-endian with the byte lengths
+    pub const fn input_layout(self) -> &'static str {
+        match self {
+            Precompile::Bls12381G1Add => "G1[128] | G1[128]",
+            Precompile::Bls12381G1Mul => "G1[128] | Fr[32]",
+            Precompile::Bls12381G1Msm => "(G1[128] | Fr[32])*",
+            Precompile::Bls12381G2Add => "G2[256] | G2[256]",
+            Precompile::Bls12381G2Mul => "G2[256] | Fr[32]",
+            Precompile::Bls12381G2Msm => "(G2[256] | Fr[32])*",
+            Precompile::Bls12381Pairing => "(G1[128] | G2[256])*",
+            Precompile::Bls12381MapFpToG1 => "Fp[64]",
+            Precompile::Bls12381MapFp2ToG2 => "Fp2[128]",
+            Precompile::Bn254Add => "G1[64] | G1[64]",
+            Precompile::Bn254Mul => "G1[64] | Fr[32]",
+            Precompile::Bn254Pairing => "(G1[64] | G2[128])*",
+        }
+    }
+
+    /// Output layout of this precompile, big-endian with the byte lengths
+    pub const fn output_layout(self) -> &'static str {
+        match self {
+            Precompile::Bls12381G1Add
+            | Precompile::Bls12381G1Mul
+            | Precompile::Bls12381G1Msm
+            | Precompile::Bls12381MapFpToG1 => "G1[128]",
+            Precompile::Bls12381G2Add
+            | Precompile::Bls12381G2Mul
+            | Precompile::Bls12381G2Msm
+            | Precompile::Bls12381MapFp2ToG2 => "G2[256]",
+            Precompile::Bls12381Pairing | Precompile::Bn254Pairing => {
+                "u256[32], 1 if the product of pairings is one else 0"
+            }
+            Precompile::Bn254Add | Precompile::Bn254Mul => "G1[64]",
+        }
+    }
+}
+
+/// Byte lengths of a curve, names are UTF-8 bytes
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct CurveAbi {
+    pub curve_id: u32,
+    pub name: Vec<u8>,
+    pub g1_len: u32,
+    pub g2_len: u32,
+    pub g1_compressed_len: u32,
+    pub g2_compressed_len: u32,
+    pub scalar_len: u32,
+    pub gt_len: u32,
+    /// `CanonicalSerialize` lengths, see [`crate::serialization`]
+    pub g1_uncompressed_len: u32,
+    pub g2_uncompressed_len: u32,
+    pub scalar_canonical_len: u32,
+    /// Layout of the field elements of the points and scalars
+    pub encoding: Vec<u8>,
+}
+
+impl CurveAbi {
+    /// Byte lengths of curve `C`
+    pub fn of<C: PairingOperations>(name: &str) -> Self {
+        CurveAbi {
+            g2_len: C::G2_LEN as u32,
+            g2_compressed_len: compressed_len::<<C as PairingEngine>::G2Affine>() as u32,
+            gt_len: C::GT_LEN as u32,
+            g2_uncompressed_len: uncompressed_len::<<C as PairingEngine>::G2Affine>() as u32,
+            ..CurveAbi::of_group::<C>(name)
+        }
+    }
+
+    /// Byte lengths of group `C`, the lengths of G2 and the target group are
+    /// zero
+    pub fn of_group<C: GroupOperations>(name: &str) -> Self {
+        CurveAbi {
+            curve_id: C::CURVE_ID,
+            name: name.as_bytes().to_vec(),
+            g1_len: C::G1_LEN as u32,
+            g2_len: 0,
+            g1_compressed_len: compressed_len::<C::Affine>() as u32,
+            g2_compressed_len: 0,
+            scalar_len: C::SCALAR_LEN as u32,
+            gt_len: 0,
+            g1_uncompressed_len: uncompressed_len::<C::Affine>() as u32,
+            g2_uncompressed_len: 0,
+            scalar_canonical_len: Scalar::<C>::zero().serialized_size() as u32,
+            encoding: C::ENCODING.as_bytes().to_vec(),
+        }
+    }
+}
+
+/// Layouts of a function id, strings are UTF-8 bytes
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct FunctionAbi {
+    pub func_id: u32,
+    /// `None` for the EVM precompiles
+    pub version: Option<Version>,
+    pub name: Vec<u8>,
+    pub input: Vec<u8>,
+    pub output: Vec<u8>,
+}
+
+/// Machine-readable description of all function ids
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Abi {
+    pub version: Version,
+    pub curves: Vec<CurveAbi>,
+    pub functions: Vec<FunctionAbi>,
+}
+
+/// Describe the ABI
+pub fn describe() -> Abi {
+    let mut functions = Vec::new();
+    for version in Version::ALL {
+        for (curve, op) in registry::supported() {
+            functions.push(FunctionAbi {
+                func_id: version.func_id(op, curve as u32),
+                version: Some(*version),
+                name: format!("{}_{}", curve.name(), op.name()).into_bytes(),
+                input: op.input_layout().as_bytes().to_vec(),
+                output: op.output_layout(*version).as_bytes().to_vec(),
+            });
+        }
+    }
+    for precompile in Precompile::ALL {
+        functions.push(FunctionAbi {
+            func_id: precompile.func_id(),
+            version: None,
+            name: precompile.name().as_bytes().to_vec(),
+            input: precompile.input_layout().as_bytes().to_vec(),
+            output: precompile.output_layout().as_bytes().to_vec(),
+        });
+    }
+
+    Abi {
+        version: Version::LATEST,
+        curves: CurveId::ALL.iter().map(|c| c.describe()).collect(),
+        functions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions() {
+        assert_eq!(
+            decode(0x01000012).unwrap(),
+            (Version::Legacy, CurveId::Bls12_381, Operation::Pairing)
+        );
+        assert_eq!(
+            decode(0x03000022).unwrap(),
+            (Version::V1, CurveId::Bn254, Operation::Pairing)
+        );
+        assert!(decode(0x02000022).is_err());
+        assert!(decode(DESCRIBE).is_err());
+
+        // an empty product of pairings is one
+        let legacy = CurveId::Bn254.call_with(Version::Legacy, Operation::Pairing, &[]);
+        let v1 = CurveId::Bn254.call_with(Version::V1, Operation::Pairing, &[]);
+        assert_eq!(legacy.unwrap(), vec![0]);
+        assert_eq!(v1.unwrap(), vec![1]);
+        for version in Version::ALL {
+            for b in [true, false].iter() {
+                assert_eq!(version.decode_bool(&version.encode_bool(*b)).unwrap(), *b);
+            }
+            assert!(version.decode_bool(&[2]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_describe() {
+        let abi = describe();
+        assert_eq!(
+            Abi::decode(&mut &*registry::dispatch(DESCRIBE, &[]).unwrap()).unwrap(),
+            abi
+        );
+        assert_eq!(
+            abi.functions.len(),
+            Version::ALL.len() * registry::supported().len() + Precompile::ALL.len()
+        );
+        for function in abi.functions.iter().filter(|f| f.version.is_some()) {
+            let (version, ..) = decode(function.func_id).unwrap();
+            assert_eq!(Some(version), function.version);
+        }
+
+        let bn254 = abi.curves.iter().find(|c| c.name == b"Bn254").unwrap();
+        assert_eq!((bn254.g1_len, bn254.g1_compressed_len), (65, 32));
+        assert_eq!(bn254.gt_len, 384);
+        assert_eq!(
+            (bn254.g1_uncompressed_len, bn254.scalar_canonical_len),
+            (64, 32)
+        );
+
+        let secp256k1 = abi.curves.iter().find(|c| c.name == b"Secp256k1").unwrap();
+        assert_eq!((secp256k1.g1_len, secp256k1.scalar_len), (81, 40));
+        assert!(String::from_utf8(secp256k1.encoding.clone())
+            .unwrap()
+            .contains("40-byte"));
+    }
+}
@@ -0,0 +1,437 @@
+//! BLS signatures
+//!
+//! The schemes of the IETF BLS signature draft on the curves hashing to both
+//! groups, `Bls12_381` and `Bls12_377`, in two variants:
+//!
+//! - [`MinPk`], public keys in G1 and signatures in G2
+//! - [`MinSig`], public keys in G2 and signatures in G1
+//!
+//! Keys and signatures are the typed bytes of the ABI, points with the
+//! trailing infinity byte, the `AUG` prefixes and proofs of possession sign
+//! the compressed encoding of the draft instead, see [`point_to_octets`].
+//! Hashing to curve and the pairing checks go through [`crate::call`],
+//! contracts run them as chain extension calls. Signing is available with the
+//! `std` feature.
+
+#[cfg(feature = "std")]
+mod sign;
+pub mod threshold;
+
+#[cfg(feature = "std")]
+pub use sign::*;
+
+use ark_std::{fmt, marker::PhantomData, vec::Vec};
+
+use crate::{
+    error::{Error, Result},
+    ops::{
+        AffineCurve, CanonicalSerialize, Field, FpParameters, PairingEngine, PairingOperations,
+        PrimeField, Zero,
+    },
+    types::{self, G1Bytes, G2Bytes, ScalarBytes},
+};
+
+/// Signature schemes, the tags of the ciphersuite ids
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// `NUL`, the messages of an aggregate must be distinct
+    Basic,
+    /// `AUG`, messages are prefixed with the public key
+    MessageAugmentation,
+    /// `POP`, public keys are checked with proofs of possession
+    ProofOfPossession,
+}
+
+impl Scheme {
+    /// Tag of this scheme in the ciphersuite id
+    pub const fn tag(self) -> &'static str {
+        match self {
+            Scheme::Basic => "NUL_",
+            Scheme::MessageAugmentation => "AUG_",
+            Scheme::ProofOfPossession => "POP_",
+        }
+    }
+}
+
+/// Groups of public keys and signatures
+pub trait Variant<C: PairingOperations> {
+    type PublicKey: Clone + PartialEq + fmt::Debug + AsRef<[u8]>;
+    type Signature: Clone + PartialEq + fmt::Debug + AsRef<[u8]>;
+
+    /// Hash to curve suite id of the signature group
+    fn suite() -> Result<&'static str>;
+
+    /// Generator of the public key group
+    fn generator() -> Self::PublicKey;
+
+    /// Whether the public key is the identity
+    fn is_identity(pk: &Self::PublicKey) -> Result<bool>;
+
+    /// Compressed encoding of the public key, see [`point_to_octets`]
+    fn public_key_octets(pk: &Self::PublicKey) -> Result<Vec<u8>>;
+
+    fn neg_public_key(pk: &Self::PublicKey) -> Result<Self::PublicKey>;
+
+    fn add_public_keys(a: &Self::PublicKey, b: &Self::PublicKey) -> Result<Self::PublicKey>;
+
+    fn add_signatures(a: &Self::Signature, b: &Self::Signature) -> Result<Self::Signature>;
+
+    fn mul_public_key(pk: &Self::PublicKey, scalar: &ScalarBytes<C>) -> Result<Self::PublicKey>;
+
+    fn mul_signature(sig: &Self::Signature, scalar: &ScalarBytes<C>) -> Result<Self::Signature>;
+
+    /// Mul by the secret key, computed locally, see [`crate::ops::mul_secret`]
+    fn mul_secret_public_key(pk: &Self::PublicKey, sk: &ScalarBytes<C>) -> Result<Self::PublicKey>;
+
+    /// Mul by the secret key, computed locally, see [`crate::ops::mul_secret`]
+    fn mul_secret_signature(sig: &Self::Signature, sk: &ScalarBytes<C>) -> Result<Self::Signature>;
+
+    fn msm_public_keys(pairs: &[(Self::PublicKey, ScalarBytes<C>)]) -> Result<Self::PublicKey>;
+
+    fn msm_signatures(pairs: &[(Self::Signature, ScalarBytes<C>)]) -> Result<Self::Signature>;
+
+    /// Hash `msg` to the signature group with the domain separation tag `dst`
+    fn hash(dst: &[u8], msg: &[u8]) -> Result<Self::Signature>;
+
+    /// Check the product of the pairings of `(pk, sig)` pairs is one
+    fn pairing(pairs: &[(Self::PublicKey, Self::Signature)]) -> Result<bool>;
+}
+
+/// Public keys in G1, signatures in G2
+pub struct MinPk<C>(PhantomData<C>);
+
+/// Public keys in G2, signatures in G1
+pub struct MinSig<C>(PhantomData<C>);
+
+impl<C: PairingOperations> Variant<C> for MinPk<C> {
+    type PublicKey = G1Bytes<C>;
+    type Signature = G2Bytes<C>;
+
+    fn suite() -> Result<&'static str> {
+        C::HASH_TO_G2.ok_or(Error::UnsupportedOperation)
+    }
+
+    fn generator() -> G1Bytes<C> {
+        G1Bytes::from_affine(&AffineCurve::prime_subgroup_generator())
+    }
+
+    fn is_identity(pk: &G1Bytes<C>) -> Result<bool> {
+        Ok(pk.to_affine()?.is_zero())
+    }
+
+    fn public_key_octets(pk: &G1Bytes<C>) -> Result<Vec<u8>> {
+        point_to_octets(&pk.to_affine()?)
+    }
+
+    fn neg_public_key(pk: &G1Bytes<C>) -> Result<G1Bytes<C>> {
+        pk.neg()
+    }
+
+    fn add_public_keys(a: &G1Bytes<C>, b: &G1Bytes<C>) -> Result<G1Bytes<C>> {
+        types::add(a, b)
+    }
+
+    fn add_signatures(a: &G2Bytes<C>, b: &G2Bytes<C>) -> Result<G2Bytes<C>> {
+        types::g2_add(a, b)
+    }
+
+    fn mul_public_key(pk: &G1Bytes<C>, scalar: &ScalarBytes<C>) -> Result<G1Bytes<C>> {
+        types::mul(pk, scalar)
+    }
+
+    fn mul_signature(sig: &G2Bytes<C>, scalar: &ScalarBytes<C>) -> Result<G2Bytes<C>> {
+        types::g2_mul(sig, scalar)
+    }
+
+    fn mul_secret_public_key(pk: &G1Bytes<C>, sk: &ScalarBytes<C>) -> Result<G1Bytes<C>> {
+        types::mul_secret(pk, sk)
+    }
+
+    fn mul_secret_signature(sig: &G2Bytes<C>, sk: &ScalarBytes<C>) -> Result<G2Bytes<C>> {
+        types::g2_mul_secret(sig, sk)
+    }
+
+    fn msm_public_keys(pairs: &[(G1Bytes<C>, ScalarBytes<C>)]) -> Result<G1Bytes<C>> {
+        types::msm_g1(pairs)
+    }
+
+    fn msm_signatures(pairs: &[(G2Bytes<C>, ScalarBytes<C>)]) -> Result<G2Bytes<C>> {
+        types::msm_g2(pairs)
+    }
+
+    fn hash(dst: &[u8], msg: &[u8]) -> Result<G2Bytes<C>> {
+        types::hash_to_g2(dst, msg)
+    }
+
+    fn pairing(pairs: &[(G1Bytes<C>, G2Bytes<C>)]) -> Result<bool> {
+        types::pairing(pairs)
+    }
+}
+
+impl<C: PairingOperations> Variant<C> for MinSig<C> {
+    type PublicKey = G2Bytes<C>;
+    type Signature = G1Bytes<C>;
+
+    fn suite() -> Result<&'static str> {
+        C::HASH_TO_G1.ok_or(Error::UnsupportedOperation)
+    }
+
+    fn generator() -> G2Bytes<C> {
+        G2Bytes::from_affine(&AffineCurve::prime_subgroup_generator())
+    }
+
+    fn is_identity(pk: &G2Bytes<C>) -> Result<bool> {
+        Ok(pk.to_affine()?.is_zero())
+    }
+
+    fn public_key_octets(pk: &G2Bytes<C>) -> Result<Vec<u8>> {
+        point_to_octets(&pk.to_affine()?)
+    }
+
+    fn neg_public_key(pk: &G2Bytes<C>) -> Result<G2Bytes<C>> {
+        pk.neg()
+    }
+
+    fn add_public_keys(a: &G2Bytes<C>, b: &G2Bytes<C>) -> Result<G2Bytes<C>> {
+        types::g2_add(a, b)
+    }
+
+    fn add_signatures(a: &G1Bytes<C>, b: &G1Bytes<C>) -> Result<G1Bytes<C>> {
+        types::add(a, b)
+    }
+
+    fn mul_public_key(pk: &G2Bytes<C>, scalar: &ScalarBytes<C>) -> Result<G2Bytes<C>> {
+        types::g2_mul(pk, scalar)
+    }
+
+    fn mul_signature(sig: &G1Bytes<C>, scalar: &ScalarBytes<C>) -> Result<G1Bytes<C>> {
+        types::mul(sig, scalar)
+    }
+
+    fn mul_secret_public_key(pk: &G2Bytes<C>, sk: &ScalarBytes<C>) -> Result<G2Bytes<C>> {
+        types::g2_mul_secret(pk, sk)
+    }
+
+    fn mul_secret_signature(sig: &G1Bytes<C>, sk: &ScalarBytes<C>) -> Result<G1Bytes<C>> {
+        types::mul_secret(sig, sk)
+    }
+
+    fn msm_public_keys(pairs: &[(G2Bytes<C>, ScalarBytes<C>)]) -> Result<G2Bytes<C>> {
+        types::msm_g2(pairs)
+    }
+
+    fn msm_signatures(pairs: &[(G1Bytes<C>, ScalarBytes<C>)]) -> Result<G1Bytes<C>> {
+        types::msm_g1(pairs)
+    }
+
+    fn hash(dst: &[u8], msg: &[u8]) -> Result<G1Bytes<C>> {
+        types::hash_to_g1(dst, msg)
+    }
+
+    fn pairing(pairs: &[(G2Bytes<C>, G1Bytes<C>)]) -> Result<bool> {
+        let pairs = pairs
+            .iter()
+            .map(|(pk, sig)| (sig.clone(), pk.clone()))
+            .collect::<Vec<_>>();
+        types::pairing(&pairs)
+    }
+}
+
+/// Domain separation tag of signatures in `scheme`
+pub fn dst<C: PairingOperations, V: Variant<C>>(scheme: Scheme) -> Result<Vec<u8>> {
+    Ok([
+        "BLS_SIG_".as_bytes(),
+        V::suite()?.as_bytes(),
+        scheme.tag().as_bytes(),
+    ]
+    .concat())
+}
+
+/// Domain separation tag of proofs of possession
+pub fn pop_dst<C: PairingOperations, V: Variant<C>>() -> Result<Vec<u8>> {
+    Ok([
+        "BLS_POP_".as_bytes(),
+        V::suite()?.as_bytes(),
+        Scheme::ProofOfPossession.tag().as_bytes(),
+    ]
+    .concat())
+}
+
+/// `point_to_octets` of the draft, the compressed encoding of Zcash: `x`
+/// big-endian, `c1` before `c0` in G2, and the top three bits flag the
+/// compression, the identity and the larger of `y` and `-y`
+///
+/// Fields without three spare bits, such as of BN254, keep the arkworks
+/// compressed encoding, the draft has no ciphersuite on them.
+pub fn point_to_octets<G: AffineCurve>(point: &G) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    point.serialize(&mut bytes)?;
+
+    let bits = <<G::BaseField as Field>::BasePrimeField as PrimeField>::Params::MODULUS_BITS;
+    if (bits + 7) / 8 * 8 - bits < 3 {
+        return Ok(bytes);
+    }
+
+    // arkworks writes `x` little-endian with the identity in bit 6 and the
+    // larger `y` in bit 7 of the last byte
+    let last = bytes.len() - 1;
+    let flags = bytes[last];
+    bytes[last] &= 0x3f;
+    bytes.reverse();
+    bytes[0] |= 0x80;
+    if flags & 0x40 != 0 {
+        bytes[0] |= 0x40;
+    } else if flags & 0x80 != 0 {
+        bytes[0] |= 0x20;
+    }
+    Ok(bytes)
+}
+
+/// Message signed in `scheme`, prefixed with the octets of `pk` in `AUG`
+fn message<C: PairingOperations, V: Variant<C>>(
+    scheme: Scheme,
+    pk: &V::PublicKey,
+    msg: &[u8],
+) -> Result<Vec<u8>> {
+    Ok(match scheme {
+        Scheme::MessageAugmentation => [&V::public_key_octets(pk)?, msg].concat(),
+        _ => msg.to_vec(),
+    })
+}
+
+/// Check `e(pk_i, H(msg_i)) == e(g, sig)` over all pairs
+fn core_aggregate_verify<C: PairingOperations, V: Variant<C>>(
+    pks: &[V::PublicKey],
+    msgs: &[Vec<u8>],
+    sig: &V::Signature,
+    dst: &[u8],
+) -> Result<bool> {
+    if pks.is_empty() || pks.len() != msgs.len() {
+        return Ok(false);
+    }
+
+    let mut pairs = Vec::with_capacity(pks.len() + 1);
+    for (pk, msg) in pks.iter().zip(msgs) {
+        if V::is_identity(pk)? {
+            return Ok(false);
+        }
+        pairs.push((pk.clone(), V::hash(dst, msg)?));
+    }
+    pairs.push((V::neg_public_key(&V::generator())?, sig.clone()));
+    V::pairing(&pairs)
+}
+
+/// Verify the signature of `msg` by `pk`
+pub fn verify<C: PairingOperations, V: Variant<C>>(
+    scheme: Scheme,
+    pk: &V::PublicKey,
+    msg: &[u8],
+    sig: &V::Signature,
+) -> Result<bool> {
+    core_aggregate_verify::<C, V>(
+        &[pk.clone()],
+        &[message::<C, V>(scheme, pk, msg)?],
+        sig,
+        &dst::<C, V>(scheme)?,
+    )
+}
+
+/// Aggregate signatures or public keys with `add`
+fn aggregate_with<T: Clone>(items: &[T], add: fn(&T, &T) -> Result<T>) -> Result<T> {
+    let (first, rest) = items.split_first().ok_or(Error::EmptyAggregate)?;
+    rest.iter()
+        .try_fold(first.clone(), |acc, item| add(&acc, item))
+}
+
+/// Aggregate signatures
+pub fn aggregate<C: PairingOperations, V: Variant<C>>(
+    sigs: &[V::Signature],
+) -> Result<V::Signature> {
+    aggregate_with(sigs, V::add_signatures)
+}
+
+/// Aggregate public keys
+pub fn aggregate_public_keys<C: PairingOperations, V: Variant<C>>(
+    pks: &[V::PublicKey],
+) -> Result<V::PublicKey> {
+    aggregate_with(pks, V::add_public_keys)
+}
+
+/// Verify the aggregate signature of `msgs[i]` by `pks[i]`, the messages must
+/// be distinct in `Basic`
+pub fn aggregate_verify<C: PairingOperations, V: Variant<C>>(
+    scheme: Scheme,
+    pks: &[V::PublicKey],
+    msgs: &[&[u8]],
+    sig: &V::Signature,
+) -> Result<bool> {
+    if pks.len() != msgs.len() {
+        return Ok(false);
+    }
+    if scheme == Scheme::Basic
+        && msgs
+            .iter()
+            .enumerate()
+            .any(|(i, msg)| msgs[..i].contains(msg))
+    {
+        return Ok(false);
+    }
+
+    let msgs = pks
+        .iter()
+        .zip(msgs)
+        .map(|(pk, msg)| message::<C, V>(scheme, pk, msg))
+        .collect::<Result<Vec<_>>>()?;
+    core_aggregate_verify::<C, V>(pks, &msgs, sig, &dst::<C, V>(scheme)?)
+}
+
+/// Verify the aggregate signature of the same `msg` by `pks` in `POP`, the
+/// public keys must have been checked with [`pop_verify`]
+pub fn fast_aggregate_verify<C: PairingOperations, V: Variant<C>>(
+    pks: &[V::PublicKey],
+    msg: &[u8],
+    sig: &V::Signature,
+) -> Result<bool> {
+    let pk = match aggregate_public_keys::<C, V>(pks) {
+        Ok(pk) => pk,
+        Err(Error::EmptyAggregate) => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    verify::<C, V>(Scheme::ProofOfPossession, &pk, msg, sig)
+}
+
+/// Verify the proof of possession of `pk`
+pub fn pop_verify<C: PairingOperations, V: Variant<C>>(
+    pk: &V::PublicKey,
+    proof: &V::Signature,
+) -> Result<bool> {
+    core_aggregate_verify::<C, V>(
+        &[pk.clone()],
+        &[V::public_key_octets(pk)?],
+        proof,
+        &pop_dst::<C, V>()?,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dst() {
+        use crate::curve::{Bls12_381, Bn254};
+
+        assert_eq!(
+            dst::<Bls12_381, MinPk<Bls12_381>>(Scheme::ProofOfPossession).unwrap(),
+            b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_".to_vec()
+        );
+        assert_eq!(
+            pop_dst::<Bls12_381, MinSig<Bls12_381>>().unwrap(),
+            b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_".to_vec()
+        );
+        assert_eq!(
+            dst::<Bn254, MinPk<Bn254>>(Scheme::Basic),
+            Err(Error::UnsupportedOperation)
+        );
+    }
+}
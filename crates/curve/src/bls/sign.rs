@@ -0,0 +1,47 @@
+//! BLS signing
+
+use ark_std::UniformRand;
+use rand::Rng;
+
+use super::{dst, message, pop_dst, Scheme, Variant};
+use crate::{
+    error::Result,
+    ops::{PairingEngine, PairingOperations, Zero},
+    types::ScalarBytes,
+};
+
+/// Generate a nonzero secret key
+pub fn keygen<C: PairingOperations, R: Rng>(rng: &mut R) -> ScalarBytes<C> {
+    loop {
+        let sk = <C as PairingEngine>::Fr::rand(rng);
+        if !sk.is_zero() {
+            return ScalarBytes::from_scalar(&sk);
+        }
+    }
+}
+
+/// Public key of `sk`
+///
+/// The muls by `sk` here are computed locally with [`crate::ops::mul_secret`],
+/// the secret key never goes to the chain extension. See its docs for the
+/// scalars and the field arithmetic its constant time doesn't cover.
+pub fn sk_to_pk<C: PairingOperations, V: Variant<C>>(sk: &ScalarBytes<C>) -> Result<V::PublicKey> {
+    V::mul_secret_public_key(&V::generator(), sk)
+}
+
+/// Sign `msg` with `sk` in `scheme`
+pub fn sign<C: PairingOperations, V: Variant<C>>(
+    scheme: Scheme,
+    sk: &ScalarBytes<C>,
+    msg: &[u8],
+) -> Result<V::Signature> {
+    let msg = message::<C, V>(scheme, &sk_to_pk::<C, V>(sk)?, msg)?;
+    V::mul_secret_signature(&V::hash(&dst::<C, V>(scheme)?, &msg)?, sk)
+}
+
+/// Proof of possession of `sk`
+pub fn pop_prove<C: PairingOperations, V: Variant<C>>(sk: &ScalarBytes<C>) -> Result<V::Signature> {
+    let pk = sk_to_pk::<C, V>(sk)?;
+    let pk = V::public_key_octets(&pk)?;
+    V::mul_secret_signature(&V::hash(&pop_dst::<C, V>()?, &pk)?, sk)
+}
@@ -0,0 +1,167 @@
+//! Threshold BLS signatures
+//!
+//! A `t`-of-`n` secret key is shared as the evaluations `f(i)` of a polynomial
+//! `f` of degree `t - 1` at the nonzero indexes `i`, the group secret key is
+//! `f(0)`. Partial signatures of any `t` shares are combined into the
+//! signature of the group by Lagrange interpolation at zero, which is checked
+//! against the group public key as a plain signature.
+
+use ark_ff::Field;
+use ark_std::vec::Vec;
+
+use super::{verify, Scheme, Variant};
+use crate::{
+    error::{Error, Result},
+    ops::{One, PairingEngine, PairingOperations, Zero},
+    types::ScalarBytes,
+};
+
+/// Lagrange coefficients at zero of the shares at `indexes`
+///
+/// `l_i = prod(x_j / (x_j - x_i))` over `j != i`, the indexes must be nonzero
+/// and distinct.
+pub fn lagrange_coefficients<C: PairingOperations>(indexes: &[u32]) -> Result<Vec<ScalarBytes<C>>> {
+    for (i, index) in indexes.iter().enumerate() {
+        if *index == 0 || indexes[..i].contains(index) {
+            return Err(Error::InvalidShareIndex(*index));
+        }
+    }
+
+    let xs = indexes
+        .iter()
+        .map(|index| <C as PairingEngine>::Fr::from(*index as u64))
+        .collect::<Vec<_>>();
+    Ok(xs
+        .iter()
+        .map(|x_i| {
+            let (num, den) = xs.iter().filter(|x_j| *x_j != x_i).fold(
+                (
+                    <C as PairingEngine>::Fr::one(),
+                    <C as PairingEngine>::Fr::one(),
+                ),
+                |(num, den), x_j| (num * x_j, den * (*x_j - x_i)),
+            );
+            ScalarBytes::from_scalar(&(num * den.inverse().expect("indexes are distinct")))
+        })
+        .collect())
+}
+
+/// Verify the partial signature of `msg` by the public key share
+pub fn verify_share<C: PairingOperations, V: Variant<C>>(
+    scheme: Scheme,
+    pk_share: &V::PublicKey,
+    msg: &[u8],
+    partial: &V::Signature,
+) -> Result<bool> {
+    verify::<C, V>(scheme, pk_share, msg, partial)
+}
+
+/// Combine `(index, partial signature)` shares into the group signature
+pub fn combine<C: PairingOperations, V: Variant<C>>(
+    partials: &[(u32, V::Signature)],
+) -> Result<V::Signature> {
+    if partials.is_empty() {
+        return Err(Error::EmptyAggregate);
+    }
+    let indexes = partials.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+    let pairs = partials
+        .iter()
+        .map(|(_, sig)| sig.clone())
+        .zip(lagrange_coefficients::<C>(&indexes)?)
+        .collect::<Vec<_>>();
+    V::msm_signatures(&pairs)
+}
+
+/// Interpolate the group public key from `(index, public key share)` shares
+pub fn combine_public_keys<C: PairingOperations, V: Variant<C>>(
+    shares: &[(u32, V::PublicKey)],
+) -> Result<V::PublicKey> {
+    if shares.is_empty() {
+        return Err(Error::EmptyAggregate);
+    }
+    let indexes = shares.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+    let pairs = shares
+        .iter()
+        .map(|(_, pk)| pk.clone())
+        .zip(lagrange_coefficients::<C>(&indexes)?)
+        .collect::<Vec<_>>();
+    V::msm_public_keys(&pairs)
+}
+
+/// Combine the partial signatures of `msg` and verify the result against the
+/// group public key, the partials should be checked with [`verify_share`]
+/// first to find the bad shares
+pub fn verify_combined<C: PairingOperations, V: Variant<C>>(
+    scheme: Scheme,
+    group_pk: &V::PublicKey,
+    msg: &[u8],
+    partials: &[(u32, V::Signature)],
+) -> Result<bool> {
+    verify::<C, V>(scheme, group_pk, msg, &combine::<C, V>(partials)?)
+}
+
+/// Shares `f(1), ..., f(n)` of the polynomial of `coefficients`, `f(0)` is
+/// the group secret key
+#[cfg(feature = "std")]
+pub fn shares<C: PairingOperations>(
+    coefficients: &[ScalarBytes<C>],
+    n: u32,
+) -> Result<Vec<(u32, ScalarBytes<C>)>> {
+    let coefficients = coefficients
+        .iter()
+        .map(|c| c.to_scalar())
+        .collect::<Result<Vec<_>>>()?;
+    Ok((1..=n)
+        .map(|index| {
+            let x = <C as PairingEngine>::Fr::from(index as u64);
+            let y = coefficients
+                .iter()
+                .rev()
+                .fold(<C as PairingEngine>::Fr::zero(), |acc, c| acc * x + c);
+            (index, ScalarBytes::from_scalar(&y))
+        })
+        .collect())
+}
+
+/// Deal a random `t`-of-`n` secret key, returns the group secret key and the
+/// shares
+#[cfg(feature = "std")]
+pub fn deal<C: PairingOperations, R: rand::Rng>(
+    rng: &mut R,
+    t: u32,
+    n: u32,
+) -> Result<(ScalarBytes<C>, Vec<(u32, ScalarBytes<C>)>)> {
+    let coefficients = (0..t)
+        .map(|_| super::keygen::<C, _>(rng))
+        .collect::<Vec<_>>();
+    Ok((coefficients[0].clone(), shares(&coefficients, n)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lagrange_coefficients() {
+        use crate::curve::Bls12_381;
+        type Fr = <Bls12_381 as PairingEngine>::Fr;
+
+        // l_1 = 3, l_2 = -3, l_3 = 1
+        let coefficients = lagrange_coefficients::<Bls12_381>(&[1, 2, 3]).unwrap();
+        assert_eq!(
+            coefficients
+                .iter()
+                .map(|c| c.to_scalar().unwrap())
+                .collect::<Vec<_>>(),
+            vec![Fr::from(3u64), -Fr::from(3u64), Fr::one()]
+        );
+        assert_eq!(
+            lagrange_coefficients::<Bls12_381>(&[1, 0]),
+            Err(Error::InvalidShareIndex(0))
+        );
+        assert_eq!(
+            lagrange_coefficients::<Bls12_381>(&[2, 5, 2]),
+            Err(Error::InvalidShareIndex(2))
+        );
+    }
+}
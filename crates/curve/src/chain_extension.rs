@@ -0,0 +1,64 @@
+//! Runtime side of the chain extension for pallet-contracts
+//!
+//! [`ZkMegaExtension`] runs the function ids of `call` with the native
+//! operations. It charges the copy of the input, reads the SCALE encoded
+//! `Vec<u8>` input from the memory of the contract, charges the weight of the
+//! function id before running it, or a flat [`weights::INVALID_CALL`] if the
+//! id or the input is invalid, and writes back the SCALE encoded
+//! `Result<Vec<u8>, Error>`, which `call` decodes on the contract side.
+//!
+//! ```ignore
+//! impl pallet_contracts::Config for Runtime {
+//!     type ChainExtension = zkmega_arkworks::chain_extension::ZkMegaExtension;
+//!     // ...
+//! }
+//! ```
+//!
+//! Runtimes with other chain extensions can route the ids of
+//! [`registry::func_ids`] to [`ZkMegaExtension`].
+
+use pallet_contracts::chain_extension::{
+    ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use parity_scale_codec::{Decode, Encode};
+use sp_runtime::DispatchError;
+
+use crate::{
+    error::{Error, Result},
+    registry, weights, Vec,
+};
+
+/// zkMega chain extension
+pub struct ZkMegaExtension;
+
+impl ChainExtension for ZkMegaExtension {
+    fn call<E: Ext>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+    where
+        <E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+    {
+        let mut env = env.buf_in_buf_out();
+        // `read` doesn't charge the copy out of the contract memory
+        env.charge_weight(weights::READ_INPUT.weight(env.in_len() as u64))?;
+        let input = env.read(env.in_len())?;
+        let output = match <Vec<u8> as Decode>::decode(&mut &*input) {
+            Ok(input) => match weights::weight(func_id, &input) {
+                Ok(weight) => {
+                    env.charge_weight(weight)?;
+                    registry::dispatch(func_id, &input)
+                }
+                // invalid function ids fail without running anything
+                Err(e) => {
+                    env.charge_weight(weights::INVALID_CALL)?;
+                    Err(e)
+                }
+            },
+            Err(e) => {
+                env.charge_weight(weights::INVALID_CALL)?;
+                Err(Error::from(e))
+            }
+        };
+
+        env.write(&output.encode(), false, None)?;
+        Ok(RetVal::Converging(0))
+    }
+}
@@ -4,3 +4,33 @@ pub use ark_bls12_381::Bls12_381;
 pub use ark_bn254::Bn254;
 pub use ark_bw6_761::BW6_761;
 pub use ark_cp6_782::CP6_782;
+pub use ark_mnt4_298::MNT4_298;
+pub use ark_mnt4_753::MNT4_753;
+pub use ark_mnt6_298::MNT6_298;
+pub use ark_mnt6_753::MNT6_753;
+
+pub mod secp256k1;
+
+/// Curves without pairing, their operations are on the prime order group
+macro_rules! group_curves {
+    ($($(#[$doc:meta])* $curve:ident),* $(,)?) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct $curve;
+        )*
+    };
+}
+
+group_curves!(
+    /// Pallas of the Pasta cycle, its scalar field is the base field of Vesta
+    Pallas,
+    /// Vesta of the Pasta cycle, its scalar field is the base field of Pallas
+    Vesta,
+    /// secp256k1 of Ethereum signatures
+    Secp256k1,
+    /// Baby Jubjub, the twisted Edwards curve over the scalar field of BN254
+    EdOnBn254,
+    /// Jubjub, the twisted Edwards curve over the scalar field of BLS12-381
+    EdOnBls12_381,
+);
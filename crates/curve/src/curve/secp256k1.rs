@@ -0,0 +1,474 @@
+//! secp256k1 of SEC 2, the curve of Ethereum signatures
+//!
+//! `y^2 = x^3 + 7` over the prime field of `2^256 - 2^32 - 977`, arkworks 0.3
+//! doesn't ship the curve. The moduli use all 256 bits, which the arithmetic
+//! of `Fp256` can't carry, so the fields are `Fp320` with a zero top limb and
+//! elements are written as 40 bytes. They are in Montgomery form with
+//! `R = 2^320`.
+//!
+//! The ABI points are 81 bytes, `x | y | infinity`, and the scalars 40. The
+//! written elements are the little-endian integers, the 32 big-endian bytes
+//! of SEC1 reversed and 8 zero bytes on top, as the `encoding` of the ABI
+//! description says. [`from_sec1`], [`to_sec1`], [`scalar_from_be_bytes`] and
+//! [`scalar_to_be_bytes`] convert them from and to the SEC1 points and
+//! 32-byte big-endian scalars of Ethereum and other secp256k1 libraries.
+
+use ark_ec::{
+    models::{ModelParameters, SWModelParameters},
+    short_weierstrass_jacobian::{GroupAffine, GroupProjective},
+};
+use ark_ff::{
+    biginteger::BigInteger320 as BigInteger,
+    field_new,
+    fields::{FftParameters, Fp320, Fp320Parameters, FpParameters},
+    BigInteger as _, FromBytes, PrimeField, SquareRootField, ToBytes, Zero,
+};
+use ark_std::vec::Vec;
+
+use crate::{
+    curve::Secp256k1,
+    error::{Error, Result},
+    ops::{read_scalar, GroupOperations},
+};
+
+/// Fq = base field of secp256k1
+pub type Fq = Fp320<FqParameters>;
+
+pub struct FqParameters;
+
+impl Fp320Parameters for FqParameters {}
+
+impl FftParameters for FqParameters {
+    type BigInt = BigInteger;
+
+    const TWO_ADICITY: u32 = 1;
+
+    /// TWO_ADIC_ROOT_OF_UNITY = GENERATOR ^ T = 115792089237316195423570985008687907853269984665640564039457584007908834671662
+    #[rustfmt::skip]
+    const TWO_ADIC_ROOT_OF_UNITY: BigInteger = BigInteger([
+        0xfffffffefffffc2f,
+        0xfffffffefffffc2e,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x0000000000000000,
+    ]);
+}
+
+impl FpParameters for FqParameters {
+    /// MODULUS = 115792089237316195423570985008687907853269984665640564039457584007908834671663
+    #[rustfmt::skip]
+    const MODULUS: BigInteger = BigInteger([
+        0xfffffffefffffc2f,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x0000000000000000,
+    ]);
+
+    const MODULUS_BITS: u32 = 256;
+
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+
+    const REPR_SHAVE_BITS: u32 = 64;
+
+    /// R = pow(2, 320) % MODULUS
+    #[rustfmt::skip]
+    const R: BigInteger = BigInteger([
+        0x0000000000000000,
+        0x00000001000003d1,
+        0x0000000000000000,
+        0x0000000000000000,
+        0x0000000000000000,
+    ]);
+
+    /// R2 = R * R % MODULUS
+    #[rustfmt::skip]
+    const R2: BigInteger = BigInteger([
+        0x0000000000000000,
+        0x0000000000000000,
+        0x000007a2000e90a1,
+        0x0000000000000001,
+        0x0000000000000000,
+    ]);
+
+    /// INV = (-MODULUS) ^ {-1} % pow(2, 64)
+    const INV: u64 = 0xd838091dd2253531;
+
+    /// GENERATOR = 3
+    #[rustfmt::skip]
+    const GENERATOR: BigInteger = BigInteger([
+        0x0000000000000000,
+        0x0000000300000b73,
+        0x0000000000000000,
+        0x0000000000000000,
+        0x0000000000000000,
+    ]);
+
+    /// (MODULUS - 1) / 2
+    #[rustfmt::skip]
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0xffffffff7ffffe17,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x7fffffffffffffff,
+        0x0000000000000000,
+    ]);
+
+    /// T = (MODULUS - 1) / 2^s
+    #[rustfmt::skip]
+    const T: BigInteger = BigInteger([
+        0xffffffff7ffffe17,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x7fffffffffffffff,
+        0x0000000000000000,
+    ]);
+
+    /// (T - 1) / 2
+    #[rustfmt::skip]
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0xffffffffbfffff0b,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x3fffffffffffffff,
+        0x0000000000000000,
+    ]);
+}
+
+/// Fr = scalar field of secp256k1
+pub type Fr = Fp320<FrParameters>;
+
+pub struct FrParameters;
+
+impl Fp320Parameters for FrParameters {}
+
+impl FftParameters for FrParameters {
+    type BigInt = BigInteger;
+
+    const TWO_ADICITY: u32 = 6;
+
+    /// TWO_ADIC_ROOT_OF_UNITY = GENERATOR ^ T = 5480320495727936603795231718619559942670027629901634955707709633242980176626
+    #[rustfmt::skip]
+    const TWO_ADIC_ROOT_OF_UNITY: BigInteger = BigInteger([
+        0x0112cb0f605a214a,
+        0x92225daffb794500,
+        0x7e42003a6ccb6212,
+        0x55980b07bc222114,
+        0x0000000000000000,
+    ]);
+}
+
+impl FpParameters for FrParameters {
+    /// MODULUS = 115792089237316195423570985008687907852837564279074904382605163141518161494337
+    #[rustfmt::skip]
+    const MODULUS: BigInteger = BigInteger([
+        0xbfd25e8cd0364141,
+        0xbaaedce6af48a03b,
+        0xfffffffffffffffe,
+        0xffffffffffffffff,
+        0x0000000000000000,
+    ]);
+
+    const MODULUS_BITS: u32 = 256;
+
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+
+    const REPR_SHAVE_BITS: u32 = 64;
+
+    /// R = pow(2, 320) % MODULUS
+    #[rustfmt::skip]
+    const R: BigInteger = BigInteger([
+        0x0000000000000000,
+        0x402da1732fc9bebf,
+        0x4551231950b75fc4,
+        0x0000000000000001,
+        0x0000000000000000,
+    ]);
+
+    /// R2 = R * R % MODULUS
+    #[rustfmt::skip]
+    const R2: BigInteger = BigInteger([
+        0x1e004f504dfd7f79,
+        0x08fcf59774a052ea,
+        0x27c4120fc94e1653,
+        0x3c1a6191e5702644,
+        0x0000000000000000,
+    ]);
+
+    /// INV = (-MODULUS) ^ {-1} % pow(2, 64)
+    const INV: u64 = 0x4b0dff665588b13f;
+
+    /// GENERATOR = 7
+    #[rustfmt::skip]
+    const GENERATOR: BigInteger = BigInteger([
+        0x0000000000000000,
+        0xc13f6a264e843739,
+        0xe537f5b135039e5d,
+        0x0000000000000008,
+        0x0000000000000000,
+    ]);
+
+    /// (MODULUS - 1) / 2
+    #[rustfmt::skip]
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0xdfe92f46681b20a0,
+        0x5d576e7357a4501d,
+        0xffffffffffffffff,
+        0x7fffffffffffffff,
+        0x0000000000000000,
+    ]);
+
+    /// T = (MODULUS - 1) / 2^s
+    #[rustfmt::skip]
+    const T: BigInteger = BigInteger([
+        0xeeff497a3340d905,
+        0xfaeabb739abd2280,
+        0xffffffffffffffff,
+        0x03ffffffffffffff,
+        0x0000000000000000,
+    ]);
+
+    /// (T - 1) / 2
+    #[rustfmt::skip]
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+        0x777fa4bd19a06c82,
+        0xfd755db9cd5e9140,
+        0xffffffffffffffff,
+        0x01ffffffffffffff,
+        0x0000000000000000,
+    ]);
+}
+
+pub type Affine = GroupAffine<Parameters>;
+pub type Projective = GroupProjective<Parameters>;
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Parameters;
+
+impl ModelParameters for Parameters {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+}
+
+impl SWModelParameters for Parameters {
+    /// COEFF_A = 0
+    const COEFF_A: Fq = field_new!(Fq, "0");
+
+    /// COEFF_B = 7
+    const COEFF_B: Fq = field_new!(Fq, "7");
+
+    /// COFACTOR = 1
+    const COFACTOR: &'static [u64] = &[0x1];
+
+    /// COFACTOR_INV = COFACTOR^{-1} mod r = 1
+    const COFACTOR_INV: Fr = field_new!(Fr, "1");
+
+    /// AFFINE_GENERATOR_COEFFS = (G_GENERATOR_X, G_GENERATOR_Y)
+    const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
+        (G_GENERATOR_X, G_GENERATOR_Y);
+
+    #[inline(always)]
+    fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
+        Self::BaseField::zero()
+    }
+}
+
+/// G_GENERATOR_X = 55066263022277343669578718895168534326250603453777594175500187360389116729240
+pub const G_GENERATOR_X: Fq = field_new!(
+    Fq,
+    "55066263022277343669578718895168534326250603453777594175500187360389116729240"
+);
+
+/// G_GENERATOR_Y = 32670510020758816978083085130507043184471273380659243275938904335757337482424
+pub const G_GENERATOR_Y: Fq = field_new!(
+    Fq,
+    "32670510020758816978083085130507043184471273380659243275938904335757337482424"
+);
+
+/// Read a field element of 32 big-endian bytes
+fn read_be<F: FromBytes>(bytes: &[u8]) -> Result<F> {
+    let mut le = [0u8; 40];
+    le[..32].copy_from_slice(bytes);
+    le[..32].reverse();
+    Ok(F::read(&le[..])?)
+}
+
+/// 32 big-endian bytes of a field element
+fn write_be<F: ToBytes>(f: &F, output: &mut Vec<u8>) {
+    let mut le = Vec::with_capacity(40);
+    f.write(&mut le).expect("write to vec never fails");
+    output.extend(le[..32].iter().rev());
+}
+
+/// Read a SEC1 point, `0x00` for the identity, `0x02 | x` or `0x03 | x`
+/// compressed with the parity of `y`, or `0x04 | x | y` uncompressed
+pub fn from_sec1(bytes: &[u8]) -> Result<Affine> {
+    let expected = match bytes.first() {
+        Some(0x00) => 1,
+        Some(0x02) | Some(0x03) => 33,
+        Some(0x04) => 65,
+        Some(_) => return Err(Error::SerializeDataFailed),
+        None => return Err(Error::InputTooShort),
+    };
+    Error::check_length(expected, bytes.len())?;
+    if bytes[0] == 0x00 {
+        return Ok(Affine::zero());
+    }
+
+    let x: Fq = read_be(&bytes[1..33])?;
+    let point = if bytes[0] == 0x04 {
+        Affine::new(x, read_be(&bytes[33..])?, false)
+    } else {
+        let y = (x * x * x + Parameters::COEFF_B)
+            .sqrt()
+            .ok_or(Error::PointNotOnCurve)?;
+        let odd = bytes[0] == 0x03;
+        Affine::new(x, if y.into_repr().is_odd() == odd { y } else { -y }, false)
+    };
+    Secp256k1::validate_g1(&point)?;
+    Ok(point)
+}
+
+/// SEC1 bytes of `point`, see [`from_sec1`]
+pub fn to_sec1(point: &Affine, compressed: bool) -> Vec<u8> {
+    if point.is_zero() {
+        return vec![0x00];
+    }
+    let mut bytes = Vec::with_capacity(65);
+    if compressed {
+        bytes.push(0x02 | point.y.into_repr().is_odd() as u8);
+        write_be(&point.x, &mut bytes);
+    } else {
+        bytes.push(0x04);
+        write_be(&point.x, &mut bytes);
+        write_be(&point.y, &mut bytes);
+    }
+    bytes
+}
+
+/// Read a scalar of 32 big-endian bytes, fails if it is not less than the
+/// group order
+pub fn scalar_from_be_bytes(bytes: &[u8]) -> Result<Fr> {
+    Error::check_length(32, bytes.len())?;
+    let mut le = [0u8; 40];
+    le[..32].copy_from_slice(bytes);
+    le[..32].reverse();
+    read_scalar(&le)
+}
+
+/// 32 big-endian bytes of `scalar`
+pub fn scalar_to_be_bytes(scalar: &Fr) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(32);
+    write_be(&scalar.into_repr(), &mut bytes);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secp256k1() {
+        use ark_ec::{AffineCurve, ProjectiveCurve};
+        use ark_ff::{Field, FpParameters, One, PrimeField, SquareRootField};
+
+        let g = Affine::prime_subgroup_generator();
+        assert!(g.is_on_curve());
+        assert!(g.mul(<Fr as PrimeField>::Params::MODULUS).is_zero());
+
+        // 2G
+        let x = field_new!(
+            Fq,
+            "89565891926547004231252920425935692360644145829622209833684329913297188986597"
+        );
+        let y = field_new!(
+            Fq,
+            "12158399299693830322967808612713398636155367887041628176798871954788371653930"
+        );
+        assert_eq!(
+            g.into_projective().double().into_affine(),
+            Affine::new(x, y, false)
+        );
+
+        assert_eq!(x * x.inverse().unwrap(), Fq::one());
+        assert_eq!((x * x).sqrt().map(|y| y * y), Some(x * x));
+        let r = Fr::from(7u64);
+        assert_eq!(r * r.inverse().unwrap(), Fr::one());
+        assert_eq!((r * r).sqrt().map(|y| y * y), Some(r * r));
+
+        // SEC1
+        let g_x = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        let g_y = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+        let g_sec1 = hex::decode(format!("04{}{}", g_x, g_y)).unwrap();
+        assert_eq!(from_sec1(&g_sec1).unwrap(), g);
+        assert_eq!(to_sec1(&g, false), g_sec1);
+        assert_eq!(
+            to_sec1(&g, true),
+            hex::decode(format!("02{}", g_x)).unwrap()
+        );
+        assert_eq!(from_sec1(&to_sec1(&g, true)).unwrap(), g);
+        assert_eq!(from_sec1(&to_sec1(&-g, true)).unwrap(), -g);
+        assert_eq!(to_sec1(&-g, true)[0], 0x03);
+        let g2 = Affine::new(x, y, false);
+        assert_eq!(
+            to_sec1(&g2, true),
+            hex::decode("02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5")
+                .unwrap()
+        );
+        assert_eq!(from_sec1(&[0]).unwrap(), Affine::zero());
+        assert_eq!(to_sec1(&Affine::zero(), true), vec![0]);
+        assert_eq!(from_sec1(&[]), Err(Error::InputTooShort));
+        assert_eq!(from_sec1(&[0x05; 33]), Err(Error::SerializeDataFailed));
+        assert_eq!(
+            from_sec1(&g_sec1[..33]),
+            Err(Error::InvalidLength {
+                expected: 65,
+                got: 33
+            })
+        );
+        let mut off_curve = g_sec1.clone();
+        off_curve[64] ^= 1;
+        assert_eq!(from_sec1(&off_curve), Err(Error::PointNotOnCurve));
+        assert_eq!(
+            from_sec1(&[&[0x02][..], &[0xff; 32]].concat()),
+            Err(Error::FieldElementOutOfRange)
+        );
+
+        // the ABI layout of the SEC1 values
+        let abi = |be: &str| {
+            let mut le = hex::decode(be).unwrap();
+            le.reverse();
+            le.resize(40, 0);
+            le
+        };
+        let g_abi = [abi(g_x), abi(g_y), vec![0]].concat();
+        let g2_abi = [
+            abi("c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5"),
+            abi("1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a"),
+            vec![0],
+        ]
+        .concat();
+        assert_eq!(g_abi.len(), Secp256k1::G1_LEN);
+        assert_eq!(
+            Secp256k1::add(&[&g_abi[..], &g_abi].concat()).unwrap(),
+            g2_abi
+        );
+        let two = abi(&format!("{:064x}", 2));
+        assert_eq!(two.len(), Secp256k1::SCALAR_LEN);
+        assert_eq!(
+            Secp256k1::mul(&[&g_abi[..], &two].concat()).unwrap(),
+            g2_abi
+        );
+
+        // 32-byte scalars
+        let mut seven = vec![0; 32];
+        seven[31] = 7;
+        assert_eq!(scalar_from_be_bytes(&seven).unwrap(), r);
+        assert_eq!(scalar_to_be_bytes(&r), seven);
+        assert_eq!(
+            scalar_from_be_bytes(&[0xff; 32]),
+            Err(Error::ScalarOutOfRange)
+        );
+        assert!(scalar_from_be_bytes(&[0; 40]).is_err());
+    }
+}
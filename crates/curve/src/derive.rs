@@ -1,12 +1,127 @@
 //! Impl ops for curves
 
+use alloc::boxed::Box;
+use once_cell::race::OnceBox;
 use rand::Rng;
 
 use ark_ec::PairingEngine;
-use ark_ff::{Field, One, PrimeField};
+use ark_ff::{Field, One, PrimeField, ToBytes};
 use ark_std::{ops::MulAssign, test_rng, vec::Vec};
 
-use crate::CurveBasicOperations;
+use crate::{
+    fixed_base::FixedBaseTable,
+    map_to_curve,
+    ops::{
+        hash_to_curve, validate, validate_te, AffineCurve, ConditionalSwap, Decompress,
+        GroupOperations, PairingOperations, ReadPoint,
+    },
+    Result,
+};
+
+/// Validate G1 points with the short Weierstrass model checks, or with
+/// `$validate`
+macro_rules! validate_g1 {
+    () => {
+        validate_g1!(validate);
+    };
+    ($validate:ident) => {
+        fn validate_g1(point: &Self::Affine) -> Result<()> {
+            $validate(point)
+        }
+    };
+}
+
+/// Validate G2 points with the short Weierstrass model checks
+macro_rules! validate_g2 {
+    () => {
+        fn validate_g2(point: &<Self as PairingEngine>::G2Affine) -> Result<()> {
+            validate(point)
+        }
+    };
+}
+
+/// Swap G1 points by the limbs of their coordinates
+macro_rules! swap_g1 {
+    () => {
+        fn swap_g1(
+            a: &mut <Self::Affine as AffineCurve>::Projective,
+            b: &mut <Self::Affine as AffineCurve>::Projective,
+            choice: bool,
+        ) {
+            ConditionalSwap::conditional_swap(a, b, choice)
+        }
+    };
+}
+
+/// Swap G2 points by the limbs of their coordinates
+macro_rules! swap_g2 {
+    () => {
+        fn swap_g2(
+            a: &mut <Self as PairingEngine>::G2Projective,
+            b: &mut <Self as PairingEngine>::G2Projective,
+            choice: bool,
+        ) {
+            ConditionalSwap::conditional_swap(a, b, choice)
+        }
+    };
+}
+
+/// Decompress G1 points of the short Weierstrass or twisted Edwards model
+macro_rules! decompress_g1 {
+    () => {
+        fn decompress_g1(input: &mut &[u8]) -> Result<Self::Affine> {
+            Decompress::decompress(input)
+        }
+    };
+}
+
+/// Decompress G2 points of the short Weierstrass model
+macro_rules! decompress_g2 {
+    () => {
+        fn decompress_g2(input: &mut &[u8]) -> Result<<Self as PairingEngine>::G2Affine> {
+            Decompress::decompress(input)
+        }
+    };
+}
+
+/// Read G1 points of the short Weierstrass or twisted Edwards model
+macro_rules! read_g1 {
+    () => {
+        fn read_g1_unchecked(bytes: &[u8]) -> Result<Self::Affine> {
+            ReadPoint::read_point(bytes)
+        }
+    };
+}
+
+/// Read G2 points of the short Weierstrass model
+macro_rules! read_g2 {
+    () => {
+        fn read_g2_unchecked(bytes: &[u8]) -> Result<<Self as PairingEngine>::G2Affine> {
+            ReadPoint::read_point(bytes)
+        }
+    };
+}
+
+/// Window table of the G1 generator of `$affine` points, statics can't name
+/// `Self`
+macro_rules! g1_generator_table {
+    ($affine:ty) => {
+        fn g1_generator_table() -> &'static FixedBaseTable<$affine> {
+            static TABLE: OnceBox<FixedBaseTable<$affine>> = OnceBox::new();
+            TABLE.get_or_init(|| Box::new(FixedBaseTable::generator()))
+        }
+    };
+}
+
+/// Window table of the G2 generator of `$affine` points
+macro_rules! g2_generator_table {
+    ($affine:ty) => {
+        fn g2_generator_table() -> &'static FixedBaseTable<$affine> {
+            static TABLE: OnceBox<FixedBaseTable<$affine>> = OnceBox::new();
+            TABLE.get_or_init(|| Box::new(FixedBaseTable::generator()))
+        }
+    };
+}
 
 /// Paste pairing test
 macro_rules! paste_test {
@@ -38,14 +153,73 @@ macro_rules! paste_test {
             assert_eq!(ans2.pow(Fr::characteristic()), $fq::one());
             assert_eq!(ans3.pow(Fr::characteristic()), $fq::one());
         }
+
+        #[test]
+        fn test_field_constants() {
+            use ark_ff::{BigInteger, FpParameters};
+            use num_bigint::BigUint;
+
+            assert_eq!(
+                BigUint::parse_bytes($curve::SCALAR_FIELD.as_bytes(), 10).unwrap(),
+                BigUint::from_bytes_le(&<Fr as PrimeField>::Params::MODULUS.to_bytes_le())
+            );
+            assert_eq!(
+                BigUint::from_bytes_le($curve::MODULUS),
+                BigUint::from_bytes_le(
+                    &<<$curve as PairingEngine>::Fq as PrimeField>::Params::MODULUS.to_bytes_le()
+                )
+            );
+            assert_eq!(
+                $curve::SCALAR_LEN,
+                <Fr as PrimeField>::BigInt::NUM_LIMBS * 8
+            );
+
+            let mut gt = Vec::new();
+            $fq::one().write(&mut gt).unwrap();
+            assert_eq!($curve::GT_LEN, gt.len());
+        }
+    };
+}
+
+/// Paste group test
+macro_rules! group_test {
+    ($curve:ident) => {
+        #[test]
+        fn test_field_constants() {
+            use ark_ec::AffineCurve;
+            use ark_ff::{BigInteger, FpParameters};
+            use num_bigint::BigUint;
+
+            type Affine = <$curve as GroupOperations>::Affine;
+            type Fq = <Affine as AffineCurve>::BaseField;
+
+            assert_eq!(
+                BigUint::parse_bytes($curve::SCALAR_FIELD.as_bytes(), 10).unwrap(),
+                BigUint::from_bytes_le(&<Fr as PrimeField>::Params::MODULUS.to_bytes_le())
+            );
+            assert_eq!(
+                BigUint::from_bytes_le($curve::MODULUS),
+                BigUint::from_bytes_le(&<Fq as PrimeField>::Params::MODULUS.to_bytes_le())
+            );
+            assert_eq!(
+                $curve::SCALAR_LEN,
+                <Fr as PrimeField>::BigInt::NUM_LIMBS * 8
+            );
+
+            let mut g1 = Vec::new();
+            Affine::prime_subgroup_generator().write(&mut g1).unwrap();
+            assert_eq!($curve::G1_LEN, g1.len());
+        }
     };
 }
 
 mod bls12_377 {
     use super::*;
-    use ark_bls12_377::{Bls12_377, Fq12, Fr, G1Projective, G2Projective};
+    use ark_bls12_377::{Bls12_377, Fq12, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+
+    impl GroupOperations for Bls12_377 {
+        type Affine = <Self as PairingEngine>::G1Affine;
 
-    impl CurveBasicOperations for Bls12_377 {
         const SCALAR_FIELD: &'static str =
             "8444461749428370424248824938781546531375899335154063827935233455917409239041";
         // const MODULUS: &'static str = "258664426012969094010652733694893533536393512754914660539884262666720468348340822774968888139573360124440321458177";
@@ -55,9 +229,35 @@ mod bls12_377 {
             23, 70, 58, 174, 1,
         ];
         const G1_LEN: usize = 97;
-        const G2_LEN: usize = 193;
         const SCALAR_LEN: usize = 32;
         const CURVE_ID: u32 = 0x00;
+        const HASH_TO_G1: Option<&'static str> = Some("BLS12377G1_XMD:SHA-256_SVDW_RO_");
+
+        validate_g1!();
+        g1_generator_table!(G1Affine);
+        swap_g1!();
+        decompress_g1!();
+        read_g1!();
+
+        fn hash_to_g1(input: &[u8]) -> Result<Vec<u8>> {
+            hash_to_curve(input, map_to_curve::bls12_377::hash_to_g1)
+        }
+    }
+
+    impl PairingOperations for Bls12_377 {
+        const G2_LEN: usize = 193;
+        const GT_LEN: usize = 576;
+        const HASH_TO_G2: Option<&'static str> = Some("BLS12377G2_XMD:SHA-256_SVDW_RO_");
+
+        validate_g2!();
+        g2_generator_table!(G2Affine);
+        swap_g2!();
+        decompress_g2!();
+        read_g2!();
+
+        fn hash_to_g2(input: &[u8]) -> Result<Vec<u8>> {
+            hash_to_curve(input, map_to_curve::bls12_377::hash_to_g2)
+        }
     }
 
     paste_test!(Bls12_377, Fq12);
@@ -65,9 +265,11 @@ mod bls12_377 {
 
 mod bls12_381 {
     use super::*;
-    use ark_bls12_381::{Bls12_381, Fq12, Fr, G1Projective, G2Projective};
+    use ark_bls12_381::{Bls12_381, Fq12, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+
+    impl GroupOperations for Bls12_381 {
+        type Affine = <Self as PairingEngine>::G1Affine;
 
-    impl CurveBasicOperations for Bls12_381 {
         const SCALAR_FIELD: &'static str =
             "52435875175126190479447740508185965837690552500527637822603658699938581184513";
         // const MODULUS: &'static str = "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559787";
@@ -77,9 +279,35 @@ mod bls12_381 {
             182, 167, 27, 75, 154, 230, 127, 57, 234, 17, 1, 26,
         ];
         const G1_LEN: usize = 97;
-        const G2_LEN: usize = 193;
         const SCALAR_LEN: usize = 32;
         const CURVE_ID: u32 = 0x10;
+        const HASH_TO_G1: Option<&'static str> = Some("BLS12381G1_XMD:SHA-256_SSWU_RO_");
+
+        validate_g1!();
+        g1_generator_table!(G1Affine);
+        swap_g1!();
+        decompress_g1!();
+        read_g1!();
+
+        fn hash_to_g1(input: &[u8]) -> Result<Vec<u8>> {
+            hash_to_curve(input, map_to_curve::bls12_381::hash_to_g1)
+        }
+    }
+
+    impl PairingOperations for Bls12_381 {
+        const G2_LEN: usize = 193;
+        const GT_LEN: usize = 576;
+        const HASH_TO_G2: Option<&'static str> = Some("BLS12381G2_XMD:SHA-256_SSWU_RO_");
+
+        validate_g2!();
+        g2_generator_table!(G2Affine);
+        swap_g2!();
+        decompress_g2!();
+        read_g2!();
+
+        fn hash_to_g2(input: &[u8]) -> Result<Vec<u8>> {
+            hash_to_curve(input, map_to_curve::bls12_381::hash_to_g2)
+        }
     }
 
     paste_test!(Bls12_381, Fq12);
@@ -87,9 +315,11 @@ mod bls12_381 {
 
 mod bn254 {
     use super::*;
-    use ark_bn254::{Bn254, Fq12, Fr, G1Projective, G2Projective};
+    use ark_bn254::{Bn254, Fq12, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+
+    impl GroupOperations for Bn254 {
+        type Affine = <Self as PairingEngine>::G1Affine;
 
-    impl CurveBasicOperations for Bn254 {
         const SCALAR_FIELD: &'static str =
             "21888242871839275222246405745257275088548364400416034343698204186575808495617";
         // const MODULUS: &'static str = "21888242871839275222246405745257275088696311157297823662689037894645226208583";
@@ -98,9 +328,30 @@ mod bn254 {
             129, 129, 182, 69, 80, 184, 41, 160, 49, 225, 114, 78, 100, 48,
         ];
         const G1_LEN: usize = 65;
-        const G2_LEN: usize = 129;
         const SCALAR_LEN: usize = 32;
         const CURVE_ID: u32 = 0x20;
+        const HASH_TO_G1: Option<&'static str> = Some("BN254G1_XMD:SHA-256_SVDW_RO_");
+
+        validate_g1!();
+        g1_generator_table!(G1Affine);
+        swap_g1!();
+        decompress_g1!();
+        read_g1!();
+
+        fn hash_to_g1(input: &[u8]) -> Result<Vec<u8>> {
+            hash_to_curve(input, map_to_curve::bn254::hash_to_g1)
+        }
+    }
+
+    impl PairingOperations for Bn254 {
+        const G2_LEN: usize = 129;
+        const GT_LEN: usize = 384;
+
+        validate_g2!();
+        g2_generator_table!(G2Affine);
+        swap_g2!();
+        decompress_g2!();
+        read_g2!();
     }
 
     paste_test!(Bn254, Fq12);
@@ -108,10 +359,12 @@ mod bn254 {
 
 mod bw6_761 {
     use super::*;
-    use ark_bw6_761::{Fq6, Fr, G1Projective, G2Projective, BW6_761};
+    use ark_bw6_761::{Fq6, Fr, G1Affine, G1Projective, G2Affine, G2Projective, BW6_761};
 
-    impl CurveBasicOperations for BW6_761 {
-        const SCALAR_FIELD: &'static str = "6891450384315732539396789682275657542479668912536150109513790160209623422243491736087683183289411687640864567753786613451161759120554247759349511699125301598951605099378508850372543631423596795951899700429969112842764913119068299";
+    impl GroupOperations for BW6_761 {
+        type Affine = <Self as PairingEngine>::G1Affine;
+
+        const SCALAR_FIELD: &'static str = "258664426012969094010652733694893533536393512754914660539884262666720468348340822774968888139573360124440321458177";
         // const MODULUS: &'static str = "6891450384315732539396789682275657542479668912536150109513790160209623422243491736087683183289411687640864567753786613451161759120554247759349511699125301598951605099378508850372543631423596795951899700429969112842764913119068299";
         const MODULUS: &'static [u8] = &[
             139, 0, 0, 0, 0, 0, 157, 244, 130, 0, 0, 112, 104, 62, 145, 230, 55, 164, 240, 234,
@@ -122,9 +375,25 @@ mod bw6_761 {
             34, 1,
         ];
         const G1_LEN: usize = 193;
-        const G2_LEN: usize = 193;
         const SCALAR_LEN: usize = 48;
         const CURVE_ID: u32 = 0x30;
+
+        validate_g1!();
+        g1_generator_table!(G1Affine);
+        swap_g1!();
+        decompress_g1!();
+        read_g1!();
+    }
+
+    impl PairingOperations for BW6_761 {
+        const G2_LEN: usize = 193;
+        const GT_LEN: usize = 576;
+
+        validate_g2!();
+        g2_generator_table!(G2Affine);
+        swap_g2!();
+        decompress_g2!();
+        read_g2!();
     }
 
     paste_test!(BW6_761, Fq6);
@@ -132,10 +401,12 @@ mod bw6_761 {
 
 mod cp6_782 {
     use super::*;
-    use ark_cp6_782::{Fq6, Fr, G1Projective, G2Projective, CP6_782};
+    use ark_cp6_782::{Fq6, Fr, G1Affine, G1Projective, G2Affine, G2Projective, CP6_782};
+
+    impl GroupOperations for CP6_782 {
+        type Affine = <Self as PairingEngine>::G1Affine;
 
-    impl CurveBasicOperations for CP6_782 {
-        const SCALAR_FIELD: &'static str = "22369874298875696930346742206501054934775599465297184582183496627646774052458024540232479018147881220178054575403841904557897715222633333372134756426301062487682326574958588001132586331462553235407484089304633076250782629492557320825577";
+        const SCALAR_FIELD: &'static str = "258664426012969094010652733694893533536393512754914660539884262666720468348340822774968888139573360124440321458177";
         // const MODULUS: &'static str = "22369874298875696930346742206501054934775599465297184582183496627646774052458024540232479018147881220178054575403841904557897715222633333372134756426301062487682326574958588001132586331462553235407484089304633076250782629492557320825577";
         const MODULUS: &'static [u8] = &[
             233, 42, 148, 123, 181, 121, 206, 218, 74, 66, 253, 109, 193, 133, 93, 84, 183, 38, 77,
@@ -146,10 +417,332 @@ mod cp6_782 {
             72, 56,
         ];
         const G1_LEN: usize = 209;
-        const G2_LEN: usize = 625;
         const SCALAR_LEN: usize = 48;
-        const CURVE_ID: u32 = 0x4;
+        const CURVE_ID: u32 = 0x40;
+
+        validate_g1!();
+        g1_generator_table!(G1Affine);
+        swap_g1!();
+        decompress_g1!();
+        read_g1!();
+    }
+
+    impl PairingOperations for CP6_782 {
+        const G2_LEN: usize = 625;
+        const GT_LEN: usize = 624;
+
+        validate_g2!();
+        g2_generator_table!(G2Affine);
+        swap_g2!();
+        decompress_g2!();
+        read_g2!();
     }
 
     paste_test!(CP6_782, Fq6);
 }
+
+mod mnt4_298 {
+    use super::*;
+    use ark_mnt4_298::{Fq4, Fr, G1Affine, G1Projective, G2Affine, G2Projective, MNT4_298};
+
+    impl GroupOperations for MNT4_298 {
+        type Affine = <Self as PairingEngine>::G1Affine;
+
+        const SCALAR_FIELD: &'static str = "475922286169261325753349249653048451545124878552823515553267735739164647307408490559963137";
+        // const MODULUS: &'static str = "475922286169261325753349249653048451545124879242694725395555128576210262817955800483758081";
+        const MODULUS: &'static [u8] = &[
+            1, 0, 102, 113, 90, 214, 12, 201, 18, 14, 32, 81, 94, 227, 169, 65, 234, 48, 19, 93,
+            99, 201, 238, 202, 142, 84, 176, 167, 157, 36, 102, 162, 115, 212, 188, 247, 188, 3,
+        ];
+        const G1_LEN: usize = 81;
+        const SCALAR_LEN: usize = 40;
+        const CURVE_ID: u32 = 0x50;
+
+        validate_g1!();
+        g1_generator_table!(G1Affine);
+        swap_g1!();
+        decompress_g1!();
+        read_g1!();
+    }
+
+    impl PairingOperations for MNT4_298 {
+        const G2_LEN: usize = 161;
+        const GT_LEN: usize = 160;
+
+        validate_g2!();
+        g2_generator_table!(G2Affine);
+        swap_g2!();
+        decompress_g2!();
+        read_g2!();
+    }
+
+    paste_test!(MNT4_298, Fq4);
+}
+
+mod mnt6_298 {
+    use super::*;
+    use ark_mnt6_298::{Fq6, Fr, G1Affine, G1Projective, G2Affine, G2Projective, MNT6_298};
+
+    impl GroupOperations for MNT6_298 {
+        type Affine = <Self as PairingEngine>::G1Affine;
+
+        const SCALAR_FIELD: &'static str = "475922286169261325753349249653048451545124879242694725395555128576210262817955800483758081";
+        // const MODULUS: &'static str = "475922286169261325753349249653048451545124878552823515553267735739164647307408490559963137";
+        const MODULUS: &'static [u8] = &[
+            1, 0, 0, 0, 164, 52, 67, 187, 211, 106, 93, 146, 7, 76, 73, 251, 148, 65, 244, 92, 99,
+            201, 238, 202, 142, 84, 176, 167, 157, 36, 102, 162, 115, 212, 188, 247, 188, 3,
+        ];
+        const G1_LEN: usize = 81;
+        const SCALAR_LEN: usize = 40;
+        const CURVE_ID: u32 = 0x60;
+
+        validate_g1!();
+        g1_generator_table!(G1Affine);
+        swap_g1!();
+        decompress_g1!();
+        read_g1!();
+    }
+
+    impl PairingOperations for MNT6_298 {
+        const G2_LEN: usize = 241;
+        const GT_LEN: usize = 240;
+
+        validate_g2!();
+        g2_generator_table!(G2Affine);
+        swap_g2!();
+        decompress_g2!();
+        read_g2!();
+    }
+
+    paste_test!(MNT6_298, Fq6);
+}
+
+mod mnt4_753 {
+    use super::*;
+    use ark_mnt4_753::{Fq4, Fr, G1Affine, G1Projective, G2Affine, G2Projective, MNT4_753};
+
+    impl GroupOperations for MNT4_753 {
+        type Affine = <Self as PairingEngine>::G1Affine;
+
+        const SCALAR_FIELD: &'static str = "41898490967918953402344214791240637128170709919953949071783502921025352812571106773058893763790338921418070971888458477323173057491593855069696241854796396165721416325350064441470418137846398469611935719059908164220784476160001";
+        // const MODULUS: &'static str = "41898490967918953402344214791240637128170709919953949071783502921025352812571106773058893763790338921418070971888253786114353726529584385201591605722013126468931404347949840543007986327743462853720628051692141265303114721689601";
+        const MODULUS: &'static [u8] = &[
+            1, 128, 94, 36, 222, 99, 144, 94, 159, 17, 221, 44, 82, 84, 157, 227, 240, 37, 196,
+            154, 113, 16, 136, 99, 164, 84, 114, 118, 233, 204, 90, 104, 56, 126, 83, 203, 165, 13,
+            15, 184, 157, 5, 24, 242, 118, 231, 23, 177, 157, 247, 90, 161, 217, 36, 209, 153, 141,
+            237, 160, 232, 37, 185, 253, 7, 115, 216, 151, 108, 249, 232, 183, 94, 237, 175, 143,
+            91, 80, 151, 249, 183, 173, 205, 226, 238, 34, 144, 34, 16, 17, 196, 146, 45, 198, 196,
+            1,
+        ];
+        const G1_LEN: usize = 193;
+        const SCALAR_LEN: usize = 96;
+        const CURVE_ID: u32 = 0x70;
+
+        validate_g1!();
+        g1_generator_table!(G1Affine);
+        swap_g1!();
+        decompress_g1!();
+        read_g1!();
+    }
+
+    impl PairingOperations for MNT4_753 {
+        const G2_LEN: usize = 385;
+        const GT_LEN: usize = 384;
+
+        validate_g2!();
+        g2_generator_table!(G2Affine);
+        swap_g2!();
+        decompress_g2!();
+        read_g2!();
+    }
+
+    paste_test!(MNT4_753, Fq4);
+}
+
+mod mnt6_753 {
+    use super::*;
+    use ark_mnt6_753::{Fq6, Fr, G1Affine, G1Projective, G2Affine, G2Projective, MNT6_753};
+
+    impl GroupOperations for MNT6_753 {
+        type Affine = <Self as PairingEngine>::G1Affine;
+
+        const SCALAR_FIELD: &'static str = "41898490967918953402344214791240637128170709919953949071783502921025352812571106773058893763790338921418070971888253786114353726529584385201591605722013126468931404347949840543007986327743462853720628051692141265303114721689601";
+        // const MODULUS: &'static str = "41898490967918953402344214791240637128170709919953949071783502921025352812571106773058893763790338921418070971888458477323173057491593855069696241854796396165721416325350064441470418137846398469611935719059908164220784476160001";
+        const MODULUS: &'static [u8] = &[
+            1, 0, 0, 64, 226, 118, 7, 217, 79, 58, 161, 15, 23, 153, 160, 78, 151, 87, 0, 63, 188,
+            129, 195, 214, 164, 58, 153, 52, 118, 249, 223, 185, 54, 38, 33, 41, 148, 202, 235, 62,
+            155, 169, 89, 200, 40, 92, 108, 178, 157, 247, 90, 161, 217, 36, 209, 153, 141, 237,
+            160, 232, 37, 185, 253, 7, 115, 216, 151, 108, 249, 232, 183, 94, 237, 175, 143, 91,
+            80, 151, 249, 183, 173, 205, 226, 238, 34, 144, 34, 16, 17, 196, 146, 45, 198, 196, 1,
+        ];
+        const G1_LEN: usize = 193;
+        const SCALAR_LEN: usize = 96;
+        const CURVE_ID: u32 = 0x80;
+
+        validate_g1!();
+        g1_generator_table!(G1Affine);
+        swap_g1!();
+        decompress_g1!();
+        read_g1!();
+    }
+
+    impl PairingOperations for MNT6_753 {
+        const G2_LEN: usize = 577;
+        const GT_LEN: usize = 576;
+
+        validate_g2!();
+        g2_generator_table!(G2Affine);
+        swap_g2!();
+        decompress_g2!();
+        read_g2!();
+    }
+
+    paste_test!(MNT6_753, Fq6);
+}
+
+mod pallas {
+    use super::*;
+    use crate::curve::Pallas;
+    use ark_pallas::{Affine, Fr};
+
+    impl GroupOperations for Pallas {
+        type Affine = Affine;
+
+        const SCALAR_FIELD: &'static str =
+            "28948022309329048855892746252171976963363056481941647379679742748393362948097";
+        // const MODULUS: &'static str = "28948022309329048855892746252171976963363056481941560715954676764349967630337";
+        const MODULUS: &'static [u8] = &[
+            1, 0, 0, 0, 237, 48, 45, 153, 27, 249, 76, 9, 252, 152, 70, 34, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 64,
+        ];
+        const G1_LEN: usize = 65;
+        const SCALAR_LEN: usize = 32;
+        const CURVE_ID: u32 = 0x90;
+
+        validate_g1!();
+        g1_generator_table!(Affine);
+        swap_g1!();
+        decompress_g1!();
+        read_g1!();
+    }
+
+    group_test!(Pallas);
+}
+
+mod vesta {
+    use super::*;
+    use crate::curve::Vesta;
+    use ark_vesta::{Affine, Fr};
+
+    impl GroupOperations for Vesta {
+        type Affine = Affine;
+
+        const SCALAR_FIELD: &'static str =
+            "28948022309329048855892746252171976963363056481941560715954676764349967630337";
+        // const MODULUS: &'static str = "28948022309329048855892746252171976963363056481941647379679742748393362948097";
+        const MODULUS: &'static [u8] = &[
+            1, 0, 0, 0, 33, 235, 70, 140, 221, 168, 148, 9, 252, 152, 70, 34, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 64,
+        ];
+        const G1_LEN: usize = 65;
+        const SCALAR_LEN: usize = 32;
+        const CURVE_ID: u32 = 0xa0;
+
+        validate_g1!();
+        g1_generator_table!(Affine);
+        swap_g1!();
+        decompress_g1!();
+        read_g1!();
+    }
+
+    group_test!(Vesta);
+}
+
+mod secp256k1 {
+    use super::*;
+    use crate::curve::secp256k1::{Affine, Fr};
+    use crate::curve::Secp256k1;
+
+    impl GroupOperations for Secp256k1 {
+        type Affine = Affine;
+
+        const SCALAR_FIELD: &'static str =
+            "115792089237316195423570985008687907852837564279074904382605163141518161494337";
+        // const MODULUS: &'static str = "115792089237316195423570985008687907853269984665640564039457584007908834671663";
+        const MODULUS: &'static [u8] = &[
+            47, 252, 255, 255, 254, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+            255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+        ];
+        const G1_LEN: usize = 81;
+        const SCALAR_LEN: usize = 40;
+        const CURVE_ID: u32 = 0xb0;
+        const ENCODING: &'static str = "Fp320, 40-byte little-endian integers, the 32-byte \
+            big-endian values of SEC1 reversed and 8 zero bytes on top";
+
+        validate_g1!();
+        g1_generator_table!(Affine);
+        swap_g1!();
+        decompress_g1!();
+        read_g1!();
+    }
+
+    group_test!(Secp256k1);
+}
+
+mod ed_on_bn254 {
+    use super::*;
+    use crate::curve::EdOnBn254;
+    use ark_ed_on_bn254::{EdwardsAffine, Fr};
+
+    impl GroupOperations for EdOnBn254 {
+        type Affine = EdwardsAffine;
+
+        const SCALAR_FIELD: &'static str =
+            "2736030358979909402780800718157159386076813972158567259200215660948447373041";
+        // const MODULUS: &'static str = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+        const MODULUS: &'static [u8] = &[
+            1, 0, 0, 240, 147, 245, 225, 67, 145, 112, 185, 121, 72, 232, 51, 40, 93, 88, 129, 129,
+            182, 69, 80, 184, 41, 160, 49, 225, 114, 78, 100, 48,
+        ];
+        const G1_LEN: usize = 64;
+        const SCALAR_LEN: usize = 32;
+        const CURVE_ID: u32 = 0xc0;
+
+        validate_g1!(validate_te);
+        g1_generator_table!(EdwardsAffine);
+        swap_g1!();
+        decompress_g1!();
+        read_g1!();
+    }
+
+    group_test!(EdOnBn254);
+}
+
+mod ed_on_bls12_381 {
+    use super::*;
+    use crate::curve::EdOnBls12_381;
+    use ark_ed_on_bls12_381::{EdwardsAffine, Fr};
+
+    impl GroupOperations for EdOnBls12_381 {
+        type Affine = EdwardsAffine;
+
+        const SCALAR_FIELD: &'static str =
+            "6554484396890773809930967563523245729705921265872317281365359162392183254199";
+        // const MODULUS: &'static str = "52435875175126190479447740508185965837690552500527637822603658699938581184513";
+        const MODULUS: &'static [u8] = &[
+            1, 0, 0, 0, 255, 255, 255, 255, 254, 91, 254, 255, 2, 164, 189, 83, 5, 216, 161, 9, 8,
+            216, 57, 51, 72, 125, 157, 41, 83, 167, 237, 115,
+        ];
+        const G1_LEN: usize = 64;
+        const SCALAR_LEN: usize = 32;
+        const CURVE_ID: u32 = 0xd0;
+
+        validate_g1!(validate_te);
+        g1_generator_table!(EdwardsAffine);
+        swap_g1!();
+        decompress_g1!();
+        read_g1!();
+    }
+
+    group_test!(EdOnBls12_381);
+}
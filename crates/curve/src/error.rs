@@ -1,7 +1,7 @@
 //! Curve Result
 
 use ark_serialize::SerializationError;
-use ark_std::{fmt, string::String, vec::Vec};
+use ark_std::{fmt, io, string::String, vec::Vec};
 
 use parity_scale_codec::{Decode, Encode};
 
@@ -9,18 +9,107 @@ use parity_scale_codec::{Decode, Encode};
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
 /// Curve Error
-#[derive(Debug, Encode, Decode)]
+///
+/// The variants are SCALE encoded by their index, new variants go to the end.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub enum Error {
     InvalidFunctionId,
     SerializeDataFailed,
     ScaleCodecError,
     VerifyParcelFailed,
     Custom(Vec<u8>),
+    /// Input bytes length mismatch
+    InvalidLength {
+        expected: u32,
+        got: u32,
+    },
+    /// Point is not on curve
+    PointNotOnCurve,
+    /// Point is on curve but not in the prime order subgroup
+    PointNotInSubgroup,
+    /// Scalar is not less than the subgroup order
+    ScalarOutOfRange,
+    /// Field element is not less than the modulus
+    FieldElementOutOfRange,
+    /// Public inputs count doesn't match the verifying key
+    PublicInputCountMismatch {
+        expected: u32,
+        got: u32,
+    },
+    /// Curve id not supported
+    UnsupportedCurve(u32),
+    /// Operation not supported on the curve
+    UnsupportedOperation,
+    /// Aggregate of no signatures or public keys
+    EmptyAggregate,
+    /// Threshold share index is zero or repeated
+    InvalidShareIndex(u32),
+    /// Zero has no inverse
+    NotInvertible,
+    /// Element of `Fqk` is not in the target group
+    NotInTargetGroup,
+    /// Input ended before the item read
+    InputTooShort,
+    /// Infinity flag byte of a point is neither `0` nor `1`
+    InvalidPointEncoding,
 }
 
+impl Error {
+    /// Check `got` bytes length equals `expected`
+    pub fn check_length(expected: usize, got: usize) -> Result<()> {
+        if expected != got {
+            return Err(Error::InvalidLength {
+                expected: expected as u32,
+                got: got as u32,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check `got` bytes length is a multiple of `unit`, the `expected` of the
+    /// error is the nearest smaller multiple
+    pub fn check_length_multiple(unit: usize, got: usize) -> Result<()> {
+        Error::check_length(got - got % unit, got)
+    }
+
+    /// `e` of a read whose invalid data can only be `invalid`
+    pub fn from_invalid_data(e: SerializationError, invalid: Error) -> Error {
+        match e {
+            SerializationError::InvalidData => invalid,
+            e => e.into(),
+        }
+    }
+
+    /// DEBUG string format
+    pub fn debug(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+/// Invalid data is any of the point and field checks, see
+/// [`Error::from_invalid_data`] where the caller knows which
 impl From<SerializationError> for Error {
     fn from(e: SerializationError) -> Self {
-        Error::SerializeDataFailed
+        match e {
+            SerializationError::NotEnoughSpace => Error::InputTooShort,
+            SerializationError::IoError(e) => e.into(),
+            SerializationError::InvalidData | SerializationError::UnexpectedFlags => {
+                Error::SerializeDataFailed
+            }
+        }
+    }
+}
+
+/// `FromBytes` of arkworks fails with `Other` on field elements not less than
+/// the modulus, and on infinity flags other than `0` and `1`, which the point
+/// reads check first, see [`crate::ops::ReadPoint`]
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::UnexpectedEof => Error::InputTooShort,
+            io::ErrorKind::Other | io::ErrorKind::InvalidData => Error::FieldElementOutOfRange,
+            _ => Error::SerializeDataFailed,
+        }
     }
 }
 
@@ -44,13 +133,102 @@ impl fmt::Display for Error {
             Self::ScaleCodecError => f.write_str("scale codec error"),
             Self::VerifyParcelFailed => f.write_str("verify parcel failed"),
             Self::Custom(msg) => write!(f, "{}", String::from_utf8_lossy(msg)),
+            Self::InvalidLength { expected, got } => write!(
+                f,
+                "invalid input length, should be {}, input length: {}",
+                expected, got
+            ),
+            Self::PointNotOnCurve => f.write_str("point is not on curve"),
+            Self::PointNotInSubgroup => f.write_str("point is not in subgroup"),
+            Self::ScalarOutOfRange => f.write_str("scalar out of range"),
+            Self::FieldElementOutOfRange => f.write_str("field element out of range"),
+            Self::PublicInputCountMismatch { expected, got } => write!(
+                f,
+                "public inputs count mismatch, should be {}, got: {}",
+                expected, got
+            ),
+            Self::UnsupportedCurve(id) => write!(f, "unsupported curve {:#x}", id),
+            Self::UnsupportedOperation => f.write_str("unsupported operation"),
+            Self::EmptyAggregate => f.write_str("empty aggregate"),
+            Self::InvalidShareIndex(index) => write!(f, "invalid share index {}", index),
+            Self::NotInvertible => f.write_str("zero has no inverse"),
+            Self::NotInTargetGroup => f.write_str("element is not in the target group"),
+            Self::InputTooShort => f.write_str("input too short"),
+            Self::InvalidPointEncoding => f.write_str("invalid point encoding"),
         }
     }
 }
 
-impl Error {
-    /// DEBUG string format
-    pub fn debug(&self) -> String {
-        format!("{:?}", self)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codec() {
+        for e in [
+            Error::InvalidFunctionId,
+            Error::InvalidLength {
+                expected: 194,
+                got: 193,
+            },
+            Error::PointNotInSubgroup,
+            Error::PublicInputCountMismatch {
+                expected: 1,
+                got: 2,
+            },
+            Error::UnsupportedCurve(0x50),
+            Error::InputTooShort,
+            Error::InvalidPointEncoding,
+        ]
+        .iter()
+        {
+            assert_eq!(&Error::decode(&mut &*e.encode()).unwrap(), e);
+        }
+
+        // the indexes of the legacy variants are stable
+        assert_eq!(Error::VerifyParcelFailed.encode(), vec![3]);
+        assert_eq!(Error::InvalidPointEncoding.encode(), vec![18]);
+
+        assert_eq!(
+            Error::from(SerializationError::NotEnoughSpace),
+            Error::InputTooShort
+        );
+        assert_eq!(
+            Error::from_invalid_data(SerializationError::InvalidData, Error::PointNotOnCurve),
+            Error::PointNotOnCurve
+        );
+        assert_eq!(
+            Error::from(io::Error::new(
+                io::ErrorKind::Other,
+                "FromBytes::read failed"
+            )),
+            Error::FieldElementOutOfRange
+        );
+
+        // the point reads tell the infinity flag from the field elements
+        {
+            use crate::{curve::Bn254, GroupOperations, PairingOperations};
+            use ark_ec::AffineCurve;
+            use ark_ff::ToBytes;
+
+            let mut g1 = Vec::new();
+            <Bn254 as GroupOperations>::Affine::prime_subgroup_generator()
+                .write(&mut g1)
+                .unwrap();
+            assert!(Bn254::read_g1(&g1).is_ok());
+            g1[64] = 2;
+            assert_eq!(Bn254::read_g1(&g1), Err(Error::InvalidPointEncoding));
+            assert_eq!(
+                Bn254::read_g1_unchecked(&g1),
+                Err(Error::InvalidPointEncoding)
+            );
+            g1[64] = 0;
+            g1[..32].copy_from_slice(&[0xff; 32]);
+            assert_eq!(Bn254::read_g1(&g1), Err(Error::FieldElementOutOfRange));
+
+            let mut g2 = vec![0; Bn254::G2_LEN];
+            g2[Bn254::G2_LEN - 1] = 0xff;
+            assert_eq!(Bn254::read_g2(&g2), Err(Error::InvalidPointEncoding));
+        }
     }
 }
@@ -0,0 +1,350 @@
+//! EIP-2537 on BLS12-381
+//!
+//! * field elements are 64 bytes big-endian, the top 16 bytes must be zero
+//! * Fp2 elements are `c0 | c1`
+//! * G1 points are `x | y`, G2 points are `x | y` over Fp2, all zeros is the
+//!   point at infinity
+//! * scalars are 32 bytes big-endian
+//! * points of MUL, MSM and PAIRING must be in the prime order subgroup
+
+use ark_bls12_381::{Bls12_381, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::{
+    msm::VariableBaseMSM, short_weierstrass_jacobian::GroupAffine, AffineCurve, PairingEngine,
+    ProjectiveCurve, SWModelParameters,
+};
+use ark_ff::{BigInteger, BigInteger384, FromBytes, One, PrimeField, Zero};
+use ark_std::vec::Vec;
+
+use crate::{
+    error::{Error, Result},
+    map_to_curve::bls12_381::{
+        clear_cofactor_g1, clear_cofactor_g2, map_to_curve_g1, map_to_curve_g2,
+    },
+};
+
+/// Padded field element bytes length
+const FQ_LEN: usize = 64;
+/// Zero padding of field elements
+const FQ_PADDING: usize = 16;
+/// Fp2 bytes length
+const FQ2_LEN: usize = FQ_LEN * 2;
+/// G1 bytes length
+const G1_LEN: usize = FQ_LEN * 2;
+/// G2 bytes length
+const G2_LEN: usize = FQ2_LEN * 2;
+/// Scalar bytes length
+const SCALAR_LEN: usize = 32;
+
+/// Check the input length is a positive multiple of `len`
+fn check_multiple_len(input: &[u8], len: usize) -> Result<()> {
+    if input.is_empty() {
+        return Err(Error::InvalidLength {
+            expected: len as u32,
+            got: 0,
+        });
+    }
+    Error::check_length_multiple(len, input.len())
+}
+
+/// Read padded big-endian field element, fails if it is not less than the
+/// modulus
+fn read_fq(bytes: &[u8]) -> Result<Fq> {
+    if bytes[..FQ_PADDING].iter().any(|b| *b != 0) {
+        return Err(Error::FieldElementOutOfRange);
+    }
+
+    let mut le = bytes[FQ_PADDING..].to_vec();
+    le.reverse();
+    Fq::from_repr(BigInteger384::read(&*le)?).ok_or(Error::FieldElementOutOfRange)
+}
+
+fn read_fq2(bytes: &[u8]) -> Result<Fq2> {
+    Ok(Fq2::new(
+        read_fq(&bytes[0..FQ_LEN])?,
+        read_fq(&bytes[FQ_LEN..FQ2_LEN])?,
+    ))
+}
+
+/// Read big-endian scalar, reduced by the subgroup order
+fn read_scalar(bytes: &[u8]) -> Fr {
+    Fr::from_be_bytes_mod_order(bytes)
+}
+
+/// Build point from coordinates, `(0, 0)` is the point at infinity
+fn point<P: SWModelParameters>(
+    x: P::BaseField,
+    y: P::BaseField,
+    subgroup_check: bool,
+) -> Result<GroupAffine<P>> {
+    if x.is_zero() && y.is_zero() {
+        return Ok(GroupAffine::zero());
+    }
+
+    let point = GroupAffine::new(x, y, false);
+    if !point.is_on_curve() {
+        return Err(Error::PointNotOnCurve);
+    }
+    if subgroup_check && !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Error::PointNotInSubgroup);
+    }
+    Ok(point)
+}
+
+fn read_g1(bytes: &[u8], subgroup_check: bool) -> Result<G1Affine> {
+    point(
+        read_fq(&bytes[0..FQ_LEN])?,
+        read_fq(&bytes[FQ_LEN..G1_LEN])?,
+        subgroup_check,
+    )
+}
+
+fn read_g2(bytes: &[u8], subgroup_check: bool) -> Result<G2Affine> {
+    point(
+        read_fq2(&bytes[0..FQ2_LEN])?,
+        read_fq2(&bytes[FQ2_LEN..G2_LEN])?,
+        subgroup_check,
+    )
+}
+
+fn write_fq(output: &mut Vec<u8>, fq: Fq) {
+    output.extend_from_slice(&[0; FQ_PADDING]);
+    output.extend_from_slice(&fq.into_repr().to_bytes_be());
+}
+
+fn write_g1(point: G1Affine) -> Vec<u8> {
+    if point.is_zero() {
+        return vec![0; G1_LEN];
+    }
+
+    let mut output = Vec::with_capacity(G1_LEN);
+    write_fq(&mut output, point.x);
+    write_fq(&mut output, point.y);
+    output
+}
+
+fn write_g2(point: G2Affine) -> Vec<u8> {
+    if point.is_zero() {
+        return vec![0; G2_LEN];
+    }
+
+    let mut output = Vec::with_capacity(G2_LEN);
+    for fq in [point.x.c0, point.x.c1, point.y.c0, point.y.c1].iter() {
+        write_fq(&mut output, *fq);
+    }
+    output
+}
+
+/// EIP-2537 BLS12_G1ADD
+///
+/// input: `G1 | G1`, 256 bytes, no subgroup check
+pub fn g1_add(input: &[u8]) -> Result<Vec<u8>> {
+    Error::check_length(G1_LEN * 2, input.len())?;
+    let point1 = read_g1(&input[0..G1_LEN], false)?;
+    let point2 = read_g1(&input[G1_LEN..], false)?;
+
+    Ok(write_g1(point1 + point2))
+}
+
+/// EIP-2537 BLS12_G1MUL
+///
+/// input: `G1 | scalar`, 160 bytes
+pub fn g1_mul(input: &[u8]) -> Result<Vec<u8>> {
+    Error::check_length(G1_LEN + SCALAR_LEN, input.len())?;
+    let point = read_g1(&input[0..G1_LEN], true)?;
+    let scalar = read_scalar(&input[G1_LEN..]);
+
+    Ok(write_g1(point.mul(scalar).into_affine()))
+}
+
+/// EIP-2537 BLS12_G1MSM
+///
+/// input: `(G1 | scalar)*`, at least one pair
+pub fn g1_msm(input: &[u8]) -> Result<Vec<u8>> {
+    let pair_len = G1_LEN + SCALAR_LEN;
+    check_multiple_len(input, pair_len)?;
+
+    let mut bases = Vec::with_capacity(input.len() / pair_len);
+    let mut scalars = Vec::with_capacity(input.len() / pair_len);
+    for pair in input.chunks(pair_len) {
+        bases.push(read_g1(&pair[0..G1_LEN], true)?);
+        scalars.push(read_scalar(&pair[G1_LEN..]).into_repr());
+    }
+
+    Ok(write_g1(
+        VariableBaseMSM::multi_scalar_mul(&bases, &scalars).into_affine(),
+    ))
+}
+
+/// EIP-2537 BLS12_G2ADD
+///
+/// input: `G2 | G2`, 512 bytes, no subgroup check
+pub fn g2_add(input: &[u8]) -> Result<Vec<u8>> {
+    Error::check_length(G2_LEN * 2, input.len())?;
+    let point1 = read_g2(&input[0..G2_LEN], false)?;
+    let point2 = read_g2(&input[G2_LEN..], false)?;
+
+    Ok(write_g2(point1 + point2))
+}
+
+/// EIP-2537 BLS12_G2MUL
+///
+/// input: `G2 | scalar`, 288 bytes
+pub fn g2_mul(input: &[u8]) -> Result<Vec<u8>> {
+    Error::check_length(G2_LEN + SCALAR_LEN, input.len())?;
+    let point = read_g2(&input[0..G2_LEN], true)?;
+    let scalar = read_scalar(&input[G2_LEN..]);
+
+    Ok(write_g2(point.mul(scalar).into_affine()))
+}
+
+/// EIP-2537 BLS12_G2MSM
+///
+/// input: `(G2 | scalar)*`, at least one pair
+pub fn g2_msm(input: &[u8]) -> Result<Vec<u8>> {
+    let pair_len = G2_LEN + SCALAR_LEN;
+    check_multiple_len(input, pair_len)?;
+
+    let mut bases = Vec::with_capacity(input.len() / pair_len);
+    let mut scalars = Vec::with_capacity(input.len() / pair_len);
+    for pair in input.chunks(pair_len) {
+        bases.push(read_g2(&pair[0..G2_LEN], true)?);
+        scalars.push(read_scalar(&pair[G2_LEN..]).into_repr());
+    }
+
+    Ok(write_g2(
+        VariableBaseMSM::multi_scalar_mul(&bases, &scalars).into_affine(),
+    ))
+}
+
+/// EIP-2537 BLS12_PAIRING
+///
+/// input: `(G1 | G2)*`, at least one pair, returns 32 bytes big-endian `1`
+/// if the product of pairings is one, else `0`
+pub fn pairing(input: &[u8]) -> Result<Vec<u8>> {
+    let g1_g2_len = G1_LEN + G2_LEN;
+    check_multiple_len(input, g1_g2_len)?;
+
+    let mut pairings = Vec::with_capacity(input.len() / g1_g2_len);
+    for pair in input.chunks(g1_g2_len) {
+        let g1 = read_g1(&pair[0..G1_LEN], true)?;
+        let g2 = read_g2(&pair[G1_LEN..], true)?;
+        pairings.push((g1.into(), g2.into()));
+    }
+
+    let mut output = vec![0; 32];
+    if <Bls12_381 as PairingEngine>::product_of_pairings(&pairings)
+        == <Bls12_381 as PairingEngine>::Fqk::one()
+    {
+        output[31] = 1;
+    }
+    Ok(output)
+}
+
+/// EIP-2537 BLS12_MAP_FP_TO_G1
+///
+/// input: `Fp`, 64 bytes
+pub fn map_fp_to_g1(input: &[u8]) -> Result<Vec<u8>> {
+    Error::check_length(FQ_LEN, input.len())?;
+    let u = read_fq(input)?;
+
+    Ok(write_g1(clear_cofactor_g1(map_to_curve_g1(u))))
+}
+
+/// EIP-2537 BLS12_MAP_FP2_TO_G2
+///
+/// input: `Fp2`, 128 bytes
+pub fn map_fp2_to_g2(input: &[u8]) -> Result<Vec<u8>> {
+    Error::check_length(FQ2_LEN, input.len())?;
+    let u = read_fq2(input)?;
+
+    Ok(write_g2(clear_cofactor_g2(map_to_curve_g2(u))))
+}
+
+#[test]
+fn test_g1_ops() {
+    let g1 = G1Affine::prime_subgroup_generator();
+    let mut input = write_g1(g1);
+    input.extend(write_g1(g1));
+    assert_eq!(
+        g1_add(&input).unwrap(),
+        write_g1(g1.mul(Fr::from(2u64)).into_affine())
+    );
+
+    // G1 * 2 == G1MSM((G1, 1), (G1, 1))
+    let mut one = vec![0; SCALAR_LEN];
+    one[SCALAR_LEN - 1] = 1;
+    let mut input = write_g1(g1);
+    input.extend(&one);
+    input.extend(write_g1(g1));
+    input.extend(&one);
+    let mut two = write_g1(g1);
+    two.extend(vec![0; SCALAR_LEN - 1]);
+    two.push(2);
+    assert_eq!(g1_msm(&input).unwrap(), g1_mul(&two).unwrap());
+    assert_eq!(g1_msm(&input).unwrap(), write_g1(g1 + g1));
+
+    // empty input and non-zero padding
+    assert!(g1_msm(&[]).is_err());
+    let mut input = write_g1(g1);
+    input.extend(write_g1(g1));
+    input[0] = 1;
+    assert!(g1_add(&input).is_err());
+}
+
+#[test]
+fn test_g2_ops() {
+    let g2 = G2Affine::prime_subgroup_generator();
+    let mut input = write_g2(g2);
+    input.extend(write_g2(g2));
+    let double = g2_add(&input).unwrap();
+
+    let mut two = write_g2(g2);
+    two.extend(vec![0; SCALAR_LEN - 1]);
+    two.push(2);
+    assert_eq!(g2_mul(&two).unwrap(), double);
+    assert_eq!(g2_msm(&two).unwrap(), double);
+    assert!(g2_add(&input[1..]).is_err());
+}
+
+#[test]
+fn test_pairing() {
+    // e(G1, G2) * e(-G1, G2) == 1
+    let g1 = G1Affine::prime_subgroup_generator();
+    let g2 = G2Affine::prime_subgroup_generator();
+    let mut input = write_g1(g1);
+    input.extend(write_g2(g2));
+    input.extend(write_g1(-g1));
+    input.extend(write_g2(g2));
+
+    let mut expected = vec![0; 32];
+    expected[31] = 1;
+    assert_eq!(pairing(&input).unwrap(), expected);
+    assert_eq!(pairing(&input[..G1_LEN + G2_LEN]).unwrap(), vec![0; 32]);
+    assert!(pairing(&[]).is_err());
+}
+
+#[test]
+fn test_map_to_curve() {
+    // RFC 9380 BLS12381G1_XMD:SHA-256_SSWU_RO_, msg = "", u[0] and Q0
+    let u = hex::decode(
+        "00000000000000000000000000000000\
+         0ba14bd907ad64a016293ee7c2d276b8eae71f25a4b941eece7b0d89f17f75cb3ae5438a614fb61d6835ad59f29c564f",
+    )
+    .unwrap();
+    let q0 = hex::decode(
+        "00000000000000000000000000000000\
+         11a3cce7e1d90975990066b2f2643b9540fa40d6137780df4e753a8054d07580db3b7f1f03396333d4a359d1fe3766fe\
+         00000000000000000000000000000000\
+         0eeaf6d794e479e270da10fdaf768db4c96b650a74518fc67b04b03927754bac66f3ac720404f339ecdcc028afa091b7",
+    )
+    .unwrap();
+    let q0 = read_g1(&q0, false).unwrap();
+    assert_eq!(map_fp_to_g1(&u).unwrap(), write_g1(clear_cofactor_g1(q0)));
+    assert!(map_fp_to_g1(&u[1..]).is_err());
+
+    // the output is in G2
+    let mut u = vec![0; FQ2_LEN];
+    u[FQ_LEN - 1] = 1;
+    let output = map_fp2_to_g2(&u).unwrap();
+    assert!(read_g2(&output, true).is_ok());
+}
@@ -0,0 +1,207 @@
+//! EIP-196 and EIP-197 on BN254
+//!
+//! * field elements are 32 bytes big-endian
+//! * G1 points are `x | y`, `(0, 0)` is the point at infinity
+//! * G2 points are `x_im | x_re | y_im | y_re`
+
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::{BigInteger, BigInteger256, FpParameters, FromBytes, One, PrimeField, Zero};
+use ark_std::vec::Vec;
+
+use super::pad;
+use crate::{
+    error::{Error, Result},
+    ops::validate,
+};
+
+/// Field element bytes length
+const FQ_LEN: usize = 32;
+/// G1 bytes length
+const G1_LEN: usize = FQ_LEN * 2;
+/// G2 bytes length
+const G2_LEN: usize = FQ_LEN * 4;
+/// Scalar bytes length
+const SCALAR_LEN: usize = 32;
+
+/// Read big-endian 256 bits
+fn read_u256(bytes: &[u8]) -> Result<BigInteger256> {
+    let mut le = bytes.to_vec();
+    le.reverse();
+    Ok(BigInteger256::read(&*le)?)
+}
+
+/// Read big-endian field element, fails if it is not less than the modulus
+fn read_fq(bytes: &[u8]) -> Result<Fq> {
+    Fq::from_repr(read_u256(bytes)?).ok_or(Error::FieldElementOutOfRange)
+}
+
+fn read_g1(bytes: &[u8]) -> Result<G1Affine> {
+    let x = read_fq(&bytes[0..FQ_LEN])?;
+    let y = read_fq(&bytes[FQ_LEN..G1_LEN])?;
+    if x.is_zero() && y.is_zero() {
+        return Ok(G1Affine::zero());
+    }
+
+    let point = G1Affine::new(x, y, false);
+    if !point.is_on_curve() {
+        return Err(Error::PointNotOnCurve);
+    }
+    Ok(point)
+}
+
+fn read_g2(bytes: &[u8]) -> Result<G2Affine> {
+    let x = Fq2::new(
+        read_fq(&bytes[FQ_LEN..FQ_LEN * 2])?,
+        read_fq(&bytes[0..FQ_LEN])?,
+    );
+    let y = Fq2::new(
+        read_fq(&bytes[FQ_LEN * 3..G2_LEN])?,
+        read_fq(&bytes[FQ_LEN * 2..FQ_LEN * 3])?,
+    );
+    if x.is_zero() && y.is_zero() {
+        return Ok(G2Affine::zero());
+    }
+
+    let point = G2Affine::new(x, y, false);
+    validate(&point)?;
+    Ok(point)
+}
+
+fn write_g1(point: G1Affine) -> Vec<u8> {
+    if point.is_zero() {
+        return vec![0; G1_LEN];
+    }
+
+    let mut output = point.x.into_repr().to_bytes_be();
+    output.extend_from_slice(&point.y.into_repr().to_bytes_be());
+    output
+}
+
+/// EIP-196 ecAdd
+///
+/// input: `G1 | G1`, right padded to 128 bytes
+pub fn ec_add(input: &[u8]) -> Result<Vec<u8>> {
+    let input = pad(input, G1_LEN * 2);
+    let point1 = read_g1(&input[0..G1_LEN])?;
+    let point2 = read_g1(&input[G1_LEN..])?;
+
+    Ok(write_g1(point1 + point2))
+}
+
+/// EIP-196 ecMul
+///
+/// input: `G1 | scalar`, right padded to 96 bytes, the scalar is not reduced
+pub fn ec_mul(input: &[u8]) -> Result<Vec<u8>> {
+    let input = pad(input, G1_LEN + SCALAR_LEN);
+    let point = read_g1(&input[0..G1_LEN])?;
+    let scalar = read_u256(&input[G1_LEN..])?;
+
+    Ok(write_g1(point.mul(scalar).into_affine()))
+}
+
+/// EIP-197 ecPairing
+///
+/// input: `(G1 | G2)*`, returns 32 bytes big-endian `1` if the product of
+/// pairings is one, else `0`
+pub fn ec_pairing(input: &[u8]) -> Result<Vec<u8>> {
+    let g1_g2_len = G1_LEN + G2_LEN;
+    Error::check_length_multiple(g1_g2_len, input.len())?;
+
+    let mut pairings = Vec::with_capacity(input.len() / g1_g2_len);
+    for pair in input.chunks(g1_g2_len) {
+        let g1 = read_g1(&pair[0..G1_LEN])?;
+        let g2 = read_g2(&pair[G1_LEN..])?;
+        pairings.push((g1.into(), g2.into()));
+    }
+
+    let mut output = vec![0; 32];
+    if <Bn254 as PairingEngine>::product_of_pairings(&pairings)
+        == <Bn254 as PairingEngine>::Fqk::one()
+    {
+        output[31] = 1;
+    }
+    Ok(output)
+}
+
+#[test]
+fn test_ec_add() {
+    // go-ethereum bn256Add chfast1
+    let input = hex::decode(
+        "18b18acfb4c2c30276db5411368e7185b311dd124691610c5d3b74034e093dc9063c909c4720840cb5134cb9f59fa749755796819658d32efc0d288198f37266\
+         07c2b7f58a84bd6145f00c9c2bc0bb1a187f20ff2c92963a88019e7c6a014eed06614e20c147e940f2d70da3f74c9a17df361706a4485c742bd6788478fa17d7",
+    )
+    .unwrap();
+    let expected = hex::decode(
+        "2243525c5efd4b9c3d3c45ac0ca3fe4dd85e830a4ce6b65fa1eeaee202839703301d1d33be6da8e509df21cc35964723180eed7532537db9ae5e7d48f195c915",
+    )
+    .unwrap();
+    assert_eq!(ec_add(&input).unwrap(), expected);
+
+    // empty input is two points at infinity
+    assert_eq!(ec_add(&[]).unwrap(), vec![0; 64]);
+
+    // (1, 3) is not on curve
+    let mut input = vec![0; 128];
+    input[31] = 1;
+    input[63] = 3;
+    assert!(ec_add(&input).is_err());
+}
+
+#[test]
+fn test_ec_mul() {
+    // go-ethereum bn256ScalarMul chfast1
+    let input = hex::decode(
+        "2bd3e6d0f3b142924f5ca7b49ce5b9d54c4703d7ae5648e61d02268b1a0a9fb721611ce0a6af85915e2f1d70300909ce2e49dfad4a4619c8390cae66cefdb204\
+         00000000000000000000000000000000000000000000000011138ce750fa15c2",
+    )
+    .unwrap();
+    let expected = hex::decode(
+        "070a8d6a982153cae4be29d434e8faef8a47b274a053f5a4ee2a6c9c13c31e5c031b8ce914eba3a9ffb989f9cdd5b0f01943074bf4f0f315690ec3cec6981afc",
+    )
+    .unwrap();
+    assert_eq!(ec_mul(&input).unwrap(), expected);
+
+    // G1 * (r - 1) == -G1
+    let mut input = write_g1(G1Affine::prime_subgroup_generator());
+    let mut r_minus_one = <<Fr as PrimeField>::Params as FpParameters>::MODULUS;
+    r_minus_one.sub_noborrow(&BigInteger256::from(1));
+    let mut scalar = r_minus_one.to_bytes_be();
+    input.append(&mut scalar);
+    assert_eq!(
+        ec_mul(&input).unwrap(),
+        write_g1(-G1Affine::prime_subgroup_generator())
+    );
+}
+
+#[test]
+fn test_ec_pairing() {
+    // go-ethereum bn256Pairing jeff1
+    let input = hex::decode(
+        "1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f59\
+         3034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41\
+         209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf7\
+         04bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a41678\
+         2bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d\
+         120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550\
+         111e129f1cf1097710d41c4ac70fcdfa5ba2023c6ff1cbeac322de49d1b6df7c\
+         2032c61a830e3c17286de9462bf242fca2883585b93870a73853face6a6bf411\
+         198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+         1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+         090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+         12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+    )
+    .unwrap();
+    let mut expected = vec![0; 32];
+    expected[31] = 1;
+    assert_eq!(ec_pairing(&input).unwrap(), expected);
+
+    // e(G1, G2) != 1
+    assert_eq!(ec_pairing(&input[192..]).unwrap(), vec![0; 32]);
+
+    // empty input
+    assert_eq!(ec_pairing(&[]).unwrap(), expected);
+
+    // invalid length
+    assert!(ec_pairing(&input[1..]).is_err());
+}
@@ -0,0 +1,104 @@
+//! EVM precompile compatible operations
+//!
+//! Inputs and outputs are byte-compatible with the Ethereum precompiles, so
+//! the Ethereum test vectors and verifiers can be used without re-encoding.
+
+pub mod bls12_381;
+mod bn254;
+
+pub use self::bn254::{ec_add, ec_mul, ec_pairing};
+
+use ark_std::{convert::TryFrom, vec::Vec};
+
+use crate::error::{Error, Result};
+
+/// EVM precompiles, function ids are `0x02000000 + CURVE_ID + op`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Precompile {
+    /// EIP-2537 BLS12_G1ADD
+    Bls12381G1Add = 0x02000010,
+    /// EIP-2537 BLS12_G1MUL
+    Bls12381G1Mul = 0x02000011,
+    /// EIP-2537 BLS12_G1MSM
+    Bls12381G1Msm = 0x02000012,
+    /// EIP-2537 BLS12_G2ADD
+    Bls12381G2Add = 0x02000013,
+    /// EIP-2537 BLS12_G2MUL
+    Bls12381G2Mul = 0x02000014,
+    /// EIP-2537 BLS12_G2MSM
+    Bls12381G2Msm = 0x02000015,
+    /// EIP-2537 BLS12_PAIRING
+    Bls12381Pairing = 0x02000016,
+    /// EIP-2537 BLS12_MAP_FP_TO_G1
+    Bls12381MapFpToG1 = 0x02000017,
+    /// EIP-2537 BLS12_MAP_FP2_TO_G2
+    Bls12381MapFp2ToG2 = 0x02000018,
+    /// EIP-196 ecAdd
+    Bn254Add = 0x02000020,
+    /// EIP-196 ecMul
+    Bn254Mul = 0x02000021,
+    /// EIP-197 ecPairing
+    Bn254Pairing = 0x02000022,
+}
+
+impl Precompile {
+    /// All precompiles
+    pub const ALL: &'static [Precompile] = &[
+        Precompile::Bls12381G1Add,
+        Precompile::Bls12381G1Mul,
+        Precompile::Bls12381G1Msm,
+        Precompile::Bls12381G2Add,
+        Precompile::Bls12381G2Mul,
+        Precompile::Bls12381G2Msm,
+        Precompile::Bls12381Pairing,
+        Precompile::Bls12381MapFpToG1,
+        Precompile::Bls12381MapFp2ToG2,
+        Precompile::Bn254Add,
+        Precompile::Bn254Mul,
+        Precompile::Bn254Pairing,
+    ];
+
+    /// Function id of this precompile
+    pub const fn func_id(self) -> u32 {
+        self as u32
+    }
+
+    /// Run this precompile
+    pub fn call(self, input: &[u8]) -> Result<Vec<u8>> {
+        Ok(match self {
+            Precompile::Bls12381G1Add => bls12_381::g1_add(input)?,
+            Precompile::Bls12381G1Mul => bls12_381::g1_mul(input)?,
+            Precompile::Bls12381G1Msm => bls12_381::g1_msm(input)?,
+            Precompile::Bls12381G2Add => bls12_381::g2_add(input)?,
+            Precompile::Bls12381G2Mul => bls12_381::g2_mul(input)?,
+            Precompile::Bls12381G2Msm => bls12_381::g2_msm(input)?,
+            Precompile::Bls12381Pairing => bls12_381::pairing(input)?,
+            Precompile::Bls12381MapFpToG1 => bls12_381::map_fp_to_g1(input)?,
+            Precompile::Bls12381MapFp2ToG2 => bls12_381::map_fp2_to_g2(input)?,
+            Precompile::Bn254Add => ec_add(input)?,
+            Precompile::Bn254Mul => ec_mul(input)?,
+            Precompile::Bn254Pairing => ec_pairing(input)?,
+        })
+    }
+}
+
+impl TryFrom<u32> for Precompile {
+    type Error = Error;
+
+    fn try_from(id: u32) -> Result<Self> {
+        Precompile::ALL
+            .iter()
+            .find(|p| **p as u32 == id)
+            .copied()
+            .ok_or(Error::InvalidFunctionId)
+    }
+}
+
+/// Right pad `input` with zeros to `len` bytes, extra bytes are ignored
+fn pad(input: &[u8], len: usize) -> Vec<u8> {
+    let mut padded = vec![0; len];
+    let n = input.len().min(len);
+    padded[..n].copy_from_slice(&input[..n]);
+    padded
+}
@@ -0,0 +1,176 @@
+//! Fixed-base scalar muls with precomputed window tables
+//!
+//! The tables of the generators are built on the first mul of the generator
+//! and kept for the process, see
+//! [`GroupOperations::g1_generator_table`](crate::ops::GroupOperations::g1_generator_table).
+//! Tables of other bases, e.g. the bases of Pedersen commitments or the powers
+//! of tau of KZG, are built by [`FixedBaseTable::new`] and are
+//! `CanonicalSerialize` to be built once and shipped.
+
+use ark_ec::{msm::FixedBaseMSM, AffineCurve, ProjectiveCurve};
+use ark_ff::{FpParameters, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use ark_std::vec::Vec;
+
+/// Window bits of the generator tables, 16 multiples per window
+pub const GENERATOR_WINDOW: usize = 4;
+
+/// Largest window bits of the tables read
+const MAX_WINDOW: usize = 16;
+
+/// Multiples `j * 2^(window * i) * base` of a base point, `j` below
+/// `2^window`, so a scalar mul is one mixed add per window of the scalar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedBaseTable<G: AffineCurve> {
+    window: usize,
+    table: Vec<Vec<G>>,
+}
+
+impl<G: AffineCurve> FixedBaseTable<G> {
+    /// Table of `base` with windows of `window` bits
+    pub fn new(base: &G, window: usize) -> Self {
+        assert!(window > 0 && window <= MAX_WINDOW, "window out of range");
+        FixedBaseTable {
+            window,
+            table: FixedBaseMSM::get_window_table(
+                scalar_bits::<G>(),
+                window,
+                base.into_projective(),
+            ),
+        }
+    }
+
+    /// Table of the prime subgroup generator
+    pub fn generator() -> Self {
+        Self::new(&G::prime_subgroup_generator(), GENERATOR_WINDOW)
+    }
+
+    /// Window bits of this table
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// `scalar` times the base
+    pub fn mul(&self, scalar: &G::ScalarField) -> G::Projective {
+        FixedBaseMSM::windowed_mul::<G::Projective>(
+            self.table.len(),
+            self.window,
+            &self.table,
+            scalar,
+        )
+    }
+
+    /// Check the shape of a table read, the muls index every window of the
+    /// scalar
+    fn checked(window: u64, table: Vec<Vec<G>>) -> Result<Self, SerializationError> {
+        let window = window as usize;
+        if window == 0 || window > MAX_WINDOW {
+            return Err(SerializationError::InvalidData);
+        }
+        let bits = scalar_bits::<G>();
+        if table.len() != (bits + window - 1) / window {
+            return Err(SerializationError::InvalidData);
+        }
+        for (i, row) in table.iter().enumerate() {
+            if row.len() < 1 << window.min(bits - i * window) {
+                return Err(SerializationError::InvalidData);
+            }
+        }
+        Ok(FixedBaseTable { window, table })
+    }
+}
+
+/// Bits of the scalars of `G`
+fn scalar_bits<G: AffineCurve>() -> usize {
+    <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize
+}
+
+impl<G: AffineCurve> CanonicalSerialize for FixedBaseTable<G> {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        (self.window as u64).serialize(&mut writer)?;
+        self.table.serialize(&mut writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        (self.window as u64).serialized_size() + self.table.serialized_size()
+    }
+
+    fn serialize_uncompressed<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        (self.window as u64).serialize_uncompressed(&mut writer)?;
+        self.table.serialize_uncompressed(&mut writer)
+    }
+
+    fn serialize_unchecked<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        (self.window as u64).serialize_unchecked(&mut writer)?;
+        self.table.serialize_unchecked(&mut writer)
+    }
+
+    fn uncompressed_size(&self) -> usize {
+        (self.window as u64).uncompressed_size() + self.table.uncompressed_size()
+    }
+}
+
+/// The points are validated unless read unchecked, whether they are the
+/// multiples of one base is up to the source of the table
+impl<G: AffineCurve> CanonicalDeserialize for FixedBaseTable<G> {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let window = u64::deserialize(&mut reader)?;
+        Self::checked(window, Vec::deserialize(&mut reader)?)
+    }
+
+    fn deserialize_uncompressed<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let window = u64::deserialize_uncompressed(&mut reader)?;
+        Self::checked(window, Vec::deserialize_uncompressed(&mut reader)?)
+    }
+
+    fn deserialize_unchecked<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let window = u64::deserialize_unchecked(&mut reader)?;
+        Self::checked(window, Vec::deserialize_unchecked(&mut reader)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_base_table() {
+        use ark_bn254::{Fr, G1Affine, G2Affine};
+        use ark_ff::{One, Zero};
+        use ark_std::{test_rng, UniformRand};
+
+        let rng = &mut test_rng();
+        let g1 = G1Affine::prime_subgroup_generator();
+        let table = FixedBaseTable::<G1Affine>::generator();
+        for s in [Fr::zero(), Fr::one(), -Fr::one(), Fr::rand(rng)] {
+            assert_eq!(table.mul(&s), g1.mul(s));
+        }
+        let g2 = G2Affine::prime_subgroup_generator();
+        let s = Fr::rand(rng);
+        assert_eq!(FixedBaseTable::<G2Affine>::generator().mul(&s), g2.mul(s));
+
+        // other bases and windows, shipped serialized
+        let base = g1.mul(Fr::rand(rng)).into_affine();
+        let table = FixedBaseTable::new(&base, 3);
+        assert_eq!(table.mul(&s), base.mul(s));
+        let mut bytes = Vec::new();
+        table.serialize(&mut bytes).unwrap();
+        assert_eq!(FixedBaseTable::deserialize(&*bytes).unwrap(), table);
+        let mut bytes = Vec::new();
+        table.serialize_uncompressed(&mut bytes).unwrap();
+        assert_eq!(
+            FixedBaseTable::deserialize_uncompressed(&*bytes).unwrap(),
+            table
+        );
+
+        // the window must match the rows
+        let mut bytes = Vec::new();
+        4u64.serialize(&mut bytes).unwrap();
+        table.table.serialize(&mut bytes).unwrap();
+        assert!(FixedBaseTable::<G1Affine>::deserialize(&*bytes).is_err());
+        let mut bytes = Vec::new();
+        0u64.serialize(&mut bytes).unwrap();
+        Vec::<Vec<G1Affine>>::new().serialize(&mut bytes).unwrap();
+        assert!(FixedBaseTable::<G1Affine>::deserialize(&*bytes).is_err());
+    }
+}
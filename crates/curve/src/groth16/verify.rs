@@ -5,12 +5,17 @@ use alloc::{
     vec::Vec,
 };
 
-use num_bigint::BigUint;
-use parity_scale_codec::{Decode, Encode};
+use ark_ec::PairingEngine;
+use ark_ff::{One, ToBytes};
+use ark_serialize::CanonicalDeserialize;
+use parity_scale_codec::{Compact, Decode, Encode};
 
 use crate::{
     error::{Error, Result},
-    ops::CurveBasicOperations,
+    ops::PairingOperations,
+    registry::Operation,
+    serialization::Serialization,
+    types::{self, G1Bytes, G2Bytes, ScalarBytes},
 };
 
 /// Groth16 Verifying Parcel
@@ -23,18 +28,18 @@ pub struct Groth16Parcel {
 }
 
 /// Verify Wrapper
-pub fn verify<C: CurveBasicOperations>(parcel: Vec<u8>) -> Result<bool> {
+pub fn verify<C: PairingOperations>(parcel: Vec<u8>) -> Result<bool> {
     let Groth16Parcel {
         vk_gamma_abc,
         vk,
         proof,
         public_inputs,
-    } = Groth16Parcel::decode(&mut parcel.as_ref()).map_err(|_| Error::VerifyParcelFailed)?;
+    } = Groth16Parcel::decode(&mut parcel.as_ref())?;
     verify_proof::<C>(vk_gamma_abc, vk, proof, public_inputs)
 }
 
 /// preprocess vk and proof to verify proof
-pub fn preprocessed_verify_proof<C: CurveBasicOperations>(
+pub fn preprocessed_verify_proof<C: PairingOperations>(
     vk: [&str; 14],
     vk_gamma_abc: [&str; 6],
     proof_and_input: &[u8],
@@ -60,101 +65,336 @@ pub fn preprocessed_verify_proof<C: CurveBasicOperations>(
     )
 }
 
+/// Groth16 verifying key
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct VerifyingKey<C: PairingOperations> {
+    pub alpha_g1: G1Bytes<C>,
+    pub beta_g2: G2Bytes<C>,
+    pub gamma_g2: G2Bytes<C>,
+    pub delta_g2: G2Bytes<C>,
+    pub gamma_abc_g1: Vec<G1Bytes<C>>,
+}
+
+impl<C: PairingOperations> VerifyingKey<C> {
+    /// Read the raw key, only the lengths are checked, the points are
+    /// validated by the curve operations of [`VerifyingKey::verify`]
+    ///
+    /// * vk_gamma_abc: `G1` each
+    /// * vk: `gamma (G2) | delta (G2) | alpha (G1) | beta (G2)`
+    pub fn from_raw(vk_gamma_abc: Vec<Vec<u8>>, vk: Vec<u8>) -> Result<Self> {
+        let (g1_len, g2_len) = (C::G1_LEN, C::G2_LEN);
+        Error::check_length(g2_len * 3 + g1_len, vk.len())?;
+
+        Ok(VerifyingKey {
+            gamma_g2: G2Bytes::from_trusted(vk[0..g2_len].to_vec())?,
+            delta_g2: G2Bytes::from_trusted(vk[g2_len..g2_len * 2].to_vec())?,
+            alpha_g1: G1Bytes::from_trusted(vk[g2_len * 2..g2_len * 2 + g1_len].to_vec())?,
+            beta_g2: G2Bytes::from_trusted(vk[g2_len * 2 + g1_len..].to_vec())?,
+            gamma_abc_g1: vk_gamma_abc
+                .into_iter()
+                .map(G1Bytes::from_trusted)
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Verify `proof` of `public_inputs`
+    pub fn verify(&self, proof: &Proof<C>, public_inputs: &[ScalarBytes<C>]) -> Result<bool> {
+        self.verify_with(proof, public_inputs, false)
+    }
+
+    /// Verify with this key trusted
+    ///
+    /// No point is validated by the curve operations, only use it for keys
+    /// which have been validated before, such as the stored ones, and a proof
+    /// of [`Proof::from_raw_validated`].
+    pub fn verify_trusted(
+        &self,
+        proof: &Proof<C>,
+        public_inputs: &[ScalarBytes<C>],
+    ) -> Result<bool> {
+        self.verify_with(proof, public_inputs, true)
+    }
+
+    fn verify_with(
+        &self,
+        proof: &Proof<C>,
+        public_inputs: &[ScalarBytes<C>],
+        trusted: bool,
+    ) -> Result<bool> {
+        let (msm_g1, pairing) = if trusted {
+            (Operation::MsmG1Trusted, Operation::PairingTrusted)
+        } else {
+            (Operation::MsmG1, Operation::Pairing)
+        };
+
+        if (public_inputs.len() + 1) != self.gamma_abc_g1.len() {
+            return Err(Error::PublicInputCountMismatch {
+                expected: self.gamma_abc_g1.len().saturating_sub(1) as u32,
+                got: public_inputs.len() as u32,
+            });
+        }
+
+        // Compute the linear combination vk_x with one msm call
+        //  [(βui(x)+αvi(x)+wi(x))/γ] ∈ G1
+        // acc = sigma(i:0~l)* [(βui(x)+αvi(x)+wi(x))/γ] ∈ G1
+        let one = ScalarBytes::from_scalar(&<C as PairingEngine>::Fr::one());
+        let pairs = self
+            .gamma_abc_g1
+            .iter()
+            .cloned()
+            .zip(core::iter::once(one).chain(public_inputs.iter().cloned()))
+            .collect::<Vec<_>>();
+        let acc = types::msm_g1_with(msm_g1, &pairs)?;
+
+        // The original verification equation is:
+        // A * B = alpha * beta + acc * gamma + C * delta
+        // ... however, we rearrange it so that it is:
+        // A * B - acc * gamma - C * delta = alpha * beta
+        // or equivalently:
+        //    A   *    B    +  (-acc) * gamma +  (-C) * delta  +   (-alpha) * beta = 0
+        let pairings = [
+            (proof.a.clone(), proof.b.clone()),
+            (acc.neg()?, self.gamma_g2.clone()),
+            (proof.c.neg()?, self.delta_g2.clone()),
+            (self.alpha_g1.neg()?, self.beta_g2.clone()),
+        ];
+
+        // Return the result of computing the pairing check
+        // e(p1[0], p2[0]) *  .... * e(p1[n], p2[n]) == 1.
+        // For example pairing([P1(), P1().negate()], [P2(), P2()]) should return true.
+        types::pairing_with(pairing, &pairings)
+    }
+}
+
+/// Groth16 proof
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Proof<C: PairingOperations> {
+    pub a: G1Bytes<C>,
+    pub b: G2Bytes<C>,
+    pub c: G1Bytes<C>,
+}
+
+impl<C: PairingOperations> Proof<C> {
+    /// Read the raw proof `A (G1) | B (G2) | C (G1)`, only the lengths are
+    /// checked, the points are validated by the curve operations of
+    /// [`VerifyingKey::verify`]
+    pub fn from_raw(proof: Vec<u8>) -> Result<Self> {
+        let (g1_len, g2_len) = (C::G1_LEN, C::G2_LEN);
+        Error::check_length(g1_len * 2 + g2_len, proof.len())?;
+
+        Ok(Proof {
+            a: G1Bytes::from_trusted(proof[0..g1_len].to_vec())?,
+            b: G2Bytes::from_trusted(proof[g1_len..g1_len + g2_len].to_vec())?,
+            c: G1Bytes::from_trusted(proof[g1_len + g2_len..].to_vec())?,
+        })
+    }
+
+    /// Read the raw proof and validate the points, for
+    /// [`VerifyingKey::verify_trusted`] whose curve operations don't
+    pub fn from_raw_validated(proof: Vec<u8>) -> Result<Self> {
+        let proof = Self::from_raw(proof)?;
+        C::read_g1(proof.a.as_bytes())?;
+        C::read_g2(proof.b.as_bytes())?;
+        C::read_g1(proof.c.as_bytes())?;
+        Ok(proof)
+    }
+}
+
+/// Read the raw public inputs, only the lengths are checked, the scalars are
+/// checked by the multi-scalar mul
+fn public_inputs<C: PairingOperations>(public_inputs: Vec<Vec<u8>>) -> Result<Vec<ScalarBytes<C>>> {
+    public_inputs
+        .into_iter()
+        .map(ScalarBytes::from_trusted)
+        .collect()
+}
+
 /// Groth16 verification
-pub fn verify_proof<C: CurveBasicOperations>(
+pub fn verify_proof<C: PairingOperations>(
+    vk_gamma_abc: Vec<Vec<u8>>,
+    vk: Vec<u8>,
+    proof: Vec<u8>,
+    public_inputs: Vec<Vec<u8>>,
+) -> Result<bool> {
+    VerifyingKey::<C>::from_raw(vk_gamma_abc, vk)?.verify(
+        &Proof::from_raw(proof)?,
+        &self::public_inputs(public_inputs)?,
+    )
+}
+
+/// Groth16 verification with a trusted verifying key
+///
+/// The points of `vk_gamma_abc` and `vk` are not validated, only use it for
+/// keys which have been validated before, such as the stored ones. The proof
+/// is still validated, locally since the trusted curve operations don't.
+pub fn verify_proof_with_trusted_vk<C: PairingOperations>(
+    vk_gamma_abc: Vec<Vec<u8>>,
+    vk: Vec<u8>,
+    proof: Vec<u8>,
+    public_inputs: Vec<Vec<u8>>,
+) -> Result<bool> {
+    VerifyingKey::<C>::from_raw(vk_gamma_abc, vk)?.verify_trusted(
+        &Proof::from_raw_validated(proof)?,
+        &self::public_inputs(public_inputs)?,
+    )
+}
+
+/// Verify Wrapper, the points of the parcel are compressed
+pub fn verify_compressed<C: PairingOperations>(parcel: Vec<u8>) -> Result<bool> {
+    let Groth16Parcel {
+        vk_gamma_abc,
+        vk,
+        proof,
+        public_inputs,
+    } = Groth16Parcel::decode(&mut parcel.as_ref())?;
+    verify_proof_compressed::<C>(vk_gamma_abc, vk, proof, public_inputs)
+}
+
+/// Groth16 verification with compressed points
+///
+/// * vk_gamma_abc: `G1` each
+/// * vk: `gamma (G2) | delta (G2) | alpha (G1) | beta (G2)`
+/// * proof: `A (G1) | B (G2) | C (G1)`
+pub fn verify_proof_compressed<C: PairingOperations>(
     vk_gamma_abc: Vec<Vec<u8>>,
     vk: Vec<u8>,
     proof: Vec<u8>,
     public_inputs: Vec<Vec<u8>>,
 ) -> Result<bool> {
-    let g1_len = C::G1_LEN;
-    let g2_len = C::G2_LEN;
-    let g1_g2_len = C::G2_LEN + C::G1_LEN;
-    let scalar_len = C::SCALAR_LEN;
+    let vk_gamma_abc = vk_gamma_abc
+        .iter()
+        .map(|point| {
+            let mut input = point.as_slice();
+            let mut output = Vec::with_capacity(C::G1_LEN);
+            decompress(&mut input, &mut output, C::decompress_g1)?;
+            finish(input, point, output)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut input = vk.as_slice();
+    let mut output = Vec::with_capacity(C::G2_LEN * 3 + C::G1_LEN);
+    decompress(&mut input, &mut output, C::decompress_g2)?;
+    decompress(&mut input, &mut output, C::decompress_g2)?;
+    decompress(&mut input, &mut output, C::decompress_g1)?;
+    decompress(&mut input, &mut output, C::decompress_g2)?;
+    let vk = finish(input, &vk, output)?;
+
+    let mut input = proof.as_slice();
+    let mut output = Vec::with_capacity(C::G1_LEN * 2 + C::G2_LEN);
+    decompress(&mut input, &mut output, C::decompress_g1)?;
+    decompress(&mut input, &mut output, C::decompress_g2)?;
+    decompress(&mut input, &mut output, C::decompress_g1)?;
+    let proof = finish(input, &proof, output)?;
+
+    verify_proof::<C>(vk_gamma_abc, vk, proof, public_inputs)
+}
+
+/// Groth16 Verifying Parcel of `CanonicalSerialize` key, proof and public
+/// inputs
+#[derive(Debug, Encode, Decode)]
+pub struct Groth16CanonicalParcel {
+    pub vk: Vec<u8>,
+    pub proof: Vec<u8>,
+    pub public_inputs: Vec<u8>,
+}
+
+/// Verify Wrapper, the parcel is in the canonical `serialization`
+pub fn verify_canonical<C: PairingOperations>(
+    serialization: Serialization,
+    parcel: Vec<u8>,
+) -> Result<bool> {
+    let Groth16CanonicalParcel {
+        vk,
+        proof,
+        public_inputs,
+    } = Groth16CanonicalParcel::decode(&mut parcel.as_ref())?;
+    verify_proof_canonical::<C>(serialization, vk, proof, public_inputs)
+}
 
-    if (public_inputs.len() + 1) != vk_gamma_abc.len() {
-        return Err(Error::VerifyParcelFailed);
+/// Groth16 verification of `CanonicalSerialize` key, proof and public inputs,
+/// as `ark-groth16` writes them
+///
+/// * vk: `alpha (G1) | beta (G2) | gamma (G2) | delta (G2) | gamma_abc (Vec<G1>)`
+/// * proof: `A (G1) | B (G2) | C (G1)`
+/// * public_inputs: `Vec<Fr>`
+///
+/// `Vec`s are prefixed with their `u64` little-endian length, the legacy
+/// serialization has its own entry points.
+pub fn verify_proof_canonical<C: PairingOperations>(
+    serialization: Serialization,
+    vk: Vec<u8>,
+    proof: Vec<u8>,
+    public_inputs: Vec<u8>,
+) -> Result<bool> {
+    if serialization == Serialization::Legacy {
+        return Err(Error::UnsupportedOperation);
     }
+    // the points are validated by the curve operations of the verification
+    let g1 = |input: &mut &[u8]| {
+        G1Bytes::<C>::from_trusted(serialization.read_point_legacy(input, C::decompress_g1)?)
+    };
+    let g2 = |input: &mut &[u8]| {
+        G2Bytes::<C>::from_trusted(serialization.read_point_legacy(input, C::decompress_g2)?)
+    };
+    let fr = |input: &mut &[u8]| {
+        ScalarBytes::<C>::from_trusted(
+            serialization.read_legacy::<<C as PairingEngine>::Fr>(input)?,
+        )
+    };
 
-    // First two fields are used as the sum
-    let mut acc = vk_gamma_abc[0].to_vec();
-
-    // Compute the linear combination vk_x
-    //  [(βui(x)+αvi(x)+wi(x))/γ] ∈ G1
-    // acc = sigma(i:0~l)* [(βui(x)+αvi(x)+wi(x))/γ] ∈ G1
-    for (i, b) in public_inputs.iter().zip(vk_gamma_abc.iter().skip(1)) {
-        let mut mul_input = Vec::with_capacity(scalar_len + g1_len);
-        mul_input.extend_from_slice(b);
-        mul_input.extend_from_slice(i);
-
-        // Check if invalid length
-        if mul_input.len() != g1_len + scalar_len {
-            return Err(Error::SerializeDataFailed);
-            // return Err(format!(
-            //     "Invalid input length {} for mul operation, should be {}",
-            //     mul_input.len(),
-            //     g1_len + scalar_len
-            // )
-            // .into());
-        }
-        let mul_ic = crate::call(0x01000001 + C::CURVE_ID, &mul_input)?;
+    let mut input = vk.as_slice();
+    let alpha_g1 = g1(&mut input)?;
+    let beta_g2 = g2(&mut input)?;
+    let gamma_g2 = g2(&mut input)?;
+    let delta_g2 = g2(&mut input)?;
+    let gamma_abc_g1 = read_vec(&mut input, g1)?;
+    Error::check_length(vk.len() - input.len(), vk.len())?;
+    let vk = VerifyingKey {
+        alpha_g1,
+        beta_g2,
+        gamma_g2,
+        delta_g2,
+        gamma_abc_g1,
+    };
 
-        let mut acc_mul_ic = Vec::with_capacity(g1_len * 2);
-        acc_mul_ic.extend_from_slice(acc.as_ref());
-        acc_mul_ic.extend_from_slice(mul_ic.as_ref());
+    let mut input = proof.as_slice();
+    let a = g1(&mut input)?;
+    let b = g2(&mut input)?;
+    let c = g1(&mut input)?;
+    Error::check_length(proof.len() - input.len(), proof.len())?;
 
-        // Check if invalid length
-        if acc_mul_ic.len() != g1_len * 2 {
-            return Err(Error::SerializeDataFailed);
-        }
-        acc = crate::call(0x01000000 + C::CURVE_ID, &*acc_mul_ic)?;
+    let mut input = public_inputs.as_slice();
+    let inputs = read_vec(&mut input, fr)?;
+    Error::check_length(public_inputs.len() - input.len(), public_inputs.len())?;
+
+    vk.verify(&Proof { a, b, c }, &inputs)
+}
+
+/// Read a `CanonicalSerialize` vector of `item`s from `input`
+fn read_vec<T>(input: &mut &[u8], item: impl Fn(&mut &[u8]) -> Result<T>) -> Result<Vec<T>> {
+    let len = <u64 as CanonicalDeserialize>::deserialize(&mut *input)?;
+    let mut items = Vec::new();
+    for _ in 0..len {
+        items.push(item(&mut *input)?);
     }
+    Ok(items)
+}
+
+/// Read a compressed point from `input` and write it uncompressed to `output`,
+/// the points are validated by the uncompressed verification
+fn decompress<G: ToBytes>(
+    input: &mut &[u8],
+    output: &mut Vec<u8>,
+    read: fn(&mut &[u8]) -> Result<G>,
+) -> Result<()> {
+    read(input)?.write(output)?;
+    Ok(())
+}
 
-    // The original verification equation is:
-    // A * B = alpha * beta + acc * gamma + C * delta
-    // ... however, we rearrange it so that it is:
-    // A * B - acc * gamma - C * delta = alpha * beta
-    // or equivalently:
-    //    A   *    B    +  (-acc) * gamma +  (-C) * delta  +   (-alpha) * beta = 0
-    let pairings = [
-        (
-            &proof[0..g1_len / 2],           // G1 x
-            &proof[g1_len / 2..g1_len - 1],  // G1 y
-            &proof[g1_len - 1..g1_len],      // G1 infinity
-            &proof[g1_len..g1_len + g2_len], // G2
-        ),
-        (
-            &acc[0..g1_len / 2],
-            &*negate_y::<C>(&acc[g1_len / 2..g1_len - 1]),
-            &acc[g1_len - 1..g1_len],
-            &vk[0..g2_len],
-        ),
-        (
-            &proof[g1_g2_len..g1_g2_len + g1_len / 2],
-            &*negate_y::<C>(&proof[g1_g2_len + g1_len / 2..g1_g2_len + g1_len - 1]),
-            &proof[g1_g2_len + g1_len - 1..g1_g2_len + g1_len],
-            &vk[g2_len..g2_len * 2],
-        ),
-        (
-            &vk[g2_len * 2..g2_len * 2 + g1_len / 2],
-            &*negate_y::<C>(&vk[g2_len * 2 + g1_len / 2..g2_len * 2 + g1_len - 1]),
-            &vk[g2_len * 2 + g1_len - 1..g2_len * 2 + g1_len],
-            &vk[g2_len * 2 + g1_len..g2_len * 3 + g1_len],
-        ),
-    ];
-
-    let mut input = Vec::with_capacity((g1_len + g2_len) * 4);
-    pairings.iter().for_each(|(x, y, infinity, g2)| {
-        input.extend_from_slice(x);
-        input.extend_from_slice(y);
-        input.extend_from_slice(infinity);
-        input.extend_from_slice(g2);
-    });
-
-    // Return the result of computing the pairing check
-    // e(p1[0], p2[0]) *  .... * e(p1[n], p2[n]) == 1.
-    // For example pairing([P1(), P1().negate()], [P2(), P2()]) should return true.
-    Ok(crate::call(0x01000002 + C::CURVE_ID, &input)?[0] == 0)
+/// Return `output` if all the `bytes` have been read, `rest` is the unread
+fn finish(rest: &[u8], bytes: &[u8], output: Vec<u8>) -> Result<Vec<u8>> {
+    Error::check_length(bytes.len() - rest.len(), bytes.len())?;
+    Ok(output)
 }
 
 //
@@ -180,21 +420,6 @@ fn decode_hex(value: String) -> Vec<u8> {
     bytes
 }
 
-fn negate_y_based_curve(y: BigUint, MODULUS: &[u8]) -> BigUint {
-    let q = BigUint::from_bytes_le(MODULUS);
-    q.clone() - y % q
-}
-
-fn negate_y<C: CurveBasicOperations>(y: &[u8]) -> Vec<u8> {
-    let neg_y = negate_y_based_curve(BigUint::from_bytes_le(y), C::MODULUS).to_bytes_le();
-
-    // Because of randomness, Negate_y vector might not satisfy g1_y_len bytes.
-    let mut neg_y_fill_with_zero = vec![0; y.len()];
-    neg_y_fill_with_zero[0..neg_y.len()].copy_from_slice(&*neg_y);
-
-    neg_y_fill_with_zero
-}
-
 #[test]
 fn test_verify() {
     use crate::curve::Bls12_381;
@@ -213,4 +438,7 @@ fn test_verify() {
             .unwrap(),
         true
     );
+
+    // parcels that don't decode are codec errors, not failed verifications
+    assert_eq!(verify::<Bls12_381>(vec![0xff]), Err(Error::ScaleCodecError));
 }
@@ -3,23 +3,41 @@
 #![allow(unused_variables)]
 #![allow(unused_must_use)]
 #![allow(non_snake_case)]
-#![cfg_attr(not(features = "std"), no_std)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 #[macro_use]
 extern crate alloc;
 
+pub mod abi;
+pub mod bls;
+#[cfg(feature = "runtime")]
+pub mod chain_extension;
 pub mod curve;
 mod derive;
 pub mod error;
+pub mod evm;
+pub mod fixed_base;
 pub mod groth16;
+pub mod map_to_curve;
+#[cfg(feature = "ink_mock")]
+pub mod mock;
 pub mod ops;
+pub mod registry;
+pub mod serialization;
 pub mod tests;
+pub mod types;
+pub mod weights;
 
 pub use ark_serialize::SerializationError;
 pub use ark_std::{io::ErrorKind, ops::MulAssign, vec::Vec};
 
 pub use self::error::{Error, Result};
-pub use self::ops::CurveBasicOperations;
+pub use self::ops::{CurveBasicOperations, GroupOperations, PairingOperations};
+pub use self::registry::{CurveId, Operation};
+pub use self::serialization::Serialization;
+pub use self::types::{G1Bytes, G2Bytes, GtBytes, ScalarBytes};
+
+use ark_std::convert::TryFrom;
 
 /// Call curve function
 #[cfg(feature = "ink")]
@@ -32,68 +50,181 @@ pub fn call(func_id: u32, input: &[u8]) -> Result<Vec<u8>> {
         .call(&input)?)
 }
 
-/// bool to bytes
-#[cfg(not(feature = "ink"))]
-fn b2b(b: bool) -> Vec<u8> {
-    Vec::from(if b { [0] } else { [1] })
-}
-
 /// Call curve function
 #[cfg(not(feature = "ink"))]
 pub fn call(func_id: u32, input: &[u8]) -> Result<Vec<u8>> {
-    Ok(match func_id {
+    match func_id {
         // debug
         0x2a => Ok(input.to_vec()),
-        // add - 0x2a + 0,1,2,3
-        0x01000000 => <ark_bls12_377::Bls12_377 as CurveBasicOperations>::add(input),
-        0x01000010 => <ark_bls12_381::Bls12_381 as CurveBasicOperations>::add(input),
-        0x01000020 => <ark_bn254::Bn254 as CurveBasicOperations>::add(input),
-        0x01000030 => <ark_bw6_761::BW6_761 as CurveBasicOperations>::add(input),
-        // mul - 0x3a + 0,1,2,3
-        0x01000001 => <ark_bls12_377::Bls12_377 as CurveBasicOperations>::mul(input),
-        0x01000011 => <ark_bls12_381::Bls12_381 as CurveBasicOperations>::mul(input),
-        0x01000021 => <ark_bn254::Bn254 as CurveBasicOperations>::mul(input),
-        0x01000031 => <ark_bw6_761::BW6_761 as CurveBasicOperations>::mul(input),
-        // pairing - 0x4a + 0,1,2,3
-        0x01000002 => <ark_bls12_377::Bls12_377 as CurveBasicOperations>::pairings(input).map(b2b),
-        0x01000012 => <ark_bls12_381::Bls12_381 as CurveBasicOperations>::pairings(input).map(b2b),
-        0x01000022 => <ark_bn254::Bn254 as CurveBasicOperations>::pairings(input).map(b2b),
-        0x01000032 => <ark_bw6_761::BW6_761 as CurveBasicOperations>::pairings(input).map(b2b),
-        id => return Err(Error::InvalidFunctionId),
-    }?)
+        id => registry::dispatch(id, input),
+    }
 }
 
 /// Integrate add
-pub fn add(func_id: u32, input: &[u8]) -> Result<Vec<u8>> {
-    Ok(match func_id {
-        0x00 => call(0x01000000, input)?,
-        0x10 => call(0x01000010, input)?,
-        0x20 => call(0x01000020, input)?,
-        0x30 => call(0x01000030, input)?,
-        _ => return Err(Error::InvalidFunctionId),
-    })
+pub fn add(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::Add, input)
 }
 
 /// Scalar mul
-pub fn mul(func_id: u32, input: &[u8]) -> Result<Vec<u8>> {
-    Ok(match func_id {
-        0x00 => call(0x01000001, input)?,
-        0x10 => call(0x01000011, input)?,
-        0x20 => call(0x01000021, input)?,
-        0x30 => call(0x01000031, input)?,
-        _ => return Err(Error::InvalidFunctionId),
-    })
-}
-
-/// pairing
-pub fn pairing(func_id: u32, input: &[u8]) -> Result<Vec<u8>> {
-    Ok(match func_id {
-        0x00 => call(0x01000002, input)?,
-        0x10 => call(0x01000012, input)?,
-        0x20 => call(0x01000022, input)?,
-        0x30 => call(0x01000032, input)?,
-        _ => return Err(Error::InvalidFunctionId),
-    })
+pub fn mul(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::Mul, input)
+}
+
+/// Scalar mul in constant time for secret scalars, computed locally and never
+/// through the chain extension
+pub fn mul_secret(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    CurveId::try_from(curve_id)?.mul_secret(input)
+}
+
+/// G2 scalar mul in constant time for secret scalars, computed locally and
+/// never through the chain extension
+pub fn g2_mul_secret(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    CurveId::try_from(curve_id)?.g2_mul_secret(input)
+}
+
+/// Scalar mul of the G1 generator, input: `Fr`, cheaper than `mul` with the
+/// precomputed table of the generator
+pub fn mul_generator(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::MulGenerator, input)
+}
+
+/// pairing, the legacy result is `[0]` if the product of pairings is one and
+/// `[1]` otherwise, see [`pairing_check`]
+pub fn pairing(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::Pairing, input)
+}
+
+/// Check the product of pairings is one, with the unambiguous v1 result
+pub fn pairing_check(curve_id: u32, input: &[u8]) -> Result<bool> {
+    let output = types::call_id_with(abi::Version::V1, curve_id, Operation::Pairing, input)?;
+    abi::Version::V1.decode_bool(&output)
+}
+
+/// ABI description of all function ids
+pub fn describe() -> Result<abi::Abi> {
+    let output = call(abi::DESCRIBE, &[])?;
+    Ok(parity_scale_codec::Decode::decode(&mut &*output)?)
+}
+
+/// Integrate add with compressed points
+pub fn add_compressed(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::AddCompressed, input)
+}
+
+/// Scalar mul with compressed points
+pub fn mul_compressed(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::MulCompressed, input)
+}
+
+/// pairing with compressed points, the legacy result is `[0]` for success
+pub fn pairing_compressed(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::PairingCompressed, input)
+}
+
+/// G2 add
+pub fn g2_add(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::G2Add, input)
+}
+
+/// G2 scalar mul
+pub fn g2_mul(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::G2Mul, input)
+}
+
+/// Scalar mul of the G2 generator, input: `Fr`, with the precomputed table
+/// of the generator
+pub fn g2_mul_generator(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::G2MulGenerator, input)
+}
+
+/// G1 multi-scalar mul
+pub fn msm_g1(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::MsmG1, input)
+}
+
+/// G2 multi-scalar mul
+pub fn msm_g2(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::MsmG2, input)
+}
+
+/// Sum of any number of G1 points
+pub fn sum_g1(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::SumG1, input)
+}
+
+/// Sum of any number of G2 points
+pub fn sum_g2(curve_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::SumG2, input)
+}
+
+/// Hash `msg` to G1 with the domain separation tag `dst`, RFC 9380
+pub fn hash_to_g1(curve_id: u32, dst: &[u8], msg: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::HashToG1, &types::hash_input(dst, msg))
+}
+
+/// Hash `msg` to G2 with the domain separation tag `dst`, RFC 9380
+pub fn hash_to_g2(curve_id: u32, dst: &[u8], msg: &[u8]) -> Result<Vec<u8>> {
+    types::call_id(curve_id, Operation::HashToG2, &types::hash_input(dst, msg))
+}
+
+/// EIP-196 ecAdd on BN254
+pub fn ec_add(input: &[u8]) -> Result<Vec<u8>> {
+    call(evm::Precompile::Bn254Add.func_id(), input)
+}
+
+/// EIP-196 ecMul on BN254
+pub fn ec_mul(input: &[u8]) -> Result<Vec<u8>> {
+    call(evm::Precompile::Bn254Mul.func_id(), input)
+}
+
+/// EIP-197 ecPairing on BN254
+pub fn ec_pairing(input: &[u8]) -> Result<Vec<u8>> {
+    call(evm::Precompile::Bn254Pairing.func_id(), input)
+}
+
+/// EIP-2537 BLS12_G1ADD on BLS12-381
+pub fn bls12_g1_add(input: &[u8]) -> Result<Vec<u8>> {
+    call(evm::Precompile::Bls12381G1Add.func_id(), input)
+}
+
+/// EIP-2537 BLS12_G1MUL on BLS12-381
+pub fn bls12_g1_mul(input: &[u8]) -> Result<Vec<u8>> {
+    call(evm::Precompile::Bls12381G1Mul.func_id(), input)
+}
+
+/// EIP-2537 BLS12_G1MSM on BLS12-381
+pub fn bls12_g1_msm(input: &[u8]) -> Result<Vec<u8>> {
+    call(evm::Precompile::Bls12381G1Msm.func_id(), input)
+}
+
+/// EIP-2537 BLS12_G2ADD on BLS12-381
+pub fn bls12_g2_add(input: &[u8]) -> Result<Vec<u8>> {
+    call(evm::Precompile::Bls12381G2Add.func_id(), input)
+}
+
+/// EIP-2537 BLS12_G2MUL on BLS12-381
+pub fn bls12_g2_mul(input: &[u8]) -> Result<Vec<u8>> {
+    call(evm::Precompile::Bls12381G2Mul.func_id(), input)
+}
+
+/// EIP-2537 BLS12_G2MSM on BLS12-381
+pub fn bls12_g2_msm(input: &[u8]) -> Result<Vec<u8>> {
+    call(evm::Precompile::Bls12381G2Msm.func_id(), input)
+}
+
+/// EIP-2537 BLS12_PAIRING on BLS12-381
+pub fn bls12_pairing(input: &[u8]) -> Result<Vec<u8>> {
+    call(evm::Precompile::Bls12381Pairing.func_id(), input)
+}
+
+/// EIP-2537 BLS12_MAP_FP_TO_G1 on BLS12-381
+pub fn bls12_map_fp_to_g1(input: &[u8]) -> Result<Vec<u8>> {
+    call(evm::Precompile::Bls12381MapFpToG1.func_id(), input)
+}
+
+/// EIP-2537 BLS12_MAP_FP2_TO_G2 on BLS12-381
+pub fn bls12_map_fp2_to_g2(input: &[u8]) -> Result<Vec<u8>> {
+    call(evm::Precompile::Bls12381MapFp2ToG2.func_id(), input)
 }
 
 /// Groth16 Verify
@@ -104,22 +235,47 @@ pub fn verify(
     proof: Vec<u8>,
     public_inputs: Vec<Vec<u8>>,
 ) -> Result<bool> {
-    match curve_id {
-        0x00 => groth16::verify_proof::<curve::Bls12_377>(vk_gamma_abc, vk, proof, public_inputs),
-        0x10 => groth16::verify_proof::<curve::Bls12_381>(vk_gamma_abc, vk, proof, public_inputs),
-        0x20 => groth16::verify_proof::<curve::Bn254>(vk_gamma_abc, vk, proof, public_inputs),
-        0x30 => groth16::verify_proof::<curve::BW6_761>(vk_gamma_abc, vk, proof, public_inputs),
-        id => Err(Error::InvalidFunctionId),
-    }
+    CurveId::try_from(curve_id)?.verify(vk_gamma_abc, vk, proof, public_inputs)
 }
 
 /// Groth16 Verify Parcel
 pub fn verify_parcel(curve_id: u32, parcel: Vec<u8>) -> Result<bool> {
-    match curve_id {
-        0x00 => groth16::verify::<curve::Bls12_377>(parcel),
-        0x10 => groth16::verify::<curve::Bls12_381>(parcel),
-        0x20 => groth16::verify::<curve::Bn254>(parcel),
-        0x30 => groth16::verify::<curve::BW6_761>(parcel),
-        id => Err(Error::InvalidFunctionId),
-    }
+    CurveId::try_from(curve_id)?.verify_parcel(parcel)
+}
+
+/// Groth16 Verify with compressed points
+pub fn verify_compressed(
+    curve_id: u32,
+    vk_gamma_abc: Vec<Vec<u8>>,
+    vk: Vec<u8>,
+    proof: Vec<u8>,
+    public_inputs: Vec<Vec<u8>>,
+) -> Result<bool> {
+    CurveId::try_from(curve_id)?.verify_compressed(vk_gamma_abc, vk, proof, public_inputs)
+}
+
+/// Groth16 Verify Parcel with compressed points
+pub fn verify_parcel_compressed(curve_id: u32, parcel: Vec<u8>) -> Result<bool> {
+    CurveId::try_from(curve_id)?.verify_parcel_compressed(parcel)
+}
+
+/// Groth16 Verify of `CanonicalSerialize` key, proof and public inputs in the
+/// canonical `serialization`, see [`groth16::verify_proof_canonical`]
+pub fn verify_canonical(
+    curve_id: u32,
+    serialization: Serialization,
+    vk: Vec<u8>,
+    proof: Vec<u8>,
+    public_inputs: Vec<u8>,
+) -> Result<bool> {
+    CurveId::try_from(curve_id)?.verify_canonical(serialization, vk, proof, public_inputs)
+}
+
+/// Groth16 Verify `CanonicalSerialize` Parcel in the canonical `serialization`
+pub fn verify_parcel_canonical(
+    curve_id: u32,
+    serialization: Serialization,
+    parcel: Vec<u8>,
+) -> Result<bool> {
+    CurveId::try_from(curve_id)?.verify_parcel_canonical(serialization, parcel)
 }
@@ -0,0 +1,177 @@
+//! BLS12-377 map to curve
+//!
+//! RFC 9380 has no BLS12-377 suites, G1 and G2 are mapped with
+//! Shallue-van de Woestijne as `BLS12377G1_XMD:SHA-256_SVDW_RO_` and
+//! `BLS12377G2_XMD:SHA-256_SVDW_RO_`. `Z` is the first `Z` of the search of
+//! RFC 9380 appendix H.1, `1` on G1 and `2` on G2, and the cofactors are
+//! cleared by multiplying them.
+
+use ark_bls12_377::{g1, g2, Fq, Fq2, G1Affine, G2Affine};
+use ark_ec::{AffineCurve, SWModelParameters};
+use ark_ff::field_new;
+
+use super::{hash_to_field, svdw, SvdwParams};
+
+/// The curve of G1, `y^2 = x^3 + 1`
+const G1_SVDW: SvdwParams<Fq> = SvdwParams {
+    a: g1::Parameters::COEFF_A,
+    b: g1::Parameters::COEFF_B,
+    z: field_new!(Fq, "1"),
+    c1: field_new!(Fq, "2"),
+    c2: field_new!(Fq, "129332213006484547005326366847446766768196756377457330269942131333360234174170411387484444069786680062220160729088"),
+    c3: field_new!(Fq, "161015587002303879183411490966296758198305781022063779778351547628381405527220668155877587635850550163400924107162"),
+    c4: field_new!(Fq, "172442950675312729340435155796595689024262341836609773693256175111146978898893881849979258759715573416293547638782"),
+};
+
+/// The curve of G2, `y^2 = x^3 + 1 / u`
+const G2_SVDW: SvdwParams<Fq2> = SvdwParams {
+    a: g2::Parameters::COEFF_A,
+    b: g2::Parameters::COEFF_B,
+    z: field_new!(Fq2, field_new!(Fq, "2"), field_new!(Fq, "0")),
+    c1: field_new!(
+        Fq2,
+        field_new!(Fq, "8"),
+        field_new!(Fq, "155198655607781456406391640216936120121836107652948796323930557600032281009004493664981332883744016074664192874906")
+    ),
+    c2: field_new!(Fq2, field_new!(Fq, "-1"), field_new!(Fq, "0")),
+    c3: field_new!(
+        Fq2,
+        field_new!(Fq, "176167996011041038227027268172818786419688168714579319282718903616278011457418412984061471725424117204123011379096"),
+        field_new!(Fq, "209524757051059563433482416591076552384687058796958840533487795092388893314652805681173445630970430848221848549578")
+    ),
+    c4: field_new!(
+        Fq2,
+        field_new!(Fq, "172442950675312729340435155796595689024262341836609773693256175111146978898893881849979258759715573416293547638782"),
+        field_new!(Fq, "34488590135062545868087031159319137804852468367321954738651235022229395779778776369995851751943114683258709527757")
+    ),
+};
+
+/// Map a field element to G1, the result is not cleared
+pub fn map_to_curve_g1(u: Fq) -> G1Affine {
+    let (x, y) = svdw(&G1_SVDW, u);
+    G1Affine::new(x, y, false)
+}
+
+/// Map a field element to G2, the result is not cleared
+pub fn map_to_curve_g2(u: Fq2) -> G2Affine {
+    let (x, y) = svdw(&G2_SVDW, u);
+    G2Affine::new(x, y, false)
+}
+
+/// Hash `msg` to G1 with the domain separation tag `dst`
+pub fn hash_to_g1(msg: &[u8], dst: &[u8]) -> G1Affine {
+    let u = hash_to_field::<Fq>(msg, dst, 2);
+    (map_to_curve_g1(u[0]) + map_to_curve_g1(u[1])).mul_by_cofactor()
+}
+
+/// Hash `msg` to G2 with the domain separation tag `dst`
+pub fn hash_to_g2(msg: &[u8], dst: &[u8]) -> G2Affine {
+    let u = hash_to_field::<Fq>(msg, dst, 4);
+    (map_to_curve_g2(Fq2::new(u[0], u[1])) + map_to_curve_g2(Fq2::new(u[2], u[3])))
+        .mul_by_cofactor()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_to_curve() {
+        use ark_ff::{One, Zero};
+
+        assert_eq!(
+            SvdwParams::find(G1_SVDW.a, G1_SVDW.b),
+            G1_SVDW,
+            "Z and the constants of the RFC"
+        );
+        assert_eq!(
+            SvdwParams::find(G2_SVDW.a, G2_SVDW.b),
+            G2_SVDW,
+            "Z and the constants of the RFC"
+        );
+
+        // known answers, computed independently of arkworks
+        assert_eq!(
+            map_to_curve_g1(Fq::zero()),
+            G1Affine::new(
+                field_new!(Fq, "129332213006484547005326366847446766768196756377457330269942131333360234174170411387484444069786680062220160729088"),
+                field_new!(Fq, "247479600692828234290951608940965786198307108640240991802625687807560545350285117415453679668498089251233628892050"),
+                false
+            )
+        );
+        assert_eq!(
+            map_to_curve_g1(-Fq::one()),
+            G1Affine::new(
+                field_new!(Fq, "258664426012969094010652733694893533536393512754914660539884262666720468348340822774968888139573360124440321458154"),
+                field_new!(Fq, "52500804401469244260336800018312214617751606268573521520804888237272613474139214247611214475351242276902849805974"),
+                false
+            )
+        );
+        assert_eq!(
+            map_to_curve_g2(Fq2::new(field_new!(Fq, "5"), field_new!(Fq, "7"))),
+            G2Affine::new(
+                field_new!(
+                    Fq2,
+                    field_new!(Fq, "157648257718601231659437880241689771363773124556367571114804126610668580907825307573083034255783060208640633368750"),
+                    field_new!(Fq, "41339996350491436322035074079805878082999475335853755898887239590456260095813623834526216216572788518279335986443")
+                ),
+                field_new!(
+                    Fq2,
+                    field_new!(Fq, "100250815060117724026858000056617029494598084628142399348211380757631003534694164138436408073078217594777528666627"),
+                    field_new!(Fq, "86905821400735203738365573698141178151547468687670153892120023142434499904319961949858416166581548878343737061287")
+                ),
+                false
+            )
+        );
+
+        let dst = b"QUUX-V01-CS02-with-BLS12377G1_XMD:SHA-256_SVDW_RO_";
+        let g1 = hash_to_g1(b"abc", dst);
+        assert!(g1.is_on_curve() && g1.is_in_correct_subgroup_assuming_on_curve());
+        assert_eq!(
+            g1,
+            G1Affine::new(
+                field_new!(Fq, "115868840133496761296458638901199588397958029987242810661200183593901149567270482653357904005353457463642779050531"),
+                field_new!(Fq, "98382909079735478427619013607531652235083884131037892389793365001960234035143828370331992869801654427911281535710"),
+                false
+            )
+        );
+        assert_ne!(g1, hash_to_g1(b"abd", dst));
+
+        let dst = b"QUUX-V01-CS02-with-BLS12377G2_XMD:SHA-256_SVDW_RO_";
+        let g2 = hash_to_g2(b"abc", dst);
+        assert!(g2.is_on_curve() && g2.is_in_correct_subgroup_assuming_on_curve());
+        assert_eq!(
+            g2,
+            G2Affine::new(
+                field_new!(
+                    Fq2,
+                    field_new!(Fq, "194235013039719318993657341487413947812008860824852111652722134286394161585073373762991265847128062135108870834128"),
+                    field_new!(Fq, "61603075753659268160557008281781100093820274261416698465442276555188122682078863150316979820672201861432020993252")
+                ),
+                field_new!(
+                    Fq2,
+                    field_new!(Fq, "196155438781014552453111622351308327449622819475485379918622851240750325996065260168288028540461389042667906338797"),
+                    field_new!(Fq, "32230311516416426908346891119394622561233187163602153007972226838896365413525876146866759402302394193552780746323")
+                ),
+                false
+            )
+        );
+        assert_eq!(
+            hash_to_g2(b"", dst),
+            G2Affine::new(
+                field_new!(
+                    Fq2,
+                    field_new!(Fq, "190955461573567000865269267528362167211069076262550666830439441963431279995894239041794345238287370456620217963664"),
+                    field_new!(Fq, "176721373618845104953446694246988926098098769462125644930384122926695070632633151558317501812584280339290968379839")
+                ),
+                field_new!(
+                    Fq2,
+                    field_new!(Fq, "10652953648419141375111615337285826093311466888889365367837299804821789548128941112525016910526734927214707378276"),
+                    field_new!(Fq, "31378453920514466995367094203754819224583796500577019884580397324221003964740134384488826667546854096487763869026")
+                ),
+                false
+            )
+        );
+        assert_ne!(g2, hash_to_g2(b"abd", dst));
+    }
+}
@@ -0,0 +1,114 @@
+//! BN254 map to curve
+//!
+//! G1 is mapped with Shallue-van de Woestijne, `Z = 1` is the first `Z` of
+//! the search of RFC 9380 appendix H.1. RFC 9380 has no BN254 suite, hashing
+//! to G1 follows its construction as `BN254G1_XMD:SHA-256_SVDW_RO_`. G1 has
+//! cofactor one, so there is nothing to clear.
+
+use ark_bn254::{Fq, G1Affine};
+use ark_ff::field_new;
+
+use super::{hash_to_field, svdw, SvdwParams};
+
+/// The curve of G1, `y^2 = x^3 + 3`
+const G1_SVDW: SvdwParams<Fq> = SvdwParams {
+    a: field_new!(Fq, "0"),
+    b: field_new!(Fq, "3"),
+    z: field_new!(Fq, "1"),
+    c1: field_new!(Fq, "4"),
+    c2: field_new!(
+        Fq,
+        "10944121435919637611123202872628637544348155578648911831344518947322613104291"
+    ),
+    c3: field_new!(
+        Fq,
+        "8815841940592487685674414971303048083897117035520822607866"
+    ),
+    c4: field_new!(
+        Fq,
+        "7296080957279758407415468581752425029565437052432607887563012631548408736189"
+    ),
+};
+
+/// Map a field element to G1
+pub fn map_to_curve_g1(u: Fq) -> G1Affine {
+    let (x, y) = svdw(&G1_SVDW, u);
+    G1Affine::new(x, y, false)
+}
+
+/// Hash `msg` to G1 with the domain separation tag `dst`
+pub fn hash_to_g1(msg: &[u8], dst: &[u8]) -> G1Affine {
+    let u = hash_to_field::<Fq>(msg, dst, 2);
+    map_to_curve_g1(u[0]) + map_to_curve_g1(u[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_to_g1() {
+        use ark_ff::{One, Zero};
+
+        let dst = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_";
+        for u in [Fq::zero(), Fq::one(), -Fq::one(), field_new!(Fq, "5")].iter() {
+            let point = map_to_curve_g1(*u);
+            assert!(point.is_on_curve());
+        }
+
+        let point = hash_to_g1(b"abc", dst);
+        assert!(point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve());
+        assert_eq!(point, hash_to_g1(b"abc", dst));
+        assert_eq!(
+            SvdwParams::find(G1_SVDW.a, G1_SVDW.b),
+            G1_SVDW,
+            "Z and the constants of the RFC"
+        );
+        assert_ne!(point, hash_to_g1(b"abd", dst));
+
+        // known answers, computed independently of arkworks
+        assert_eq!(
+            map_to_curve_g1(Fq::zero()),
+            G1Affine::new(
+                field_new!(
+                    Fq,
+                    "10944121435919637611123202872628637544348155578648911831344518947322613104291"
+                ),
+                field_new!(
+                    Fq,
+                    "4718603453640367770405249522358112449463417117041194427604452040985121683380"
+                ),
+                false
+            )
+        );
+        assert_eq!(
+            map_to_curve_g1(field_new!(Fq, "5")),
+            G1Affine::new(
+                field_new!(
+                    Fq,
+                    "20262878302148239933902159870143739200212645228355066860207035924029037262790"
+                ),
+                field_new!(
+                    Fq,
+                    "9758744144075580486226292637147400604374287218633575049759831181260788318551"
+                ),
+                false
+            )
+        );
+        assert_eq!(
+            point,
+            G1Affine::new(
+                field_new!(
+                    Fq,
+                    "16267524812466668166267883771992486438338357688076900798565538061554532963281"
+                ),
+                field_new!(
+                    Fq,
+                    "1844916233815282837483764409618609279507070495361570126601873459268232811805"
+                ),
+                false
+            )
+        );
+        assert_ne!(point, hash_to_g1(b"abc", b"another tag"));
+    }
+}
@@ -0,0 +1,301 @@
+//! Map field elements to curve points
+//!
+//! Simplified SWU and Shallue-van de Woestijne maps, isogeny evaluation and
+//! `expand_message_xmd` hashing to fields of RFC 9380, the curve specific
+//! constants and the hash to curve suites live in the curve modules.
+
+pub mod bls12_377;
+pub mod bls12_381;
+pub mod bn254;
+
+use ark_ff::{
+    BigInteger, Field, Fp256, Fp256Parameters, Fp384, Fp384Parameters, FpParameters, PrimeField,
+    QuadExtField, QuadExtParameters, SquareRootField, Zero,
+};
+use ark_std::vec::Vec;
+use sha2::{Digest, Sha256};
+
+/// Output bytes of SHA-256
+const B_IN_BYTES: usize = 32;
+
+/// Input block bytes of SHA-256
+const S_IN_BYTES: usize = 64;
+
+/// Prefix of hashed domain separation tags longer than 255 bytes
+const OVERSIZE_DST: &[u8] = b"H2C-OVERSIZE-DST-";
+
+/// `expand_message_xmd` of RFC 9380 section 5.3.1 with SHA-256
+///
+/// Tags longer than 255 bytes are hashed as section 5.3.3, panics if
+/// `len_in_bytes` is larger than `255 * 32`.
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+    let ell = (len_in_bytes + B_IN_BYTES - 1) / B_IN_BYTES;
+    assert!(
+        ell <= 255,
+        "len_in_bytes of expand_message_xmd is too large"
+    );
+
+    let oversize;
+    let dst = if dst.len() > 255 {
+        oversize = Sha256::new().chain(OVERSIZE_DST).chain(dst).finalize();
+        &oversize[..]
+    } else {
+        dst
+    };
+    let dst_len = [dst.len() as u8];
+
+    let b_0 = Sha256::new()
+        .chain([0u8; S_IN_BYTES])
+        .chain(msg)
+        .chain((len_in_bytes as u16).to_be_bytes())
+        .chain([0u8])
+        .chain(dst)
+        .chain(dst_len)
+        .finalize();
+
+    let mut uniform = Vec::with_capacity(ell * B_IN_BYTES);
+    let mut b_i = Sha256::new()
+        .chain(b_0)
+        .chain([1u8])
+        .chain(dst)
+        .chain(dst_len)
+        .finalize();
+    uniform.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        let mut xor = b_0;
+        xor.iter_mut().zip(b_i.iter()).for_each(|(x, b)| *x ^= b);
+        b_i = Sha256::new()
+            .chain(xor)
+            .chain([i as u8])
+            .chain(dst)
+            .chain(dst_len)
+            .finalize();
+        uniform.extend_from_slice(&b_i);
+    }
+    uniform.truncate(len_in_bytes);
+    uniform
+}
+
+/// `hash_to_field` of RFC 9380 section 5.2 with `expand_message_xmd`, `count`
+/// elements of the prime field `F` at the security level `k = 128`
+///
+/// Extension field elements of degree `m` are the following `m` elements.
+pub fn hash_to_field<F: PrimeField>(msg: &[u8], dst: &[u8], count: usize) -> Vec<F> {
+    let len = (<F::Params as FpParameters>::MODULUS_BITS as usize + 128 + 7) / 8;
+    expand_message_xmd(msg, dst, count * len)
+        .chunks(len)
+        .map(F::from_be_bytes_mod_order)
+        .collect()
+}
+
+/// The `sgn0` function of RFC 9380 section 4.1
+pub trait Sgn0 {
+    /// Sign of the field element, `true` for odd
+    fn sgn0(&self) -> bool;
+}
+
+impl<P: Fp256Parameters> Sgn0 for Fp256<P> {
+    fn sgn0(&self) -> bool {
+        self.into_repr().is_odd()
+    }
+}
+
+impl<P: Fp384Parameters> Sgn0 for Fp384<P> {
+    fn sgn0(&self) -> bool {
+        self.into_repr().is_odd()
+    }
+}
+
+impl<P: QuadExtParameters> Sgn0 for QuadExtField<P>
+where
+    P::BaseField: Sgn0,
+{
+    fn sgn0(&self) -> bool {
+        self.c0.sgn0() || (self.c0.is_zero() && self.c1.sgn0())
+    }
+}
+
+/// Simplified SWU parameters of the curve `y^2 = x^3 + A * x + B`
+pub struct SwuParams<F> {
+    pub a: F,
+    pub b: F,
+    pub z: F,
+}
+
+/// Simplified SWU map, returns the affine coordinates on the curve of `params`
+///
+/// The straight-line implementation of RFC 9380 section 6.6.2.
+pub fn swu<F: SquareRootField + Sgn0>(params: &SwuParams<F>, u: F) -> (F, F) {
+    let SwuParams { a, b, z } = params;
+    let g = |x: F| x.square() * x + *a * x + b;
+
+    let zu2 = *z * u.square();
+    let tv1 = (zu2.square() + zu2).inverse().unwrap_or_else(F::zero);
+    let x1 = if tv1.is_zero() {
+        *b / (*z * a)
+    } else {
+        -*b / a * (F::one() + tv1)
+    };
+    let (x, mut y) = match g(x1).sqrt() {
+        Some(y) => (x1, y),
+        None => {
+            let x2 = zu2 * x1;
+            (x2, g(x2).sqrt().expect("g(x2) is square if g(x1) is not"))
+        }
+    };
+    if u.sgn0() != y.sgn0() {
+        y = -y;
+    }
+    (x, y)
+}
+
+/// Shallue-van de Woestijne parameters of the curve `y^2 = x^3 + A * x + B`
+/// and the constants `c1..c4` of RFC 9380 section 6.6.1 derived from them
+#[derive(Debug, PartialEq)]
+pub struct SvdwParams<F> {
+    pub a: F,
+    pub b: F,
+    pub z: F,
+    pub c1: F,
+    pub c2: F,
+    pub c3: F,
+    pub c4: F,
+}
+
+impl<F: SquareRootField + Sgn0> SvdwParams<F> {
+    /// Parameters of the curve `y^2 = x^3 + A * x + B` with `Z`, derives
+    /// `c1..c4`
+    pub fn new(a: F, b: F, z: F) -> Self {
+        let g = |x: F| x.square() * x + a * x + b;
+        let two = F::one().double();
+        let four = two.double();
+
+        let c1 = g(z);
+        let c2 = -z / two;
+        let h = z.square() * F::from(3u8) + four * a;
+        let mut c3 = (-c1 * h)
+            .sqrt()
+            .expect("-g(Z) * (3 * Z^2 + 4 * A) is square");
+        if c3.sgn0() {
+            c3 = -c3;
+        }
+        let c4 = -four * c1 / h;
+        SvdwParams {
+            a,
+            b,
+            z,
+            c1,
+            c2,
+            c3,
+            c4,
+        }
+    }
+
+    /// Parameters of the curve `y^2 = x^3 + A * x + B` with the `Z` found by
+    /// `find_z_svdw` of RFC 9380 appendix H.1
+    pub fn find(a: F, b: F) -> Self {
+        let g = |x: F| x.square() * x + a * x + b;
+        let is_square = |x: F| x.sqrt().is_some();
+        let three = F::from(3u8);
+        let four = F::from(4u8);
+
+        let mut ctr = F::one();
+        loop {
+            for z in [ctr, -ctr].iter() {
+                let gz = g(*z);
+                if gz.is_zero() {
+                    continue;
+                }
+                let h = -(three * z.square() + four * a) / (four * gz);
+                if h.is_zero() || !is_square(h) {
+                    continue;
+                }
+                if is_square(gz) || is_square(g(-*z / F::from(2u8))) {
+                    return Self::new(a, b, *z);
+                }
+            }
+            ctr += F::one();
+        }
+    }
+}
+
+/// Shallue-van de Woestijne map, returns the affine coordinates on the curve
+/// of `params`
+///
+/// The straight-line implementation of RFC 9380 section 6.6.1.
+pub fn svdw<F: SquareRootField + Sgn0>(params: &SvdwParams<F>, u: F) -> (F, F) {
+    let SvdwParams {
+        a,
+        b,
+        z,
+        c1,
+        c2,
+        c3,
+        c4,
+    } = *params;
+    let g = |x: F| x.square() * x + a * x + b;
+
+    let tv1 = u.square() * c1;
+    let tv2 = F::one() + tv1;
+    let tv1 = F::one() - tv1;
+    let tv3 = (tv1 * tv2).inverse().unwrap_or_else(F::zero);
+    let tv4 = u * tv1 * tv3 * c3;
+    let x1 = c2 - tv4;
+    let x2 = c2 + tv4;
+    let x3 = z + c4 * (tv2.square() * tv3).square();
+
+    let (x, mut y) = match g(x1).sqrt() {
+        Some(y) => (x1, y),
+        None => match g(x2).sqrt() {
+            Some(y) => (x2, y),
+            None => (
+                x3,
+                g(x3)
+                    .sqrt()
+                    .expect("g(x3) is square if g(x1), g(x2) are not"),
+            ),
+        },
+    };
+    if u.sgn0() != y.sgn0() {
+        y = -y;
+    }
+    (x, y)
+}
+
+/// Isogeny map coefficients, in ascending order
+pub struct IsogenyMap<F: 'static> {
+    pub x_num: &'static [F],
+    pub x_den: &'static [F],
+    pub y_num: &'static [F],
+    pub y_den: &'static [F],
+}
+
+impl<F: Field> IsogenyMap<F> {
+    /// Map `(x, y)` through the isogeny, `None` for the point at infinity
+    pub fn apply(&self, x: F, y: F) -> Option<(F, F)> {
+        let eval = |coeffs: &[F]| coeffs.iter().rev().fold(F::zero(), |acc, c| acc * x + c);
+
+        let x_den = eval(self.x_den).inverse()?;
+        let y_den = eval(self.y_den).inverse()?;
+        Some((eval(self.x_num) * x_den, y * eval(self.y_num) * y_den))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_message_xmd() {
+        // RFC 9380 appendix K.1
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(
+            hex::encode(expand_message_xmd(b"", dst, 0x20)),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        assert_eq!(
+            hex::encode(expand_message_xmd(b"abc", dst, 0x20)),
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! Off-chain mock of the chain extension
+//!
+//! With the `ink` feature `call` goes through the chain extension, which the
+//! off-chain environment of ink doesn't have. [`register`] registers a handler
+//! of every function id in the off-chain environment, running the native
+//! operations the same as the runtime, so contract unit tests can call the
+//! curve functions and `verify` in `cargo test`.
+//!
+//! ```ignore
+//! #[ink::test]
+//! fn verify_works() {
+//!     zkmega_arkworks::mock::register();
+//!     assert!(zkmega_arkworks::verify_parcel(0x20, parcel).unwrap());
+//! }
+//! ```
+
+use ink_env::test::{register_chain_extension, ChainExtension};
+
+use crate::{registry, Vec};
+
+/// Chain extension of one function id
+pub struct CurveExtension {
+    func_id: u32,
+}
+
+impl CurveExtension {
+    pub fn new(func_id: u32) -> Self {
+        CurveExtension { func_id }
+    }
+}
+
+impl ChainExtension for CurveExtension {
+    fn func_id(&self) -> u32 {
+        self.func_id
+    }
+
+    fn call(&mut self, input: &[u8], output: &mut Vec<u8>) -> u32 {
+        output.extend(registry::dispatch_encoded(self.func_id, input));
+        0
+    }
+}
+
+/// Register the chain extension of every function id in the off-chain
+/// environment of the current thread
+pub fn register() {
+    for func_id in registry::func_ids() {
+        register_chain_extension(CurveExtension::new(func_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock() {
+        use ark_ec::PairingEngine;
+        use ark_ff::{Field, ToBytes};
+        use ark_groth16::{create_random_proof, generate_random_parameters};
+        use ark_relations::{
+            lc,
+            r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError},
+        };
+        use ark_std::{test_rng, UniformRand};
+
+        use crate::{curve::Bn254, CurveId, Error, Operation, PairingOperations};
+
+        /// Knowledge of a square root of the public input
+        struct Square<F: Field>(Option<F>);
+
+        impl<F: Field> ConstraintSynthesizer<F> for Square<F> {
+            fn generate_constraints(
+                self,
+                cs: ConstraintSystemRef<F>,
+            ) -> Result<(), SynthesisError> {
+                let root = self.0.ok_or(SynthesisError::AssignmentMissing);
+                let x = cs.new_witness_variable(|| root)?;
+                let y = cs.new_input_variable(|| root.map(|x| x.square()))?;
+                cs.enforce_constraint(lc!() + x, lc!() + x, lc!() + y)
+            }
+        }
+
+        fn write(bytes: &mut Vec<u8>, value: impl ToBytes) {
+            value.write(bytes).unwrap();
+        }
+
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            register();
+
+            // the curve functions
+            let g1 = crate::hash_to_g1(0x20, b"dst", b"msg").unwrap();
+            assert_eq!(
+                crate::add(0x20, &[g1.clone(), g1.clone()].concat()).unwrap(),
+                Bn254::add(&[g1.clone(), g1].concat()).unwrap()
+            );
+            assert!(crate::pairing_check(0x20, &[]).unwrap());
+            assert_eq!(crate::describe().unwrap(), crate::abi::describe());
+            assert_eq!(
+                crate::call(CurveId::Bn254.func_id(Operation::HashToG2), &[]),
+                Err(Error::UnsupportedOperation)
+            );
+
+            // groth16 verify end to end
+            let rng = &mut test_rng();
+            let root = <Bn254 as PairingEngine>::Fr::rand(rng);
+            let params = generate_random_parameters::<Bn254, _, _>(Square(None), rng).unwrap();
+            let proof = create_random_proof(Square(Some(root)), &params, rng).unwrap();
+
+            let vk_gamma_abc = params
+                .vk
+                .gamma_abc_g1
+                .iter()
+                .map(|g1| {
+                    let mut bytes = Vec::new();
+                    write(&mut bytes, g1);
+                    bytes
+                })
+                .collect::<Vec<_>>();
+            let mut vk = Vec::new();
+            write(&mut vk, params.vk.gamma_g2);
+            write(&mut vk, params.vk.delta_g2);
+            write(&mut vk, params.vk.alpha_g1);
+            write(&mut vk, params.vk.beta_g2);
+            let mut raw_proof = Vec::new();
+            write(&mut raw_proof, proof.a);
+            write(&mut raw_proof, proof.b);
+            write(&mut raw_proof, proof.c);
+            let mut square = Vec::new();
+            write(&mut square, root.square());
+
+            assert!(crate::verify(
+                0x20,
+                vk_gamma_abc.clone(),
+                vk.clone(),
+                raw_proof.clone(),
+                vec![square]
+            )
+            .unwrap());
+            let mut wrong = Vec::new();
+            write(&mut wrong, root);
+            assert!(!crate::verify(0x20, vk_gamma_abc, vk, raw_proof, vec![wrong]).unwrap());
+            Ok(())
+        })
+        .unwrap();
+    }
+}
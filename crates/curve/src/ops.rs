@@ -1,49 +1,102 @@
 //! Curve Operations
 
-pub use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
-pub use ark_ff::{FromBytes, One, PrimeField, ToBytes, Zero};
-pub use ark_serialize::SerializationError;
+pub use ark_ec::{
+    msm::VariableBaseMSM, short_weierstrass_jacobian::GroupAffine,
+    twisted_edwards_extended::GroupAffine as EdwardsAffine, AffineCurve, PairingEngine,
+    ProjectiveCurve, SWModelParameters, TEModelParameters,
+};
+use ark_ec::{
+    short_weierstrass_jacobian::GroupProjective,
+    twisted_edwards_extended::GroupProjective as EdwardsProjective,
+};
+pub use ark_ff::{
+    batch_inversion, BigInteger, Field, FpParameters, FromBytes, One, PrimeField, ToBytes, Zero,
+};
+use ark_ff::{
+    CubicExtField, CubicExtParameters, Fp256, Fp320, Fp384, Fp768, Fp832, QuadExtField,
+    QuadExtParameters,
+};
+pub use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_serialize::{CanonicalDeserializeWithFlags, EdwardsFlags, SWFlags};
 pub use ark_std::{
-    io::{Error, ErrorKind},
     ops::{MulAssign, Neg},
     str::FromStr,
     test_rng,
     vec::Vec,
     UniformRand,
 };
+use parity_scale_codec::{Compact, Decode};
+
+use crate::{
+    error::{Error, Result},
+    fixed_base::FixedBaseTable,
+};
+
+/// The former name of [`PairingOperations`]
+pub use self::PairingOperations as CurveBasicOperations;
+
+/// Operations of a prime order group
+///
+/// Curves without pairing only implement the group operations, their group
+/// takes the place of G1 and uses its function ids.
+pub trait GroupOperations {
+    /// Affine points of the group
+    type Affine: AffineCurve;
 
-/// Arkworks Curve Ops
-pub trait CurveBasicOperations: PairingEngine {
     // curve basic parameters
     const SCALAR_FIELD: &'static str;
     const MODULUS: &'static [u8];
     // G1 bytes length
     const G1_LEN: usize;
-    // G2 bytes length
-    const G2_LEN: usize;
     // Scalar bytes length
     const SCALAR_LEN: usize;
     // Curve ID
     const CURVE_ID: u32;
+    // Suite id of hashing to G1, if supported
+    const HASH_TO_G1: Option<&'static str> = None;
+    // Layout of the field elements of the points and scalars
+    const ENCODING: &'static str = "little-endian integers of whole 64-bit limbs";
+
+    /// Check the G1 point is on curve and in the prime order subgroup
+    fn validate_g1(point: &Self::Affine) -> Result<()>;
+
+    /// Window table of the G1 generator, built on first use and kept for the
+    /// process
+    fn g1_generator_table() -> &'static FixedBaseTable<Self::Affine>;
+
+    /// Swap G1 points in constant time, see [`ConditionalSwap`]
+    fn swap_g1(
+        a: &mut <Self::Affine as AffineCurve>::Projective,
+        b: &mut <Self::Affine as AffineCurve>::Projective,
+        choice: bool,
+    );
+
+    /// Read a `CanonicalSerialize` compressed G1 point from `input` without
+    /// validating it, see [`Decompress`]
+    fn decompress_g1(input: &mut &[u8]) -> Result<Self::Affine>;
 
-    fn add(input: &[u8]) -> Result<Vec<u8>, SerializationError> {
+    /// Read a `ToBytes` G1 point without validating it, see [`ReadPoint`]
+    fn read_g1_unchecked(bytes: &[u8]) -> Result<Self::Affine>;
+
+    /// Read and validate compressed G1 point
+    fn read_g1_compressed(bytes: &[u8]) -> Result<Self::Affine> {
+        let point = Self::decompress_g1(&mut &*bytes)?;
+        Self::validate_g1(&point)?;
+        Ok(point)
+    }
+
+    /// Read and validate G1 point
+    fn read_g1(bytes: &[u8]) -> Result<Self::Affine> {
+        let point = Self::read_g1_unchecked(bytes)?;
+        Self::validate_g1(&point)?;
+        Ok(point)
+    }
+
+    fn add(input: &[u8]) -> Result<Vec<u8>> {
         // g1 infinity is bool, so two g1s should be + 2 byte.
-        if input.len() != Self::G1_LEN * 2 {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!(
-                    "add operation input invalid length, should be {}, \
-                     input length: {:?}",
-                    Self::G1_LEN * 2,
-                    input.len(),
-                ),
-            )
-            .into());
-        }
-        let point1 =
-            <<Self as PairingEngine>::G1Affine as FromBytes>::read(&input[0..Self::G1_LEN])?;
-        let point2 =
-            <<Self as PairingEngine>::G1Affine as FromBytes>::read(&input[Self::G1_LEN..])?;
+        Error::check_length(Self::G1_LEN * 2, input.len())?;
+        let point1 = Self::read_g1(&input[0..Self::G1_LEN])?;
+        let point2 = Self::read_g1(&input[Self::G1_LEN..])?;
 
         let sum_res = point1 + point2;
         let mut output = Vec::new();
@@ -52,23 +105,11 @@ pub trait CurveBasicOperations: PairingEngine {
         Ok(output)
     }
 
-    fn mul(input: &[u8]) -> Result<Vec<u8>, SerializationError> {
+    fn mul(input: &[u8]) -> Result<Vec<u8>> {
         // g1 infinity is bool, so + 1 byte.
-        if input.len() != Self::G1_LEN + Self::SCALAR_LEN {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!(
-                    "scalar_mul operation input invalid length, should be {}, \
-                     input length: {:?}",
-                    Self::G1_LEN + Self::SCALAR_LEN,
-                    input.len(),
-                ),
-            )
-            .into());
-        }
-        let point =
-            <<Self as PairingEngine>::G1Affine as FromBytes>::read(&input[0..Self::G1_LEN])?;
-        let scalar = <<Self as PairingEngine>::Fr as FromBytes>::read(&input[Self::G1_LEN..])?;
+        Error::check_length(Self::G1_LEN + Self::SCALAR_LEN, input.len())?;
+        let point = Self::read_g1(&input[0..Self::G1_LEN])?;
+        let scalar = read_scalar::<Scalar<Self>>(&input[Self::G1_LEN..])?;
 
         let mul_res = point.into_projective().mul(scalar.into_repr());
         let mut output = Vec::new();
@@ -76,39 +117,668 @@ pub trait CurveBasicOperations: PairingEngine {
         Ok(output)
     }
 
-    fn pairings(input: &[u8]) -> Result<bool, SerializationError> {
-        // g1 infinity is bool, so + 1 byte.
-        let g1_len = Self::G1_LEN;
-        // ditto, g1 g2 + 2.
-        let g1_g2_len = Self::G1_LEN + Self::G2_LEN;
-        if input.len() % g1_g2_len != 0 && !input.is_empty() {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!(
-                    "pairing operation input invalid length, should be {} \
-                     input length: {:?}",
-                    g1_g2_len,
-                    input.len(),
-                ),
-            )
-            .into());
+    /// Scalar mul in constant time for secret scalars, input: `G1 | Fr`, see
+    /// [`mul_secret`]
+    ///
+    /// It is not an operation of the chain, whose scalars are public, but of
+    /// the off-chain signers and provers.
+    fn mul_secret(input: &[u8]) -> Result<Vec<u8>> {
+        Error::check_length(Self::G1_LEN + Self::SCALAR_LEN, input.len())?;
+        let point = Self::read_g1(&input[0..Self::G1_LEN])?;
+        let scalar = read_scalar::<Scalar<Self>>(&input[Self::G1_LEN..])?;
+
+        let mut output = Vec::new();
+        mul_secret(&point, &scalar, Self::swap_g1)
+            .into_affine()
+            .write(&mut output)?;
+        Ok(output)
+    }
+
+    /// Scalar mul of the G1 generator with its window table, input: `Fr`
+    fn mul_generator(input: &[u8]) -> Result<Vec<u8>> {
+        fixed_base_mul(input, Self::SCALAR_LEN, Self::g1_generator_table())
+    }
+
+    fn add_compressed(input: &[u8]) -> Result<Vec<u8>> {
+        let g1_len = compressed_len::<Self::Affine>();
+        Error::check_length(g1_len * 2, input.len())?;
+        let point1 = Self::read_g1_compressed(&input[0..g1_len])?;
+        let point2 = Self::read_g1_compressed(&input[g1_len..])?;
+
+        let sum_res = point1 + point2;
+        let mut output = Vec::new();
+        sum_res.serialize(&mut output)?;
+        Ok(output)
+    }
+
+    fn mul_compressed(input: &[u8]) -> Result<Vec<u8>> {
+        let g1_len = compressed_len::<Self::Affine>();
+        Error::check_length(g1_len + Self::SCALAR_LEN, input.len())?;
+        let point = Self::read_g1_compressed(&input[0..g1_len])?;
+        let scalar = read_scalar::<Scalar<Self>>(&input[g1_len..])?;
+
+        let mul_res = point.into_projective().mul(scalar.into_repr());
+        let mut output = Vec::new();
+        mul_res.into_affine().serialize(&mut output)?;
+        Ok(output)
+    }
+
+    fn msm_g1(input: &[u8]) -> Result<Vec<u8>> {
+        msm(input, Self::G1_LEN, Self::SCALAR_LEN, Self::read_g1)
+    }
+
+    /// G1 multi-scalar mul without validating the points
+    fn msm_g1_trusted(input: &[u8]) -> Result<Vec<u8>> {
+        msm(
+            input,
+            Self::G1_LEN,
+            Self::SCALAR_LEN,
+            Self::read_g1_unchecked,
+        )
+    }
+
+    /// Hash to G1 with the RFC 9380 suite of this curve
+    ///
+    /// input: SCALE `Vec<u8>` domain separation tag, then the message
+    fn hash_to_g1(input: &[u8]) -> Result<Vec<u8>> {
+        Err(Error::UnsupportedOperation)
+    }
+
+    /// Sum of any number of G1 points, input: `G1*`, the sum of none is zero
+    fn sum_g1(input: &[u8]) -> Result<Vec<u8>> {
+        sum(input, Self::G1_LEN, Self::read_g1)
+    }
+
+    /// Scalar field add, input: `Fr | Fr`
+    fn fr_add(input: &[u8]) -> Result<Vec<u8>> {
+        fr_binary::<Scalar<Self>>(input, Self::SCALAR_LEN, |a, b| a + b)
+    }
+
+    /// Scalar field sub, input: `Fr | Fr`
+    fn fr_sub(input: &[u8]) -> Result<Vec<u8>> {
+        fr_binary::<Scalar<Self>>(input, Self::SCALAR_LEN, |a, b| a - b)
+    }
+
+    /// Scalar field mul, input: `Fr | Fr`
+    fn fr_mul(input: &[u8]) -> Result<Vec<u8>> {
+        fr_binary::<Scalar<Self>>(input, Self::SCALAR_LEN, |a, b| a * b)
+    }
+
+    /// Scalar field inverse, input: `Fr`, zero has no inverse
+    fn fr_inverse(input: &[u8]) -> Result<Vec<u8>> {
+        Error::check_length(Self::SCALAR_LEN, input.len())?;
+        let scalar = read_scalar::<Scalar<Self>>(input)?;
+
+        let mut output = Vec::new();
+        scalar
+            .inverse()
+            .ok_or(Error::NotInvertible)?
+            .write(&mut output)?;
+        Ok(output)
+    }
+
+    /// Scalar field power, input: `Fr | e`, the exponent `e` is a little-endian
+    /// integer of `SCALAR_LEN` bytes
+    fn fr_pow(input: &[u8]) -> Result<Vec<u8>> {
+        Error::check_length(Self::SCALAR_LEN * 2, input.len())?;
+        let scalar = read_scalar::<Scalar<Self>>(&input[..Self::SCALAR_LEN])?;
+        let exp =
+            <<Scalar<Self> as PrimeField>::BigInt as FromBytes>::read(&input[Self::SCALAR_LEN..])?;
+
+        let mut output = Vec::new();
+        scalar.pow(exp).write(&mut output)?;
+        Ok(output)
+    }
+
+    /// Scalar field batch inverse, input: `n | Fr*n`, zero has no inverse
+    fn fr_batch_inverse(input: &[u8]) -> Result<Vec<u8>> {
+        let mut input = input;
+        let count = <Compact<u32> as Decode>::decode(&mut input)?.0 as usize;
+        Error::check_length(count.saturating_mul(Self::SCALAR_LEN), input.len())?;
+
+        let mut scalars = input
+            .chunks(Self::SCALAR_LEN)
+            .map(read_scalar::<Scalar<Self>>)
+            .collect::<Result<Vec<_>>>()?;
+        if scalars.iter().any(Zero::is_zero) {
+            return Err(Error::NotInvertible);
         }
+        batch_inversion(&mut scalars);
+
+        let mut output = Vec::with_capacity(input.len());
+        for scalar in scalars {
+            scalar.write(&mut output)?;
+        }
+        Ok(output)
+    }
+}
+
+/// Scalar field of the group of `C`
+pub type Scalar<C> = <<C as GroupOperations>::Affine as AffineCurve>::ScalarField;
+
+/// Operations of a pairing friendly curve, the group operations run on G1
+pub trait PairingOperations:
+    PairingEngine + GroupOperations<Affine = <Self as PairingEngine>::G1Affine>
+{
+    // G2 bytes length
+    const G2_LEN: usize;
+    // Target group bytes length
+    const GT_LEN: usize;
+    // Suite id of hashing to G2, if supported
+    const HASH_TO_G2: Option<&'static str> = None;
+
+    /// Check the G2 point is on curve and in the prime order subgroup
+    fn validate_g2(point: &<Self as PairingEngine>::G2Affine) -> Result<()>;
+
+    /// Window table of the G2 generator, built on first use and kept for the
+    /// process
+    fn g2_generator_table() -> &'static FixedBaseTable<<Self as PairingEngine>::G2Affine>;
+
+    /// Swap G2 points in constant time, see [`ConditionalSwap`]
+    fn swap_g2(
+        a: &mut <Self as PairingEngine>::G2Projective,
+        b: &mut <Self as PairingEngine>::G2Projective,
+        choice: bool,
+    );
+
+    /// Read a `CanonicalSerialize` compressed G2 point from `input` without
+    /// validating it, see [`Decompress`]
+    fn decompress_g2(input: &mut &[u8]) -> Result<<Self as PairingEngine>::G2Affine>;
+
+    /// Read a `ToBytes` G2 point without validating it, see [`ReadPoint`]
+    fn read_g2_unchecked(bytes: &[u8]) -> Result<<Self as PairingEngine>::G2Affine>;
+
+    /// Read and validate compressed G2 point
+    fn read_g2_compressed(bytes: &[u8]) -> Result<<Self as PairingEngine>::G2Affine> {
+        let point = Self::decompress_g2(&mut &*bytes)?;
+        Self::validate_g2(&point)?;
+        Ok(point)
+    }
+
+    /// Read and validate G2 point
+    fn read_g2(bytes: &[u8]) -> Result<<Self as PairingEngine>::G2Affine> {
+        let point = Self::read_g2_unchecked(bytes)?;
+        Self::validate_g2(&point)?;
+        Ok(point)
+    }
+
+    /// Read and validate target group element
+    fn read_gt(bytes: &[u8]) -> Result<<Self as PairingEngine>::Fqk> {
+        let gt = <<Self as PairingEngine>::Fqk as FromBytes>::read(bytes)?;
+        validate_gt::<Self>(&gt)?;
+        Ok(gt)
+    }
+
+    fn g2_add(input: &[u8]) -> Result<Vec<u8>> {
+        // g2 infinity is bool, so two g2s should be + 2 byte.
+        Error::check_length(Self::G2_LEN * 2, input.len())?;
+        let point1 = Self::read_g2(&input[0..Self::G2_LEN])?;
+        let point2 = Self::read_g2(&input[Self::G2_LEN..])?;
+
+        let sum_res = point1 + point2;
+        let mut output = Vec::new();
+
+        sum_res.write(&mut output)?;
+        Ok(output)
+    }
+
+    fn g2_mul(input: &[u8]) -> Result<Vec<u8>> {
+        // g2 infinity is bool, so + 1 byte.
+        Error::check_length(Self::G2_LEN + Self::SCALAR_LEN, input.len())?;
+        let point = Self::read_g2(&input[0..Self::G2_LEN])?;
+        let scalar = read_scalar::<<Self as PairingEngine>::Fr>(&input[Self::G2_LEN..])?;
+
+        let mul_res = point.into_projective().mul(scalar.into_repr());
+        let mut output = Vec::new();
+        mul_res.into_affine().write(&mut output)?;
+        Ok(output)
+    }
+
+    /// G2 scalar mul in constant time for secret scalars, input: `G2 | Fr`,
+    /// see [`mul_secret`], computed locally like
+    /// [`GroupOperations::mul_secret`]
+    fn g2_mul_secret(input: &[u8]) -> Result<Vec<u8>> {
+        Error::check_length(Self::G2_LEN + Self::SCALAR_LEN, input.len())?;
+        let point = Self::read_g2(&input[0..Self::G2_LEN])?;
+        let scalar = read_scalar::<<Self as PairingEngine>::Fr>(&input[Self::G2_LEN..])?;
+
+        let mut output = Vec::new();
+        mul_secret(&point, &scalar, Self::swap_g2)
+            .into_affine()
+            .write(&mut output)?;
+        Ok(output)
+    }
+
+    /// Scalar mul of the G2 generator with its window table, input: `Fr`
+    fn g2_mul_generator(input: &[u8]) -> Result<Vec<u8>> {
+        fixed_base_mul(input, Self::SCALAR_LEN, Self::g2_generator_table())
+    }
+
+    fn msm_g2(input: &[u8]) -> Result<Vec<u8>> {
+        msm(input, Self::G2_LEN, Self::SCALAR_LEN, Self::read_g2)
+    }
 
-        // Get pairs
-        let mut pairings = Vec::with_capacity(4);
-        for i in 0..input.len() / g1_g2_len {
-            let g1 = <<Self as PairingEngine>::G1Affine as FromBytes>::read(
-                &input[i * g1_g2_len..i * g1_g2_len + g1_len],
-            )?;
-            let g2 = <<Self as PairingEngine>::G2Affine as FromBytes>::read(
-                &input[i * g1_g2_len + g1_len..(i + 1) * g1_g2_len],
-            )?;
+    /// Sum of any number of G2 points, input: `G2*`, the sum of none is zero
+    fn sum_g2(input: &[u8]) -> Result<Vec<u8>> {
+        sum(input, Self::G2_LEN, Self::read_g2)
+    }
+
+    fn pairings(input: &[u8]) -> Result<bool> {
+        pairings::<Self>(input, true)
+    }
+
+    /// Pairings without validating the points
+    fn pairings_trusted(input: &[u8]) -> Result<bool> {
+        pairings::<Self>(input, false)
+    }
+
+    /// Hash to G2 with the RFC 9380 suite of this curve
+    ///
+    /// input: SCALE `Vec<u8>` domain separation tag, then the message
+    fn hash_to_g2(input: &[u8]) -> Result<Vec<u8>> {
+        Err(Error::UnsupportedOperation)
+    }
+
+    /// Pairing of one pair, input: `G1 | G2`
+    fn gt_pairing(input: &[u8]) -> Result<Vec<u8>> {
+        Error::check_length(Self::G1_LEN + Self::G2_LEN, input.len())?;
+        let g1 = Self::read_g1(&input[..Self::G1_LEN])?;
+        let g2 = Self::read_g2(&input[Self::G1_LEN..])?;
+
+        let mut output = Vec::new();
+        <Self as PairingEngine>::pairing(g1, g2).write(&mut output)?;
+        Ok(output)
+    }
+
+    /// Target group mul, input: `GT | GT`
+    fn gt_mul(input: &[u8]) -> Result<Vec<u8>> {
+        Error::check_length(Self::GT_LEN * 2, input.len())?;
+        let a = Self::read_gt(&input[..Self::GT_LEN])?;
+        let b = Self::read_gt(&input[Self::GT_LEN..])?;
+
+        let mut output = Vec::new();
+        (a * b).write(&mut output)?;
+        Ok(output)
+    }
+
+    /// Target group exponentiation, input: `GT | Fr`
+    fn gt_pow(input: &[u8]) -> Result<Vec<u8>> {
+        Error::check_length(Self::GT_LEN + Self::SCALAR_LEN, input.len())?;
+        let gt = Self::read_gt(&input[..Self::GT_LEN])?;
+        let scalar = read_scalar::<<Self as PairingEngine>::Fr>(&input[Self::GT_LEN..])?;
+
+        let mut output = Vec::new();
+        gt.pow(scalar.into_repr()).write(&mut output)?;
+        Ok(output)
+    }
+
+    /// Target group equality, input: `GT | GT`, the elements are only checked
+    /// to be canonical
+    fn gt_eq(input: &[u8]) -> Result<bool> {
+        Error::check_length(Self::GT_LEN * 2, input.len())?;
+        let a = <<Self as PairingEngine>::Fqk as FromBytes>::read(&input[..Self::GT_LEN])?;
+        let b = <<Self as PairingEngine>::Fqk as FromBytes>::read(&input[Self::GT_LEN..])?;
+        Ok(a == b)
+    }
+
+    fn pairings_compressed(input: &[u8]) -> Result<bool> {
+        let g1_len = compressed_len::<<Self as PairingEngine>::G1Affine>();
+        let g1_g2_len = g1_len + compressed_len::<<Self as PairingEngine>::G2Affine>();
+        Error::check_length_multiple(g1_g2_len, input.len())?;
+
+        let mut pairings = Vec::with_capacity(input.len() / g1_g2_len);
+        for pair in input.chunks(g1_g2_len) {
+            let g1 = Self::read_g1_compressed(&pair[0..g1_len])?;
+            let g2 = Self::read_g2_compressed(&pair[g1_len..])?;
 
             pairings.push((g1.into(), g2.into()))
         }
 
-        // Check if pairing
         Ok(<Self as PairingEngine>::product_of_pairings(&pairings)
             == <Self as PairingEngine>::Fqk::one())
     }
 }
+
+/// Check the pairings of `(G1, G2)*` equals one
+fn pairings<C: PairingOperations>(input: &[u8], validate: bool) -> Result<bool> {
+    // g1 infinity is bool, so + 1 byte.
+    let g1_len = C::G1_LEN;
+    // ditto, g1 g2 + 2.
+    let g1_g2_len = C::G1_LEN + C::G2_LEN;
+    Error::check_length_multiple(g1_g2_len, input.len())?;
+
+    // Get pairs
+    let mut pairings = Vec::with_capacity(4);
+    for i in 0..input.len() / g1_g2_len {
+        let g1 = C::read_g1_unchecked(&input[i * g1_g2_len..i * g1_g2_len + g1_len])?;
+        let g2 = C::read_g2_unchecked(&input[i * g1_g2_len + g1_len..(i + 1) * g1_g2_len])?;
+        if validate {
+            C::validate_g1(&g1)?;
+            C::validate_g2(&g2)?;
+        }
+
+        pairings.push((g1.into(), g2.into()))
+    }
+
+    // Check if pairing
+    Ok(<C as PairingEngine>::product_of_pairings(&pairings) == <C as PairingEngine>::Fqk::one())
+}
+
+/// Hash the message of `input` to a point with `hash(msg, dst)`
+///
+/// input: SCALE `Vec<u8>` domain separation tag, then the message
+pub fn hash_to_curve<G: ToBytes>(input: &[u8], hash: fn(&[u8], &[u8]) -> G) -> Result<Vec<u8>> {
+    let mut msg = input;
+    let dst = <Vec<u8> as Decode>::decode(&mut msg)?;
+
+    let mut output = Vec::new();
+    hash(msg, &dst).write(&mut output)?;
+    Ok(output)
+}
+
+/// Binary operation `f` of two scalars, input: `Fr | Fr`
+fn fr_binary<F: PrimeField>(input: &[u8], scalar_len: usize, f: fn(F, F) -> F) -> Result<Vec<u8>> {
+    Error::check_length(scalar_len * 2, input.len())?;
+    let a = read_scalar::<F>(&input[..scalar_len])?;
+    let b = read_scalar::<F>(&input[scalar_len..])?;
+
+    let mut output = Vec::new();
+    f(a, b).write(&mut output)?;
+    Ok(output)
+}
+
+/// Points of `CanonicalSerialize` compressed bytes, the x-coordinate with the
+/// flags, decompressed without the subgroup check of `deserialize`
+///
+/// The `deserialize_unchecked` of arkworks reads uncompressed points, this is
+/// the unchecked read of compressed ones, the caller validates the points.
+pub trait Decompress: Sized {
+    fn decompress(input: &mut &[u8]) -> Result<Self>;
+}
+
+impl<P: SWModelParameters> Decompress for GroupAffine<P> {
+    fn decompress(input: &mut &[u8]) -> Result<Self> {
+        let (x, flags): (P::BaseField, SWFlags) =
+            CanonicalDeserializeWithFlags::deserialize_with_flags(&mut *input)
+                .map_err(|e| Error::from_invalid_data(e, Error::FieldElementOutOfRange))?;
+        if flags.is_infinity() {
+            return Ok(Self::zero());
+        }
+        Self::get_point_from_x(x, flags.is_positive().unwrap_or_default())
+            .ok_or(Error::PointNotOnCurve)
+    }
+}
+
+impl<P: TEModelParameters> Decompress for EdwardsAffine<P> {
+    fn decompress(input: &mut &[u8]) -> Result<Self> {
+        let (x, flags): (P::BaseField, EdwardsFlags) =
+            CanonicalDeserializeWithFlags::deserialize_with_flags(&mut *input)
+                .map_err(|e| Error::from_invalid_data(e, Error::FieldElementOutOfRange))?;
+        if x.is_zero() {
+            return Ok(Self::zero());
+        }
+        Self::get_point_from_x(x, flags.is_positive()).ok_or(Error::PointNotOnCurve)
+    }
+}
+
+/// Points of arkworks `ToBytes` bytes, read without the validation of the
+/// curve
+///
+/// Short Weierstrass points end with an infinity flag byte, which `FromBytes`
+/// rejects with the same error as the field elements out of range, it is
+/// checked here instead.
+pub trait ReadPoint: Sized {
+    fn read_point(bytes: &[u8]) -> Result<Self>;
+}
+
+impl<P: SWModelParameters> ReadPoint for GroupAffine<P> {
+    fn read_point(mut bytes: &[u8]) -> Result<Self> {
+        let x = P::BaseField::read(&mut bytes)?;
+        let y = P::BaseField::read(&mut bytes)?;
+        let infinity = match u8::read(&mut bytes)? {
+            0 => false,
+            1 => true,
+            _ => return Err(Error::InvalidPointEncoding),
+        };
+        Ok(Self::new(x, y, infinity))
+    }
+}
+
+impl<P: TEModelParameters> ReadPoint for EdwardsAffine<P> {
+    fn read_point(bytes: &[u8]) -> Result<Self> {
+        Ok(<Self as FromBytes>::read(bytes)?)
+    }
+}
+
+/// Compressed bytes length of points, x-coordinate with flags
+pub fn compressed_len<G: AffineCurve>() -> usize {
+    G::zero().serialized_size()
+}
+
+/// `CanonicalSerialize` uncompressed bytes length of points
+pub fn uncompressed_len<G: AffineCurve>() -> usize {
+    G::zero().uncompressed_size()
+}
+
+/// Read little-endian scalar, fails if it is not less than the subgroup order
+pub fn read_scalar<F: PrimeField>(bytes: &[u8]) -> Result<F> {
+    F::from_repr(<F::BigInt as FromBytes>::read(bytes)?).ok_or(Error::ScalarOutOfRange)
+}
+
+/// Scalar mul of the base of `table`, input: `Fr`
+fn fixed_base_mul<G: AffineCurve>(
+    input: &[u8],
+    scalar_len: usize,
+    table: &FixedBaseTable<G>,
+) -> Result<Vec<u8>> {
+    Error::check_length(scalar_len, input.len())?;
+    let scalar = read_scalar::<G::ScalarField>(input)?;
+
+    let mut output = Vec::new();
+    table.mul(&scalar).into_affine().write(&mut output)?;
+    Ok(output)
+}
+
+/// Multi-scalar multiplication with Pippenger's algorithm
+///
+/// input: `Compact<u32>` count of pairs, then (point, scalar) pairs
+fn msm<G: AffineCurve>(
+    input: &[u8],
+    point_len: usize,
+    scalar_len: usize,
+    read: fn(&[u8]) -> Result<G>,
+) -> Result<Vec<u8>> {
+    let mut input = input;
+    let count = <Compact<u32> as Decode>::decode(&mut input)?.0 as usize;
+    let pair_len = point_len + scalar_len;
+    Error::check_length(count.saturating_mul(pair_len), input.len())?;
+
+    let mut bases = Vec::with_capacity(count);
+    let mut scalars = Vec::with_capacity(count);
+    for pair in input.chunks(pair_len) {
+        bases.push(read(&pair[0..point_len])?);
+        scalars.push(read_scalar::<G::ScalarField>(&pair[point_len..])?.into_repr());
+    }
+
+    let mut output = Vec::new();
+    VariableBaseMSM::multi_scalar_mul(&bases, &scalars)
+        .into_affine()
+        .write(&mut output)?;
+    Ok(output)
+}
+
+/// Sum of the points of `input`, accumulated in projective coordinates and
+/// converted to affine once
+fn sum<G: AffineCurve>(
+    input: &[u8],
+    point_len: usize,
+    read: fn(&[u8]) -> Result<G>,
+) -> Result<Vec<u8>> {
+    Error::check_length_multiple(point_len, input.len())?;
+
+    let mut acc = G::Projective::zero();
+    for point in input.chunks(point_len) {
+        acc.add_assign_mixed(&read(point)?);
+    }
+
+    let mut output = Vec::new();
+    acc.into_affine().write(&mut output)?;
+    Ok(output)
+}
+
+/// Scalar mul in constant time for secret scalars, `point` must be in the
+/// prime order subgroup
+///
+/// A Montgomery ladder over `k + n` or `k + 2n`, whichever has the bit
+/// `MODULUS_BITS` set, so every scalar runs the same `MODULUS_BITS` steps of
+/// one add and one double, and the points are swapped by `swap`, the
+/// [`ConditionalSwap`] of the points, instead of branches.
+///
+/// The point formulas branch on the identity, which the ladder passes when the
+/// bits read so far are `n` or `n - 1`, that is for the scalars `0`, `1`, `-1`
+/// and `-2`, and for every scalar if `point` is the identity. These take
+/// another path, a uniform nonzero secret key is one of them with negligible
+/// probability. For the other scalars neither the group operations nor the
+/// memory they access depend on the scalar. The field arithmetic of arkworks
+/// underneath, including the inversion converting the result to affine, is
+/// not audited for constant time, check it with the timing harness of the
+/// tests crate.
+pub fn mul_secret<G: AffineCurve>(
+    point: &G,
+    scalar: &G::ScalarField,
+    swap: fn(&mut G::Projective, &mut G::Projective, bool),
+) -> G::Projective {
+    let bits = <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
+    let modulus = <G::ScalarField as PrimeField>::Params::MODULUS;
+
+    // k + n, and k + 2n if the top bit of k + n is not set, which is in the
+    // carry if the modulus fills the limbs
+    let mut k = scalar.into_repr();
+    let carry = k.add_nocarry(&modulus);
+    let mut k2 = k;
+    k2.add_nocarry(&modulus);
+    let top = if bits == k.as_ref().len() * 64 {
+        carry
+    } else {
+        k.get_bit(bits)
+    };
+    let mask = 0u64.wrapping_sub(!top as u64);
+    for (a, b) in k.as_mut().iter_mut().zip(k2.as_ref()) {
+        *a ^= mask & (*a ^ *b);
+    }
+
+    // the top bit is set, start from P and 2P
+    let mut r0 = point.into_projective();
+    let mut r1 = r0.double();
+    for i in (0..bits).rev() {
+        let bit = k.get_bit(i);
+        swap(&mut r0, &mut r1, bit);
+        r1 += &r0;
+        r0.double_in_place();
+        swap(&mut r0, &mut r1, bit);
+    }
+    r0
+}
+
+/// Swap of `a` and `b` if `choice`, by masking the limbs of their
+/// coordinates instead of branching
+pub trait ConditionalSwap {
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool);
+}
+
+/// Swap the limbs of the prime fields
+macro_rules! conditional_swap_fp {
+    ($($fp:ident),*) => {
+        $(
+            impl<P> ConditionalSwap for $fp<P> {
+                fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool) {
+                    let mask = 0u64.wrapping_sub(choice as u64);
+                    for (x, y) in a.0.as_mut().iter_mut().zip(b.0.as_mut().iter_mut()) {
+                        let t = mask & (*x ^ *y);
+                        *x ^= t;
+                        *y ^= t;
+                    }
+                }
+            }
+        )*
+    };
+}
+
+conditional_swap_fp!(Fp256, Fp320, Fp384, Fp768, Fp832);
+
+impl<P: QuadExtParameters> ConditionalSwap for QuadExtField<P>
+where
+    P::BaseField: ConditionalSwap,
+{
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool) {
+        ConditionalSwap::conditional_swap(&mut a.c0, &mut b.c0, choice);
+        ConditionalSwap::conditional_swap(&mut a.c1, &mut b.c1, choice);
+    }
+}
+
+impl<P: CubicExtParameters> ConditionalSwap for CubicExtField<P>
+where
+    P::BaseField: ConditionalSwap,
+{
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool) {
+        ConditionalSwap::conditional_swap(&mut a.c0, &mut b.c0, choice);
+        ConditionalSwap::conditional_swap(&mut a.c1, &mut b.c1, choice);
+        ConditionalSwap::conditional_swap(&mut a.c2, &mut b.c2, choice);
+    }
+}
+
+impl<P: SWModelParameters> ConditionalSwap for GroupProjective<P>
+where
+    P::BaseField: ConditionalSwap,
+{
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool) {
+        ConditionalSwap::conditional_swap(&mut a.x, &mut b.x, choice);
+        ConditionalSwap::conditional_swap(&mut a.y, &mut b.y, choice);
+        ConditionalSwap::conditional_swap(&mut a.z, &mut b.z, choice);
+    }
+}
+
+impl<P: TEModelParameters> ConditionalSwap for EdwardsProjective<P>
+where
+    P::BaseField: ConditionalSwap,
+{
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool) {
+        ConditionalSwap::conditional_swap(&mut a.x, &mut b.x, choice);
+        ConditionalSwap::conditional_swap(&mut a.y, &mut b.y, choice);
+        ConditionalSwap::conditional_swap(&mut a.t, &mut b.t, choice);
+        ConditionalSwap::conditional_swap(&mut a.z, &mut b.z, choice);
+    }
+}
+
+/// Check the element of `Fqk` is in the target group, the subgroup of order
+/// `r`
+pub fn validate_gt<C: PairingEngine>(gt: &C::Fqk) -> Result<()> {
+    if !gt.pow(<C::Fr as PrimeField>::Params::MODULUS).is_one() {
+        return Err(Error::NotInTargetGroup);
+    }
+    Ok(())
+}
+
+/// Check the short Weierstrass point is on curve and in the prime order
+/// subgroup
+pub fn validate<P: SWModelParameters>(point: &GroupAffine<P>) -> Result<()> {
+    if !point.is_on_curve() {
+        return Err(Error::PointNotOnCurve);
+    }
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Error::PointNotInSubgroup);
+    }
+    Ok(())
+}
+
+/// Check the twisted Edwards point is on curve and in the prime order
+/// subgroup
+pub fn validate_te<P: TEModelParameters>(point: &EdwardsAffine<P>) -> Result<()> {
+    if !point.is_on_curve() {
+        return Err(Error::PointNotOnCurve);
+    }
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Error::PointNotInSubgroup);
+    }
+    Ok(())
+}
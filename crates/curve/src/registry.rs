@@ -0,0 +1,622 @@
+//! Curve registry
+//!
+//! Function ids are composed as `Operation + CURVE_ID`, the low byte's high
+//! nibble and bits 20 to 23 select the curve and the rest selects the
+//! operation. The low nibble holds the point operations, the groups of
+//! operations added later count from bit 8, `0x100` for the scalar field,
+//! `0x200` for the target group, `0x300` for the point sums and `0x400` for
+//! the fixed-base muls. Bits 16 and 17 select the serialization, see
+//! [`crate::serialization`], and the top byte selects the ABI version, see
+//! [`crate::abi`].
+//!
+//! The first 16 curves take `0x00` to `0xf0`, the next ones count on in bits
+//! 20 to 23, `0x00100000` to `0x001000f0` and so on, so the ids of the
+//! curves before stay the same. CP6-782 had `CURVE_ID` `0x4` before the
+//! registry, it is [`CP6_782_LEGACY_ID`], an alias accepted where curve ids
+//! are taken but not in function ids, where `0x4` is an operation.
+
+use ark_std::{convert::TryFrom, vec::Vec};
+
+use parity_scale_codec::{Decode, Encode};
+
+use crate::{
+    abi::{self, CurveAbi, Version},
+    curve,
+    error::{Error, Result},
+    evm, groth16,
+    ops::{GroupOperations, PairingOperations},
+    serialization::Serialization,
+};
+
+/// Mask of the curve id in function ids
+pub const CURVE_ID_MASK: u32 = 0x00f000f0;
+
+/// `CURVE_ID` of CP6-782 before the registry, an alias of
+/// [`CurveId::CP6_782`]
+pub const CP6_782_LEGACY_ID: u32 = 0x4;
+
+/// Curve operations, the discriminants are the legacy function ids
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Operation {
+    Add = 0x01000000,
+    Mul = 0x01000001,
+    Pairing = 0x01000002,
+    G2Add = 0x01000003,
+    G2Mul = 0x01000004,
+    MsmG1 = 0x01000005,
+    MsmG2 = 0x01000006,
+    AddCompressed = 0x01000007,
+    MulCompressed = 0x01000008,
+    PairingCompressed = 0x01000009,
+    MsmG1Trusted = 0x0100000a,
+    PairingTrusted = 0x0100000b,
+    HashToG1 = 0x0100000c,
+    HashToG2 = 0x0100000d,
+    FrAdd = 0x01000100,
+    FrSub = 0x01000101,
+    FrMul = 0x01000102,
+    FrInverse = 0x01000103,
+    FrPow = 0x01000104,
+    FrBatchInverse = 0x01000105,
+    GtPairing = 0x01000200,
+    GtMul = 0x01000201,
+    GtPow = 0x01000202,
+    GtEq = 0x01000203,
+    SumG1 = 0x01000300,
+    SumG2 = 0x01000301,
+    MulGenerator = 0x01000400,
+    G2MulGenerator = 0x01000401,
+}
+
+impl Operation {
+    /// All operations
+    pub const ALL: &'static [Operation] = &[
+        Operation::Add,
+        Operation::Mul,
+        Operation::Pairing,
+        Operation::G2Add,
+        Operation::G2Mul,
+        Operation::MsmG1,
+        Operation::MsmG2,
+        Operation::AddCompressed,
+        Operation::MulCompressed,
+        Operation::PairingCompressed,
+        Operation::MsmG1Trusted,
+        Operation::PairingTrusted,
+        Operation::HashToG1,
+        Operation::HashToG2,
+        Operation::FrAdd,
+        Operation::FrSub,
+        Operation::FrMul,
+        Operation::FrInverse,
+        Operation::FrPow,
+        Operation::FrBatchInverse,
+        Operation::GtPairing,
+        Operation::GtMul,
+        Operation::GtPow,
+        Operation::GtEq,
+        Operation::SumG1,
+        Operation::SumG2,
+        Operation::MulGenerator,
+        Operation::G2MulGenerator,
+    ];
+
+    /// Legacy function id of this operation on curve `curve_id`
+    pub const fn func_id(self, curve_id: u32) -> u32 {
+        self as u32 + curve_id
+    }
+
+    /// Whether this operation is one of [`GroupOperations`]
+    pub fn is_group_operation(self) -> bool {
+        matches!(
+            self,
+            Operation::Add
+                | Operation::Mul
+                | Operation::MsmG1
+                | Operation::AddCompressed
+                | Operation::MulCompressed
+                | Operation::MsmG1Trusted
+                | Operation::HashToG1
+                | Operation::FrAdd
+                | Operation::FrSub
+                | Operation::FrMul
+                | Operation::FrInverse
+                | Operation::FrPow
+                | Operation::FrBatchInverse
+                | Operation::SumG1
+                | Operation::MulGenerator
+        )
+    }
+
+    /// Whether group `C` supports this operation
+    pub fn supported_by_group<C: GroupOperations>(self) -> bool {
+        match self {
+            Operation::HashToG1 => C::HASH_TO_G1.is_some(),
+            op => op.is_group_operation(),
+        }
+    }
+
+    /// Whether curve `C` supports this operation
+    pub fn supported_by<C: PairingOperations>(self) -> bool {
+        match self {
+            Operation::HashToG1 => C::HASH_TO_G1.is_some(),
+            Operation::HashToG2 => C::HASH_TO_G2.is_some(),
+            _ => true,
+        }
+    }
+
+    /// Run this operation on group `C`, the group operations don't depend on
+    /// the ABI version
+    pub fn call_group<C: GroupOperations>(self, input: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            Operation::Add => C::add(input),
+            Operation::Mul => C::mul(input),
+            Operation::MsmG1 => C::msm_g1(input),
+            Operation::AddCompressed => C::add_compressed(input),
+            Operation::MulCompressed => C::mul_compressed(input),
+            Operation::MsmG1Trusted => C::msm_g1_trusted(input),
+            Operation::HashToG1 => C::hash_to_g1(input),
+            Operation::FrAdd => C::fr_add(input),
+            Operation::FrSub => C::fr_sub(input),
+            Operation::FrMul => C::fr_mul(input),
+            Operation::FrInverse => C::fr_inverse(input),
+            Operation::FrPow => C::fr_pow(input),
+            Operation::FrBatchInverse => C::fr_batch_inverse(input),
+            Operation::SumG1 => C::sum_g1(input),
+            Operation::MulGenerator => C::mul_generator(input),
+            _ => Err(Error::UnsupportedOperation),
+        }
+    }
+
+    /// Run this operation on curve `C` with the legacy ABI
+    pub fn call<C: PairingOperations>(self, input: &[u8]) -> Result<Vec<u8>> {
+        self.call_with::<C>(Version::Legacy, input)
+    }
+
+    /// Run this operation on curve `C` with the ABI of `version`
+    pub fn call_with<C: PairingOperations>(
+        self,
+        version: Version,
+        input: &[u8],
+    ) -> Result<Vec<u8>> {
+        let b2b = |b: bool| version.encode_bool(b);
+        Ok(match self {
+            Operation::Pairing => C::pairings(input).map(b2b)?,
+            Operation::G2Add => C::g2_add(input)?,
+            Operation::G2Mul => C::g2_mul(input)?,
+            Operation::MsmG2 => C::msm_g2(input)?,
+            Operation::PairingCompressed => C::pairings_compressed(input).map(b2b)?,
+            Operation::PairingTrusted => C::pairings_trusted(input).map(b2b)?,
+            Operation::HashToG2 => C::hash_to_g2(input)?,
+            Operation::GtPairing => C::gt_pairing(input)?,
+            Operation::GtMul => C::gt_mul(input)?,
+            Operation::GtPow => C::gt_pow(input)?,
+            Operation::GtEq => C::gt_eq(input)?.encode(),
+            Operation::SumG2 => C::sum_g2(input)?,
+            Operation::G2MulGenerator => C::g2_mul_generator(input)?,
+            op => op.call_group::<C>(input)?,
+        })
+    }
+}
+
+impl TryFrom<u32> for Operation {
+    type Error = Error;
+
+    fn try_from(op: u32) -> Result<Self> {
+        Operation::ALL
+            .iter()
+            .find(|o| **o as u32 == op)
+            .copied()
+            .ok_or(Error::InvalidFunctionId)
+    }
+}
+
+/// Declare the curves implemented `PairingOperations` and the groups
+/// implemented `GroupOperations` only
+macro_rules! curves {
+    (pairing: $($curve:ident),* ; group: $($group:ident),* $(,)?) => {
+        /// Curve ids, equal to `GroupOperations::CURVE_ID`
+        #[allow(non_camel_case_types)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #[repr(u32)]
+        pub enum CurveId {
+            $($curve = <curve::$curve as GroupOperations>::CURVE_ID,)*
+            $($group = <curve::$group as GroupOperations>::CURVE_ID,)*
+        }
+
+        impl CurveId {
+            /// All supported curves
+            pub const ALL: &'static [CurveId] = &[$(CurveId::$curve,)* $(CurveId::$group,)*];
+
+            /// Name of this curve
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(CurveId::$curve => stringify!($curve),)*
+                    $(CurveId::$group => stringify!($group),)*
+                }
+            }
+
+            /// Whether this curve has a pairing
+            pub const fn has_pairing(self) -> bool {
+                match self {
+                    $(CurveId::$curve => true,)*
+                    $(CurveId::$group => false,)*
+                }
+            }
+
+            /// Byte lengths of this curve
+            pub fn describe(self) -> CurveAbi {
+                match self {
+                    $(CurveId::$curve => CurveAbi::of::<curve::$curve>(self.name()),)*
+                    $(CurveId::$group => CurveAbi::of_group::<curve::$group>(self.name()),)*
+                }
+            }
+
+            /// Whether this curve supports `op`
+            pub fn supports(self, op: Operation) -> bool {
+                match self {
+                    $(CurveId::$curve => op.supported_by::<curve::$curve>(),)*
+                    $(CurveId::$group => op.supported_by_group::<curve::$group>(),)*
+                }
+            }
+
+            /// Run operation on this curve with the legacy ABI
+            pub fn call(self, op: Operation, input: &[u8]) -> Result<Vec<u8>> {
+                self.call_with(Version::Legacy, op, input)
+            }
+
+            /// Run operation on this curve with the ABI of `version`
+            pub fn call_with(self, version: Version, op: Operation, input: &[u8]) -> Result<Vec<u8>> {
+                match self {
+                    $(CurveId::$curve => op.call_with::<curve::$curve>(version, input),)*
+                    $(CurveId::$group => op.call_group::<curve::$group>(input),)*
+                }
+            }
+
+            /// Run operation on this curve with the ABI of `version` in
+            /// `serialization`
+            pub fn call_serialized(
+                self,
+                version: Version,
+                serialization: Serialization,
+                op: Operation,
+                input: &[u8],
+            ) -> Result<Vec<u8>> {
+                match self {
+                    $(CurveId::$curve => serialization.call::<curve::$curve>(version, op, input),)*
+                    $(CurveId::$group => serialization.call_group::<curve::$group>(op, input),)*
+                }
+            }
+
+            /// Scalar mul in constant time for secret scalars on this curve,
+            /// computed locally, see [`GroupOperations::mul_secret`]
+            pub fn mul_secret(self, input: &[u8]) -> Result<Vec<u8>> {
+                match self {
+                    $(CurveId::$curve => <curve::$curve as GroupOperations>::mul_secret(input),)*
+                    $(CurveId::$group => <curve::$group as GroupOperations>::mul_secret(input),)*
+                }
+            }
+
+            /// G2 scalar mul in constant time for secret scalars on this
+            /// curve, computed locally, see
+            /// [`PairingOperations::g2_mul_secret`]
+            pub fn g2_mul_secret(self, input: &[u8]) -> Result<Vec<u8>> {
+                match self {
+                    $(CurveId::$curve => <curve::$curve as PairingOperations>::g2_mul_secret(input),)*
+                    _ => Err(Error::UnsupportedOperation),
+                }
+            }
+
+            /// Groth16 verify on this curve
+            pub fn verify(
+                self,
+                vk_gamma_abc: Vec<Vec<u8>>,
+                vk: Vec<u8>,
+                proof: Vec<u8>,
+                public_inputs: Vec<Vec<u8>>,
+            ) -> Result<bool> {
+                match self {
+                    $(CurveId::$curve => groth16::verify_proof::<curve::$curve>(
+                        vk_gamma_abc,
+                        vk,
+                        proof,
+                        public_inputs,
+                    ),)*
+                    _ => Err(Error::UnsupportedOperation),
+                }
+            }
+
+            /// Groth16 verify with compressed points on this curve
+            pub fn verify_compressed(
+                self,
+                vk_gamma_abc: Vec<Vec<u8>>,
+                vk: Vec<u8>,
+                proof: Vec<u8>,
+                public_inputs: Vec<Vec<u8>>,
+            ) -> Result<bool> {
+                match self {
+                    $(CurveId::$curve => groth16::verify_proof_compressed::<curve::$curve>(
+                        vk_gamma_abc,
+                        vk,
+                        proof,
+                        public_inputs,
+                    ),)*
+                    _ => Err(Error::UnsupportedOperation),
+                }
+            }
+
+            /// Groth16 verify of `CanonicalSerialize` key, proof and public
+            /// inputs on this curve
+            pub fn verify_canonical(
+                self,
+                serialization: Serialization,
+                vk: Vec<u8>,
+                proof: Vec<u8>,
+                public_inputs: Vec<u8>,
+            ) -> Result<bool> {
+                match self {
+                    $(CurveId::$curve => groth16::verify_proof_canonical::<curve::$curve>(
+                        serialization,
+                        vk,
+                        proof,
+                        public_inputs,
+                    ),)*
+                    _ => Err(Error::UnsupportedOperation),
+                }
+            }
+
+            /// Groth16 verify parcel on this curve
+            pub fn verify_parcel(self, parcel: Vec<u8>) -> Result<bool> {
+                match self {
+                    $(CurveId::$curve => groth16::verify::<curve::$curve>(parcel),)*
+                    _ => Err(Error::UnsupportedOperation),
+                }
+            }
+
+            /// Groth16 verify parcel with compressed points on this curve
+            pub fn verify_parcel_compressed(self, parcel: Vec<u8>) -> Result<bool> {
+                match self {
+                    $(CurveId::$curve => groth16::verify_compressed::<curve::$curve>(parcel),)*
+                    _ => Err(Error::UnsupportedOperation),
+                }
+            }
+
+            /// Groth16 verify `CanonicalSerialize` parcel on this curve
+            pub fn verify_parcel_canonical(
+                self,
+                serialization: Serialization,
+                parcel: Vec<u8>,
+            ) -> Result<bool> {
+                match self {
+                    $(CurveId::$curve => {
+                        groth16::verify_canonical::<curve::$curve>(serialization, parcel)
+                    })*
+                    _ => Err(Error::UnsupportedOperation),
+                }
+            }
+        }
+    };
+}
+
+curves!(
+    pairing: Bls12_377, Bls12_381, Bn254, BW6_761, CP6_782, MNT4_298, MNT6_298, MNT4_753, MNT6_753;
+    group: Pallas, Vesta, Secp256k1, EdOnBn254, EdOnBls12_381,
+);
+
+impl CurveId {
+    /// Legacy function id of `op` on this curve
+    pub const fn func_id(self, op: Operation) -> u32 {
+        op.func_id(self as u32)
+    }
+}
+
+impl TryFrom<u32> for CurveId {
+    type Error = Error;
+
+    fn try_from(id: u32) -> Result<Self> {
+        if id == CP6_782_LEGACY_ID {
+            return Ok(CurveId::CP6_782);
+        }
+        CurveId::ALL
+            .iter()
+            .find(|c| **c as u32 == id)
+            .copied()
+            .ok_or(Error::UnsupportedCurve(id))
+    }
+}
+
+/// Split legacy function id into curve and operation
+pub fn decode(func_id: u32) -> Result<(CurveId, Operation)> {
+    Ok((
+        CurveId::try_from(func_id & CURVE_ID_MASK)?,
+        Operation::try_from(func_id & !CURVE_ID_MASK)?,
+    ))
+}
+
+/// Run the operation of `func_id`
+pub fn dispatch(func_id: u32, input: &[u8]) -> Result<Vec<u8>> {
+    if let Ok(precompile) = evm::Precompile::try_from(func_id) {
+        return precompile.call(input);
+    }
+    if func_id == abi::DESCRIBE {
+        return Ok(abi::describe().encode());
+    }
+
+    let (serialization, func_id) = Serialization::split(func_id)?;
+    let (version, curve, op) = abi::decode(func_id)?;
+    curve.call_serialized(version, serialization, op, input)
+}
+
+/// Run the operation of `func_id` on the SCALE encoded `Vec<u8>` input of the
+/// chain extension, the output is the SCALE encoded `Result<Vec<u8>, Error>`
+/// which `call` decodes
+pub fn dispatch_encoded(func_id: u32, mut input: &[u8]) -> Vec<u8> {
+    <Vec<u8> as Decode>::decode(&mut input)
+        .map_err(Error::from)
+        .and_then(|input| dispatch(func_id, &input))
+        .encode()
+}
+
+/// All function ids, the operations of every version, serialization and
+/// curve, the EVM precompiles and the ABI description
+///
+/// The operations are listed on every curve, the ones a curve doesn't
+/// support fail with [`Error::UnsupportedOperation`], see [`supported`].
+pub fn func_ids() -> Vec<u32> {
+    let mut ids = Vec::new();
+    for version in Version::ALL {
+        for serialization in Serialization::ALL {
+            for curve in CurveId::ALL {
+                for op in Operation::ALL
+                    .iter()
+                    .filter(|o| serialization.supports(**o))
+                {
+                    ids.push(serialization.flag() | version.func_id(*op, *curve as u32));
+                }
+            }
+        }
+    }
+    ids.extend(evm::Precompile::ALL.iter().map(|p| p.func_id()));
+    ids.push(abi::DESCRIBE);
+    ids
+}
+
+/// All supported (curve, operation) pairs
+pub fn supported() -> Vec<(CurveId, Operation)> {
+    CurveId::ALL
+        .iter()
+        .flat_map(|c| Operation::ALL.iter().map(move |o| (*c, *o)))
+        .filter(|(c, o)| c.supports(*o))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_ids() {
+        assert_eq!(
+            decode(0x01000000).unwrap(),
+            (CurveId::Bls12_377, Operation::Add)
+        );
+        assert_eq!(
+            decode(0x01000012).unwrap(),
+            (CurveId::Bls12_381, Operation::Pairing)
+        );
+        assert_eq!(
+            decode(0x01000021).unwrap(),
+            (CurveId::Bn254, Operation::Mul)
+        );
+        assert_eq!(
+            decode(0x01000030).unwrap(),
+            (CurveId::BW6_761, Operation::Add)
+        );
+        assert_eq!(
+            decode(0x01000051).unwrap(),
+            (CurveId::MNT4_298, Operation::Mul)
+        );
+        assert!(decode(0x0100000f).is_err());
+        assert!(decode(0x010000f0).is_err());
+        assert_eq!(decode(0x01100000), Err(Error::UnsupportedCurve(0x00100000)));
+        for curve in CurveId::ALL {
+            assert_eq!(*curve as u32 & !CURVE_ID_MASK, 0);
+        }
+    }
+
+    #[test]
+    fn test_cp6_legacy_id() {
+        // the old CP6-782 id is a curve id, but an operation in function ids
+        assert_eq!(
+            CurveId::try_from(CP6_782_LEGACY_ID).unwrap(),
+            CurveId::CP6_782
+        );
+        assert_eq!(
+            decode(0x01000004).unwrap(),
+            (CurveId::Bls12_377, Operation::G2Mul)
+        );
+    }
+
+    #[test]
+    fn test_func_id_roundtrip() {
+        for (curve, op) in supported() {
+            assert_eq!(decode(curve.func_id(op)).unwrap(), (curve, op));
+        }
+        let ops_per_curve: usize = Serialization::ALL
+            .iter()
+            .map(|s| Operation::ALL.iter().filter(|o| s.supports(**o)).count())
+            .sum();
+        assert_eq!(
+            func_ids().len(),
+            Version::ALL.len() * CurveId::ALL.len() * ops_per_curve
+                + evm::Precompile::ALL.len()
+                + 1
+        );
+    }
+
+    #[test]
+    fn test_supported_operations() {
+        // hash to curve on BLS12-377, BLS12-381 G1, G2 and BN254 G1, the groups
+        // without pairing support the group operations but hashing
+        let hashes = [
+            (CurveId::Bls12_377, Operation::HashToG1),
+            (CurveId::Bls12_377, Operation::HashToG2),
+            (CurveId::Bls12_381, Operation::HashToG1),
+            (CurveId::Bls12_381, Operation::HashToG2),
+            (CurveId::Bn254, Operation::HashToG1),
+        ];
+        for curve in CurveId::ALL {
+            for op in Operation::ALL {
+                let supported = match op {
+                    Operation::HashToG1 | Operation::HashToG2 => hashes.contains(&(*curve, *op)),
+                    op => curve.has_pairing() || op.is_group_operation(),
+                };
+                assert_eq!(curve.supports(*op), supported, "{:?} {:?}", curve, op);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unsupported_operations() {
+        assert_eq!(
+            CurveId::Bn254.call(Operation::HashToG2, &[0]),
+            Err(Error::UnsupportedOperation)
+        );
+        assert_eq!(
+            decode(0x01000092).unwrap(),
+            (CurveId::Pallas, Operation::Pairing)
+        );
+        assert_eq!(
+            CurveId::Pallas.call(Operation::Pairing, &[]),
+            Err(Error::UnsupportedOperation)
+        );
+        assert_eq!(
+            CurveId::Secp256k1.verify_parcel(vec![]),
+            Err(Error::UnsupportedOperation)
+        );
+    }
+
+    #[test]
+    fn test_evm_ids() {
+        assert_eq!(dispatch(0x02000020, &[]).unwrap(), vec![0; 64]);
+        assert!(decode(0x02000020).is_err());
+    }
+
+    #[test]
+    fn test_dispatch_encoded() {
+        for func_id in func_ids() {
+            assert_ne!(dispatch(func_id, &[]), Err(Error::InvalidFunctionId));
+        }
+        let output = dispatch_encoded(abi::DESCRIBE, &Vec::<u8>::new().encode());
+        assert_eq!(
+            Result::<Vec<u8>>::decode(&mut &*output).unwrap(),
+            Ok(abi::describe().encode())
+        );
+        let output = dispatch_encoded(abi::DESCRIBE, &[4]);
+        assert_eq!(
+            Result::<Vec<u8>>::decode(&mut &*output).unwrap(),
+            Err(Error::ScaleCodecError)
+        );
+    }
+}
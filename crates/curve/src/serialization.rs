@@ -0,0 +1,549 @@
+//! Serializations of the curve operations
+//!
+//! Every function id of the curve operations has two more ids which take and
+//! return the arkworks `CanonicalSerialize` encodings, as `ark-groth16` writes
+//! its proofs and verifying keys, instead of `ToBytes`. Bits 16 and 17 of the
+//! function id select the serialization:
+//!
+//! ```text
+//!  31     24 23     20 19 18 17     16 15      8 7      4 3      0
+//! | version | curve hi | 0     | serial.  | group   | curve  | op     |
+//! ```
+//!
+//! - `0b00` [`Serialization::Legacy`], `ToBytes` points with the trailing
+//!   infinity byte, field elements of whole `u64` limbs
+//! - `0b01` [`Serialization::Canonical`], `CanonicalSerialize` uncompressed
+//!   points with the flags in the spare bits of `y`, field elements of the
+//!   bytes of their modulus
+//! - `0b10` [`Serialization::CanonicalCompressed`], the same but points are
+//!   compressed to `x` with the flags
+//!
+//! So `0x01010020` is the legacy version of BN254 `add` on uncompressed
+//! canonical points and `0x03020022` the v1 pairing on compressed ones. The
+//! canonical ids rewrite their input to the legacy serialization, run the
+//! legacy operation and rewrite its output back, the validation of points and
+//! scalars is the operation's. Compressed points are decompressed without
+//! validation, an `x` of no point is [`Error::PointNotOnCurve`]. Counts `n`, exponents `e`, hashing inputs and the pairing
+//! results are the same in every serialization. Field elements not less than
+//! the modulus are [`Error::FieldElementOutOfRange`], truncated items
+//! [`Error::InputTooShort`]. The `*Compressed` operations have no
+//! canonical ids, they are canonical already.
+//!
+//! Groth16 keys, proofs and public inputs as `ark-groth16` writes them are
+//! verified by [`crate::groth16::verify_proof_canonical`].
+
+use ark_std::vec::Vec;
+use parity_scale_codec::{Compact, Decode, Encode};
+
+use crate::{
+    abi::Version,
+    error::{Error, Result},
+    ops::{
+        CanonicalDeserialize, CanonicalSerialize, FromBytes, GroupOperations, PairingEngine,
+        PairingOperations, PrimeField, Scalar, ToBytes,
+    },
+    registry::Operation,
+};
+
+/// Mask of the serialization in function ids
+pub const SERIALIZATION_MASK: u32 = 0x00030000;
+
+/// Serializations of the points and field elements
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum Serialization {
+    /// arkworks `ToBytes` and `FromBytes`
+    Legacy,
+    /// arkworks `CanonicalSerialize`, uncompressed
+    Canonical,
+    /// arkworks `CanonicalSerialize`, compressed
+    CanonicalCompressed,
+}
+
+impl Serialization {
+    /// All serializations
+    pub const ALL: &'static [Serialization] = &[
+        Serialization::Legacy,
+        Serialization::Canonical,
+        Serialization::CanonicalCompressed,
+    ];
+
+    /// Flag of this serialization in function ids
+    pub const fn flag(self) -> u32 {
+        match self {
+            Serialization::Legacy => 0,
+            Serialization::Canonical => 0x00010000,
+            Serialization::CanonicalCompressed => 0x00020000,
+        }
+    }
+
+    /// Name of this serialization
+    pub const fn name(self) -> &'static str {
+        match self {
+            Serialization::Legacy => "legacy",
+            Serialization::Canonical => "canonical",
+            Serialization::CanonicalCompressed => "canonical_compressed",
+        }
+    }
+
+    /// Split `func_id` into this serialization and the id of the legacy
+    /// serialization
+    pub fn split(func_id: u32) -> Result<(Serialization, u32)> {
+        let serialization = Serialization::ALL
+            .iter()
+            .find(|s| s.flag() == func_id & SERIALIZATION_MASK)
+            .copied()
+            .ok_or(Error::InvalidFunctionId)?;
+        Ok((serialization, func_id & !SERIALIZATION_MASK))
+    }
+
+    /// Whether `op` has function ids in this serialization
+    pub fn supports(self, op: Operation) -> bool {
+        self == Serialization::Legacy
+            || !matches!(
+                op,
+                Operation::AddCompressed | Operation::MulCompressed | Operation::PairingCompressed
+            )
+    }
+
+    /// Read a scalar, exponent or element of the target group of this
+    /// serialization from `input`, points are read by [`Self::read_point`]
+    pub fn read<T: FromBytes + CanonicalDeserialize>(self, input: &mut &[u8]) -> Result<T> {
+        Ok(match self {
+            Serialization::Legacy => T::read(&mut *input)?,
+            Serialization::Canonical | Serialization::CanonicalCompressed => {
+                T::deserialize_unchecked(&mut *input)
+                    .map_err(|e| Error::from_invalid_data(e, Error::FieldElementOutOfRange))?
+            }
+        })
+    }
+
+    /// Read a point of this serialization from `input` without validating it,
+    /// compressed points are decompressed by `decompress`, such as
+    /// [`GroupOperations::decompress_g1`]
+    pub fn read_point<T: FromBytes + CanonicalDeserialize>(
+        self,
+        input: &mut &[u8],
+        decompress: fn(&mut &[u8]) -> Result<T>,
+    ) -> Result<T> {
+        match self {
+            Serialization::CanonicalCompressed => decompress(input),
+            _ => self.read(input),
+        }
+    }
+
+    /// Write `item` in this serialization to `output`
+    pub fn write<T: ToBytes + CanonicalSerialize>(
+        self,
+        item: &T,
+        output: &mut Vec<u8>,
+    ) -> Result<()> {
+        match self {
+            Serialization::Legacy => item.write(&mut *output)?,
+            Serialization::Canonical => item.serialize_uncompressed(&mut *output)?,
+            Serialization::CanonicalCompressed => item.serialize(&mut *output)?,
+        }
+        Ok(())
+    }
+
+    /// Read a scalar of this serialization from `input` and return its legacy
+    /// bytes
+    pub fn read_legacy<T>(self, input: &mut &[u8]) -> Result<Vec<u8>>
+    where
+        T: FromBytes + ToBytes + CanonicalSerialize + CanonicalDeserialize,
+    {
+        let mut output = Vec::new();
+        Serialization::Legacy.write(&self.read::<T>(input)?, &mut output)?;
+        Ok(output)
+    }
+
+    /// Read a point of this serialization from `input` and return its legacy
+    /// bytes, see [`Self::read_point`]
+    pub fn read_point_legacy<T>(
+        self,
+        input: &mut &[u8],
+        decompress: fn(&mut &[u8]) -> Result<T>,
+    ) -> Result<Vec<u8>>
+    where
+        T: FromBytes + ToBytes + CanonicalSerialize + CanonicalDeserialize,
+    {
+        let mut output = Vec::new();
+        Serialization::Legacy.write(&self.read_point(input, decompress)?, &mut output)?;
+        Ok(output)
+    }
+
+    /// Run `op` on group `C` in this serialization
+    pub fn call_group<C: GroupOperations>(self, op: Operation, input: &[u8]) -> Result<Vec<u8>> {
+        self.call_with(op, input, group_item::<C>, |input| {
+            op.call_group::<C>(input)
+        })
+    }
+
+    /// Run `op` on curve `C` with the ABI of `version` in this serialization
+    pub fn call<C: PairingOperations>(
+        self,
+        version: Version,
+        op: Operation,
+        input: &[u8],
+    ) -> Result<Vec<u8>> {
+        self.call_with(op, input, pairing_item::<C>, |input| {
+            op.call_with::<C>(version, input)
+        })
+    }
+
+    fn call_with(
+        self,
+        op: Operation,
+        input: &[u8],
+        item: ItemTranscoder,
+        call: impl FnOnce(&[u8]) -> Result<Vec<u8>>,
+    ) -> Result<Vec<u8>> {
+        if self == Serialization::Legacy {
+            return call(input);
+        }
+        if !self.supports(op) {
+            return Err(Error::UnsupportedOperation);
+        }
+
+        let input = Layout::input(op).transcode(input, |i, input, output| {
+            item(i, self, Serialization::Legacy, input, output)
+        })?;
+        let output = call(&input)?;
+        Layout::output(op).transcode(&output, |i, input, output| {
+            item(i, Serialization::Legacy, self, input, output)
+        })
+    }
+}
+
+/// Items of the operation layouts, see [`crate::abi`]
+#[derive(Debug, Clone, Copy)]
+enum Item {
+    G1,
+    G2,
+    Fr,
+    Gt,
+    /// Exponent, an integer of the scalar limbs
+    Exp,
+}
+
+/// Read `Item` of a curve in the first serialization and write it in the
+/// second
+type ItemTranscoder =
+    fn(Item, Serialization, Serialization, &mut &[u8], &mut Vec<u8>) -> Result<()>;
+
+/// Layout of the input or output of an operation
+#[derive(Debug, Clone, Copy)]
+enum Layout {
+    /// Not serialized by the curve, kept as it is
+    Raw,
+    /// The items once
+    Fixed(&'static [Item]),
+    /// The items repeated to the end
+    Repeated(&'static [Item]),
+    /// `n`, then the items repeated `n` times
+    Counted(&'static [Item]),
+}
+
+impl Layout {
+    /// Input layout of `op`
+    fn input(op: Operation) -> Layout {
+        use Item::*;
+
+        match op {
+            Operation::Add | Operation::AddCompressed => Layout::Fixed(&[G1, G1]),
+            Operation::Mul | Operation::MulCompressed => Layout::Fixed(&[G1, Fr]),
+            Operation::Pairing | Operation::PairingTrusted | Operation::PairingCompressed => {
+                Layout::Repeated(&[G1, G2])
+            }
+            Operation::G2Add => Layout::Fixed(&[G2, G2]),
+            Operation::G2Mul => Layout::Fixed(&[G2, Fr]),
+            Operation::MsmG1 | Operation::MsmG1Trusted => Layout::Counted(&[G1, Fr]),
+            Operation::MsmG2 => Layout::Counted(&[G2, Fr]),
+            Operation::HashToG1 | Operation::HashToG2 => Layout::Raw,
+            Operation::FrAdd | Operation::FrSub | Operation::FrMul => Layout::Fixed(&[Fr, Fr]),
+            Operation::FrInverse => Layout::Fixed(&[Fr]),
+            Operation::FrPow => Layout::Fixed(&[Fr, Exp]),
+            Operation::FrBatchInverse => Layout::Counted(&[Fr]),
+            Operation::GtPairing => Layout::Fixed(&[G1, G2]),
+            Operation::GtMul | Operation::GtEq => Layout::Fixed(&[Gt, Gt]),
+            Operation::GtPow => Layout::Fixed(&[Gt, Fr]),
+            Operation::SumG1 => Layout::Repeated(&[G1]),
+            Operation::SumG2 => Layout::Repeated(&[G2]),
+            Operation::MulGenerator | Operation::G2MulGenerator => Layout::Fixed(&[Fr]),
+        }
+    }
+
+    /// Output layout of `op`
+    fn output(op: Operation) -> Layout {
+        use Item::*;
+
+        match op {
+            Operation::Add
+            | Operation::Mul
+            | Operation::MsmG1
+            | Operation::MsmG1Trusted
+            | Operation::HashToG1
+            | Operation::AddCompressed
+            | Operation::MulCompressed
+            | Operation::SumG1
+            | Operation::MulGenerator => Layout::Fixed(&[G1]),
+            Operation::G2Add
+            | Operation::G2Mul
+            | Operation::MsmG2
+            | Operation::HashToG2
+            | Operation::SumG2
+            | Operation::G2MulGenerator => Layout::Fixed(&[G2]),
+            Operation::FrAdd
+            | Operation::FrSub
+            | Operation::FrMul
+            | Operation::FrInverse
+            | Operation::FrPow => Layout::Fixed(&[Fr]),
+            Operation::FrBatchInverse => Layout::Repeated(&[Fr]),
+            Operation::GtPairing | Operation::GtMul | Operation::GtPow => Layout::Fixed(&[Gt]),
+            Operation::Pairing
+            | Operation::PairingTrusted
+            | Operation::PairingCompressed
+            | Operation::GtEq => Layout::Raw,
+        }
+    }
+
+    /// Rewrite the items of `bytes` with `item`, all the bytes must be read
+    fn transcode(
+        self,
+        bytes: &[u8],
+        item: impl Fn(Item, &mut &[u8], &mut Vec<u8>) -> Result<()>,
+    ) -> Result<Vec<u8>> {
+        let mut input = bytes;
+        let mut output = Vec::with_capacity(bytes.len());
+        let mut items = |items: &[Item], input: &mut &[u8], output: &mut Vec<u8>| {
+            items.iter().try_for_each(|i| item(*i, input, output))
+        };
+
+        match self {
+            Layout::Raw => return Ok(bytes.to_vec()),
+            Layout::Fixed(fixed) => items(fixed, &mut input, &mut output)?,
+            Layout::Repeated(repeated) => {
+                while !input.is_empty() {
+                    items(repeated, &mut input, &mut output)?;
+                }
+            }
+            Layout::Counted(counted) => {
+                let count = <Compact<u32> as Decode>::decode(&mut input)?;
+                count.encode_to(&mut output);
+                for _ in 0..count.0 {
+                    items(counted, &mut input, &mut output)?;
+                }
+            }
+        }
+
+        Error::check_length(bytes.len() - input.len(), bytes.len())?;
+        Ok(output)
+    }
+}
+
+/// Read `T` in `from` and write it in `to`
+fn transcode<T>(
+    from: Serialization,
+    to: Serialization,
+    input: &mut &[u8],
+    output: &mut Vec<u8>,
+) -> Result<()>
+where
+    T: FromBytes + ToBytes + CanonicalSerialize + CanonicalDeserialize,
+{
+    to.write(&from.read::<T>(input)?, output)
+}
+
+/// Read point `T` in `from` and write it in `to`, the operation validates it
+fn transcode_point<T>(
+    from: Serialization,
+    to: Serialization,
+    input: &mut &[u8],
+    output: &mut Vec<u8>,
+    decompress: fn(&mut &[u8]) -> Result<T>,
+) -> Result<()>
+where
+    T: FromBytes + ToBytes + CanonicalSerialize + CanonicalDeserialize,
+{
+    to.write(&from.read_point(input, decompress)?, output)
+}
+
+/// Transcode the items of group `C`, there is no G2 or target group
+fn group_item<C: GroupOperations>(
+    item: Item,
+    from: Serialization,
+    to: Serialization,
+    input: &mut &[u8],
+    output: &mut Vec<u8>,
+) -> Result<()> {
+    match item {
+        Item::G1 => transcode_point(from, to, input, output, C::decompress_g1),
+        Item::Fr => transcode::<Scalar<C>>(from, to, input, output),
+        Item::Exp => transcode::<<Scalar<C> as PrimeField>::BigInt>(from, to, input, output),
+        Item::G2 | Item::Gt => Err(Error::UnsupportedOperation),
+    }
+}
+
+/// Transcode the items of curve `C`
+fn pairing_item<C: PairingOperations>(
+    item: Item,
+    from: Serialization,
+    to: Serialization,
+    input: &mut &[u8],
+    output: &mut Vec<u8>,
+) -> Result<()> {
+    match item {
+        Item::G2 => transcode_point(from, to, input, output, C::decompress_g2),
+        Item::Gt => transcode::<<C as PairingEngine>::Fqk>(from, to, input, output),
+        item => group_item::<C>(item, from, to, input, output),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization() {
+        use crate::{
+            curve::Bn254,
+            ops::{AffineCurve, ProjectiveCurve, UniformRand, Zero},
+            registry::{dispatch, CurveId},
+        };
+        use ark_std::test_rng;
+
+        type G1 = <Bn254 as PairingEngine>::G1Affine;
+        type G2 = <Bn254 as PairingEngine>::G2Affine;
+        type Fr = <Bn254 as PairingEngine>::Fr;
+
+        let mut rng = test_rng();
+        let a = G1::prime_subgroup_generator()
+            .mul(Fr::rand(&mut rng))
+            .into_affine();
+        let b = G2::prime_subgroup_generator()
+            .mul(Fr::rand(&mut rng))
+            .into_affine();
+        let s = Fr::rand(&mut rng);
+
+        assert_eq!(
+            Serialization::split(0x01010020).unwrap(),
+            (Serialization::Canonical, 0x01000020)
+        );
+        assert_eq!(
+            Serialization::split(0x03020022).unwrap(),
+            (Serialization::CanonicalCompressed, 0x03000022)
+        );
+        assert!(Serialization::split(0x01030020).is_err());
+
+        for serialization in Serialization::ALL.iter().copied() {
+            let id = |op: Operation| {
+                serialization.flag() | Version::V1.func_id(op, CurveId::Bn254 as u32)
+            };
+            let write = |g1: &[G1], g2: &[G2], fr: &[Fr]| {
+                let mut bytes = Vec::new();
+                g1.iter()
+                    .for_each(|p| serialization.write(p, &mut bytes).unwrap());
+                g2.iter()
+                    .for_each(|p| serialization.write(p, &mut bytes).unwrap());
+                fr.iter()
+                    .for_each(|s| serialization.write(s, &mut bytes).unwrap());
+                bytes
+            };
+
+            let sum = (a.into_projective() + G1::prime_subgroup_generator().into_projective())
+                .into_affine();
+            assert_eq!(
+                dispatch(
+                    id(Operation::Add),
+                    &write(&[a, G1::prime_subgroup_generator()], &[], &[])
+                ),
+                Ok(write(&[sum], &[], &[]))
+            );
+            assert_eq!(
+                dispatch(id(Operation::Mul), &write(&[a], &[], &[s])),
+                Ok(write(&[a.mul(s).into_affine()], &[], &[]))
+            );
+            assert_eq!(
+                dispatch(id(Operation::G2Mul), &write(&[], &[b], &[s])),
+                Ok(write(&[], &[b.mul(s).into_affine()], &[]))
+            );
+            assert_eq!(
+                dispatch(id(Operation::FrMul), &write(&[], &[], &[s, s])),
+                Ok(write(&[], &[], &[s * s]))
+            );
+            assert_eq!(
+                dispatch(
+                    id(Operation::Add),
+                    &write(&[G1::zero(), G1::zero()], &[], &[])
+                ),
+                Ok(write(&[G1::zero()], &[], &[]))
+            );
+
+            // e(sa, b) * e(-a, sb) == 1
+            let mut input = write(&[a.mul(s).into_affine()], &[b], &[]);
+            input.extend(write(&[-a], &[b.mul(s).into_affine()], &[]));
+            assert_eq!(dispatch(id(Operation::Pairing), &input), Ok(true.encode()));
+
+            // msm of `n | (G1 | Fr)*n`
+            let mut input = Compact(2u32).encode();
+            input.extend(write(&[a], &[], &[s]));
+            input.extend(write(&[a], &[], &[s]));
+            assert_eq!(
+                dispatch(id(Operation::MsmG1), &input),
+                Ok(write(&[a.mul(s + s).into_affine()], &[], &[]))
+            );
+
+            // trailing bytes
+            let mut input = write(&[a, a], &[], &[]);
+            input.push(0);
+            assert!(dispatch(id(Operation::Add), &input).is_err());
+        }
+
+        // the compressed operations are canonical already
+        assert_eq!(
+            dispatch(
+                Serialization::Canonical.flag() | CurveId::Bn254.func_id(Operation::AddCompressed),
+                &[]
+            ),
+            Err(Error::UnsupportedOperation)
+        );
+
+        // uncompressed points are validated by the operation
+        let mut input = Vec::new();
+        Serialization::Canonical.write(&a, &mut input).unwrap();
+        input[0] ^= 1;
+        Serialization::Canonical.write(&a, &mut input).unwrap();
+        assert_eq!(
+            dispatch(
+                Serialization::Canonical.flag() | CurveId::Bn254.func_id(Operation::Add),
+                &input
+            ),
+            Err(Error::PointNotOnCurve)
+        );
+
+        // compressed points are decompressed unchecked, with the errors of the
+        // uncompressed reads, and validated by the operation
+        let add =
+            Serialization::CanonicalCompressed.flag() | CurveId::Bn254.func_id(Operation::Add);
+        let mut point = Vec::new();
+        Serialization::CanonicalCompressed
+            .write(&a, &mut point)
+            .unwrap();
+        // x not less than the modulus, without flags
+        let mut x = vec![0xff; point.len()];
+        x[point.len() - 1] = 0x3f;
+        assert_eq!(
+            dispatch(add, &[&x[..], &point].concat()),
+            Err(Error::FieldElementOutOfRange)
+        );
+        assert_eq!(
+            dispatch(add, &point[..point.len() - 1]),
+            Err(Error::InputTooShort)
+        );
+        // x of no point, 0^3 + 3 is not a square
+        let x = vec![0; point.len()];
+        assert_eq!(
+            dispatch(add, &[&x[..], &point].concat()),
+            Err(Error::PointNotOnCurve)
+        );
+    }
+}
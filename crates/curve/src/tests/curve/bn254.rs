@@ -4,7 +4,7 @@ use ark_groth16::{verify_proof, PreparedVerifyingKey, Proof, VerifyingKey};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{ops::MulAssign, test_rng, vec::Vec, UniformRand};
 
-use crate::{tests::mimc::test_mimc_groth_16, CurveBasicOperations};
+use crate::{tests::mimc::test_mimc_groth_16, GroupOperations, PairingOperations};
 
 /// BN254 ADD
 pub fn bn254_add() {
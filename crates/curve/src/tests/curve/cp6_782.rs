@@ -1,4 +1,4 @@
-use crate::{curve::CP6_782, CurveBasicOperations};
+use crate::{curve::CP6_782, GroupOperations, PairingOperations};
 
 /// CP6_782 ADD
 pub fn cp6_782_add() {
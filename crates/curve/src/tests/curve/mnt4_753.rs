@@ -0,0 +1,6 @@
+use crate::{curve::MNT4_753, tests::mimc::test_mimc_groth_16};
+
+#[test]
+fn test_mnt4_753_groth16() {
+    test_mimc_groth_16::<MNT4_753>();
+}
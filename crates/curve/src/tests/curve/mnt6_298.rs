@@ -0,0 +1,6 @@
+use crate::{curve::MNT6_298, tests::mimc::test_mimc_groth_16};
+
+#[test]
+fn test_mnt6_298_groth16() {
+    test_mimc_groth_16::<MNT6_298>();
+}
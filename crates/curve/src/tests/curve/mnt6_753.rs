@@ -0,0 +1,6 @@
+use crate::{curve::MNT6_753, tests::mimc::test_mimc_groth_16};
+
+#[test]
+fn test_mnt6_753_groth16() {
+    test_mimc_groth_16::<MNT6_753>();
+}
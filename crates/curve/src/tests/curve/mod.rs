@@ -5,5 +5,9 @@ mod bls12_381;
 mod bn254;
 mod bw6_761;
 mod cp6_782;
+mod mnt4_298;
+mod mnt4_753;
+mod mnt6_298;
+mod mnt6_753;
 
 pub use self::{bls12_377::*, bls12_381::*, bn254::*, bw6_761::*, cp6_782::*};
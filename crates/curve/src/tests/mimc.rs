@@ -4,7 +4,7 @@
 use rand::Rng;
 
 // For benchmarking
-use ark_ff::{Field, FromBytes, ToBytes};
+use ark_ff::{Field, FromBytes, One, ToBytes};
 use ark_std::{test_rng, time::Duration, vec::Vec, UniformRand};
 // We'll use these interfaces to construct our circuit.
 use ark_relations::{
@@ -19,6 +19,8 @@ use ark_groth16::{
 };
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
+use crate::{groth16, PairingOperations, Serialization};
+
 const MIMC_ROUNDS: usize = 322;
 
 /// This is an implementation of MiMC, specifically a
@@ -134,7 +136,7 @@ impl<'a, F: Field> ConstraintSynthesizer<F> for MiMCDemo<'a, F> {
 }
 
 #[allow(unused)]
-pub(crate) fn test_mimc_groth_16<E: PairingEngine>() {
+pub(crate) fn test_mimc_groth_16<E: PairingOperations>() {
     // This may not be cryptographically safe, use
     // `OsRng` (for example) in production software.
     let rng = &mut test_rng();
@@ -205,6 +207,40 @@ pub(crate) fn test_mimc_groth_16<E: PairingEngine>() {
 
             // let start = Instant::now();
             assert!(verify_proof(&pvk, &proof, &[image]).unwrap());
+
+            // ark-groth16 encodings, compressed as above and uncompressed
+            let mut inputs_vector = Vec::new();
+            vec![image].serialize(&mut inputs_vector);
+            assert!(groth16::verify_proof_canonical::<E>(
+                Serialization::CanonicalCompressed,
+                pvk_vector.clone(),
+                proof_vector.clone(),
+                inputs_vector.clone(),
+            )
+            .unwrap());
+
+            let mut proof_vector = Vec::new();
+            proof.serialize_uncompressed(&mut proof_vector);
+            let mut pvk_vector = Vec::new();
+            pvk.vk.serialize_uncompressed(&mut pvk_vector);
+            assert!(groth16::verify_proof_canonical::<E>(
+                Serialization::Canonical,
+                pvk_vector.clone(),
+                proof_vector.clone(),
+                inputs_vector.clone(),
+            )
+            .unwrap());
+
+            // the wrong image
+            let mut inputs_vector = Vec::new();
+            vec![image + E::Fr::one()].serialize(&mut inputs_vector);
+            assert!(!groth16::verify_proof_canonical::<E>(
+                Serialization::Canonical,
+                pvk_vector,
+                proof_vector,
+                inputs_vector,
+            )
+            .unwrap());
             // total_verifying += start.elapsed();
 
             // proof.write(&mut proof_vec).unwrap();
@@ -0,0 +1,592 @@
+//! Typed bytes of points and scalars
+//!
+//! `G1Bytes<C>`, `G2Bytes<C>`, `ScalarBytes<C>` and `GtBytes<C>` hold the
+//! bytes of the raw API on curve `C`. The constructors check the length and
+//! validate the point or scalar, so a G2 point or a scalar of another curve
+//! can't be passed where a G1 point belongs. Target group elements are only
+//! checked to be in `Fqk`, the exponentiation checking the group is left to
+//! the target group operations. They are
+//! SCALE encoded as `Vec<u8>`, the same as the fields of the raw parcels.
+
+use ark_std::{convert::TryFrom, fmt, marker::PhantomData, ops::Neg, vec::Vec};
+use parity_scale_codec::{Compact, Decode, Encode, EncodeLike, Input, Output};
+
+use crate::{
+    abi::Version,
+    error::{Error, Result},
+    ops::{
+        self, read_scalar, FromBytes, GroupOperations, PairingEngine, PairingOperations,
+        PrimeField, ProjectiveCurve, Scalar, ToBytes,
+    },
+    registry::{CurveId, Operation},
+};
+
+/// Declare bytes of `C::$len` length
+macro_rules! typed_bytes {
+    ($(#[$attr:meta])* $name:ident, $len:ident, $bound:ident) => {
+        $(#[$attr])*
+        pub struct $name<C>(Vec<u8>, PhantomData<C>);
+
+        impl<C: $bound> $name<C> {
+            /// Wrap trusted bytes, only the length is checked
+            pub fn from_trusted(bytes: Vec<u8>) -> Result<Self> {
+                Error::check_length(C::$len, bytes.len())?;
+                Ok($name(bytes, PhantomData))
+            }
+        }
+
+        impl<C> $name<C> {
+            pub fn as_bytes(&self) -> &[u8] {
+                &self.0
+            }
+
+            pub fn into_bytes(self) -> Vec<u8> {
+                self.0
+            }
+        }
+
+        impl<C> Clone for $name<C> {
+            fn clone(&self) -> Self {
+                $name(self.0.clone(), PhantomData)
+            }
+        }
+
+        impl<C> PartialEq for $name<C> {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl<C> Eq for $name<C> {}
+
+        impl<C> fmt::Debug for $name<C> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}({})", stringify!($name), hex::encode(&self.0))
+            }
+        }
+
+        impl<C> AsRef<[u8]> for $name<C> {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl<C: $bound> TryFrom<Vec<u8>> for $name<C> {
+            type Error = Error;
+
+            fn try_from(bytes: Vec<u8>) -> Result<Self> {
+                Self::new(bytes)
+            }
+        }
+
+        impl<C> Encode for $name<C> {
+            fn size_hint(&self) -> usize {
+                self.0.size_hint()
+            }
+
+            fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+                self.0.encode_to(dest)
+            }
+        }
+
+        impl<C> EncodeLike for $name<C> {}
+
+        impl<C: $bound> Decode for $name<C> {
+            fn decode<I: Input>(input: &mut I) -> core::result::Result<Self, parity_scale_codec::Error> {
+                Self::new(Vec::<u8>::decode(input)?)
+                    .map_err(|_| concat!("invalid ", stringify!($name)).into())
+            }
+        }
+    };
+}
+
+typed_bytes!(
+    /// Bytes of a G1 point of curve `C`
+    G1Bytes,
+    G1_LEN,
+    GroupOperations
+);
+
+typed_bytes!(
+    /// Bytes of a G2 point of curve `C`
+    G2Bytes,
+    G2_LEN,
+    PairingOperations
+);
+
+typed_bytes!(
+    /// Bytes of a scalar of curve `C`
+    ScalarBytes,
+    SCALAR_LEN,
+    GroupOperations
+);
+
+typed_bytes!(
+    /// Bytes of a target group element of curve `C`
+    GtBytes,
+    GT_LEN,
+    PairingOperations
+);
+
+impl<C: GroupOperations> G1Bytes<C> {
+    /// Check the length and validate the point
+    pub fn new(bytes: Vec<u8>) -> Result<Self> {
+        Error::check_length(C::G1_LEN, bytes.len())?;
+        C::read_g1(&bytes)?;
+        Ok(G1Bytes(bytes, PhantomData))
+    }
+
+    pub fn from_affine(point: &C::Affine) -> Self {
+        let mut bytes = Vec::with_capacity(C::G1_LEN);
+        point.write(&mut bytes).expect("write to vec never fails");
+        G1Bytes(bytes, PhantomData)
+    }
+
+    pub fn to_affine(&self) -> Result<C::Affine> {
+        Ok(FromBytes::read(self.as_bytes())?)
+    }
+
+    /// Negation of the point
+    pub fn neg(&self) -> Result<Self> {
+        Ok(Self::from_affine(&self.to_affine()?.neg()))
+    }
+}
+
+impl<C: PairingOperations> G2Bytes<C> {
+    /// Check the length and validate the point
+    pub fn new(bytes: Vec<u8>) -> Result<Self> {
+        Error::check_length(C::G2_LEN, bytes.len())?;
+        C::read_g2(&bytes)?;
+        Ok(G2Bytes(bytes, PhantomData))
+    }
+
+    pub fn from_affine(point: &<C as PairingEngine>::G2Affine) -> Self {
+        let mut bytes = Vec::with_capacity(C::G2_LEN);
+        point.write(&mut bytes).expect("write to vec never fails");
+        G2Bytes(bytes, PhantomData)
+    }
+
+    pub fn to_affine(&self) -> Result<<C as PairingEngine>::G2Affine> {
+        Ok(FromBytes::read(self.as_bytes())?)
+    }
+
+    /// Negation of the point
+    pub fn neg(&self) -> Result<Self> {
+        Ok(Self::from_affine(&self.to_affine()?.neg()))
+    }
+}
+
+impl<C: GroupOperations> ScalarBytes<C> {
+    /// Check the length and the scalar is less than the subgroup order
+    pub fn new(bytes: Vec<u8>) -> Result<Self> {
+        Error::check_length(C::SCALAR_LEN, bytes.len())?;
+        read_scalar::<Scalar<C>>(&bytes)?;
+        Ok(ScalarBytes(bytes, PhantomData))
+    }
+
+    pub fn from_scalar(scalar: &Scalar<C>) -> Self {
+        let mut bytes = Vec::with_capacity(C::SCALAR_LEN);
+        scalar.write(&mut bytes).expect("write to vec never fails");
+        ScalarBytes(bytes, PhantomData)
+    }
+
+    pub fn to_scalar(&self) -> Result<Scalar<C>> {
+        read_scalar(self.as_bytes())
+    }
+}
+
+impl<C: PairingOperations> GtBytes<C> {
+    /// Check the length and the element is in `Fqk`, the target group
+    /// membership is checked by the target group operations, see
+    /// [`PairingOperations::read_gt`]
+    pub fn new(bytes: Vec<u8>) -> Result<Self> {
+        Error::check_length(C::GT_LEN, bytes.len())?;
+        <<C as PairingEngine>::Fqk as FromBytes>::read(&*bytes)?;
+        Ok(GtBytes(bytes, PhantomData))
+    }
+
+    pub fn from_fqk(gt: &<C as PairingEngine>::Fqk) -> Self {
+        let mut bytes = Vec::with_capacity(C::GT_LEN);
+        gt.write(&mut bytes).expect("write to vec never fails");
+        GtBytes(bytes, PhantomData)
+    }
+
+    pub fn to_fqk(&self) -> Result<<C as PairingEngine>::Fqk> {
+        Ok(FromBytes::read(self.as_bytes())?)
+    }
+}
+
+/// Call `op` on the curve of `curve_id` with the ABI of `version`
+///
+/// The one route of the typed API to [`crate::call`], the byte functions of
+/// the crate root are shims over it.
+pub(crate) fn call_id_with(
+    version: Version,
+    curve_id: u32,
+    op: Operation,
+    input: &[u8],
+) -> Result<Vec<u8>> {
+    let curve = CurveId::try_from(curve_id)? as u32;
+    crate::call(version.func_id(op, curve), input)
+}
+
+/// Call `op` on the curve of `curve_id` with the legacy ABI
+pub(crate) fn call_id(curve_id: u32, op: Operation, input: &[u8]) -> Result<Vec<u8>> {
+    call_id_with(Version::Legacy, curve_id, op, input)
+}
+
+/// Call `op` on curve or group `C`
+fn call<C: GroupOperations>(op: Operation, input: &[u8]) -> Result<Vec<u8>> {
+    call_id(C::CURVE_ID, op, input)
+}
+
+/// G1 add
+pub fn add<C: GroupOperations>(a: &G1Bytes<C>, b: &G1Bytes<C>) -> Result<G1Bytes<C>> {
+    G1Bytes::from_trusted(call::<C>(
+        Operation::Add,
+        &[a.as_bytes(), b.as_bytes()].concat(),
+    )?)
+}
+
+/// G1 scalar mul
+pub fn mul<C: GroupOperations>(point: &G1Bytes<C>, scalar: &ScalarBytes<C>) -> Result<G1Bytes<C>> {
+    G1Bytes::from_trusted(call::<C>(
+        Operation::Mul,
+        &[point.as_bytes(), scalar.as_bytes()].concat(),
+    )?)
+}
+
+/// G1 scalar mul in constant time for secret scalars, computed locally
+pub fn mul_secret<C: GroupOperations>(
+    point: &G1Bytes<C>,
+    scalar: &ScalarBytes<C>,
+) -> Result<G1Bytes<C>> {
+    let point = ops::mul_secret(&point.to_affine()?, &scalar.to_scalar()?, C::swap_g1);
+    Ok(G1Bytes::from_affine(&point.into_affine()))
+}
+
+/// G1 generator scalar mul
+pub fn mul_generator<C: GroupOperations>(scalar: &ScalarBytes<C>) -> Result<G1Bytes<C>> {
+    G1Bytes::from_trusted(call::<C>(Operation::MulGenerator, scalar.as_bytes())?)
+}
+
+/// G2 add
+pub fn g2_add<C: PairingOperations>(a: &G2Bytes<C>, b: &G2Bytes<C>) -> Result<G2Bytes<C>> {
+    G2Bytes::from_trusted(call::<C>(
+        Operation::G2Add,
+        &[a.as_bytes(), b.as_bytes()].concat(),
+    )?)
+}
+
+/// G2 scalar mul
+pub fn g2_mul<C: PairingOperations>(
+    point: &G2Bytes<C>,
+    scalar: &ScalarBytes<C>,
+) -> Result<G2Bytes<C>> {
+    G2Bytes::from_trusted(call::<C>(
+        Operation::G2Mul,
+        &[point.as_bytes(), scalar.as_bytes()].concat(),
+    )?)
+}
+
+/// G2 scalar mul in constant time for secret scalars, computed locally
+pub fn g2_mul_secret<C: PairingOperations>(
+    point: &G2Bytes<C>,
+    scalar: &ScalarBytes<C>,
+) -> Result<G2Bytes<C>> {
+    let point = ops::mul_secret(&point.to_affine()?, &scalar.to_scalar()?, C::swap_g2);
+    Ok(G2Bytes::from_affine(&point.into_affine()))
+}
+
+/// G2 generator scalar mul
+pub fn g2_mul_generator<C: PairingOperations>(scalar: &ScalarBytes<C>) -> Result<G2Bytes<C>> {
+    G2Bytes::from_trusted(call::<C>(Operation::G2MulGenerator, scalar.as_bytes())?)
+}
+
+/// Input of hashing to curve
+pub(crate) fn hash_input(dst: &[u8], msg: &[u8]) -> Vec<u8> {
+    let mut input = dst.encode();
+    input.extend_from_slice(msg);
+    input
+}
+
+/// Hash `msg` to G1 with the domain separation tag `dst`
+pub fn hash_to_g1<C: GroupOperations>(dst: &[u8], msg: &[u8]) -> Result<G1Bytes<C>> {
+    G1Bytes::from_trusted(call::<C>(Operation::HashToG1, &hash_input(dst, msg))?)
+}
+
+/// Hash `msg` to G2 with the domain separation tag `dst`
+pub fn hash_to_g2<C: PairingOperations>(dst: &[u8], msg: &[u8]) -> Result<G2Bytes<C>> {
+    G2Bytes::from_trusted(call::<C>(Operation::HashToG2, &hash_input(dst, msg))?)
+}
+
+/// Scalar field operation `op` of `scalars`
+fn fr_op<C: GroupOperations>(op: Operation, scalars: &[&ScalarBytes<C>]) -> Result<ScalarBytes<C>> {
+    let input = scalars
+        .iter()
+        .flat_map(|s| s.as_bytes())
+        .copied()
+        .collect::<Vec<_>>();
+    ScalarBytes::from_trusted(call::<C>(op, &input)?)
+}
+
+/// Scalar field add
+pub fn fr_add<C: GroupOperations>(
+    a: &ScalarBytes<C>,
+    b: &ScalarBytes<C>,
+) -> Result<ScalarBytes<C>> {
+    fr_op(Operation::FrAdd, &[a, b])
+}
+
+/// Scalar field sub
+pub fn fr_sub<C: GroupOperations>(
+    a: &ScalarBytes<C>,
+    b: &ScalarBytes<C>,
+) -> Result<ScalarBytes<C>> {
+    fr_op(Operation::FrSub, &[a, b])
+}
+
+/// Scalar field mul
+pub fn fr_mul<C: GroupOperations>(
+    a: &ScalarBytes<C>,
+    b: &ScalarBytes<C>,
+) -> Result<ScalarBytes<C>> {
+    fr_op(Operation::FrMul, &[a, b])
+}
+
+/// Scalar field inverse
+pub fn fr_inverse<C: GroupOperations>(a: &ScalarBytes<C>) -> Result<ScalarBytes<C>> {
+    fr_op(Operation::FrInverse, &[a])
+}
+
+/// Scalar field power, `exp` is an integer of the scalar limbs
+pub fn fr_pow<C: GroupOperations>(
+    a: &ScalarBytes<C>,
+    exp: &<Scalar<C> as PrimeField>::BigInt,
+) -> Result<ScalarBytes<C>> {
+    let mut input = a.as_bytes().to_vec();
+    exp.write(&mut input).expect("write to vec never fails");
+    ScalarBytes::from_trusted(call::<C>(Operation::FrPow, &input)?)
+}
+
+/// Scalar field batch inverse
+pub fn fr_batch_inverse<C: GroupOperations>(
+    scalars: &[ScalarBytes<C>],
+) -> Result<Vec<ScalarBytes<C>>> {
+    let mut input = Compact(scalars.len() as u32).encode();
+    for scalar in scalars {
+        input.extend_from_slice(scalar.as_bytes());
+    }
+    call::<C>(Operation::FrBatchInverse, &input)?
+        .chunks(C::SCALAR_LEN)
+        .map(|s| ScalarBytes::from_trusted(s.to_vec()))
+        .collect()
+}
+
+/// Pairing e(`g1`, `g2`)
+pub fn gt_pairing<C: PairingOperations>(g1: &G1Bytes<C>, g2: &G2Bytes<C>) -> Result<GtBytes<C>> {
+    GtBytes::from_trusted(call::<C>(
+        Operation::GtPairing,
+        &[g1.as_bytes(), g2.as_bytes()].concat(),
+    )?)
+}
+
+/// Target group mul
+pub fn gt_mul<C: PairingOperations>(a: &GtBytes<C>, b: &GtBytes<C>) -> Result<GtBytes<C>> {
+    GtBytes::from_trusted(call::<C>(
+        Operation::GtMul,
+        &[a.as_bytes(), b.as_bytes()].concat(),
+    )?)
+}
+
+/// Target group exponentiation
+pub fn gt_pow<C: PairingOperations>(
+    gt: &GtBytes<C>,
+    scalar: &ScalarBytes<C>,
+) -> Result<GtBytes<C>> {
+    GtBytes::from_trusted(call::<C>(
+        Operation::GtPow,
+        &[gt.as_bytes(), scalar.as_bytes()].concat(),
+    )?)
+}
+
+/// Target group equality
+pub fn gt_eq<C: PairingOperations>(a: &GtBytes<C>, b: &GtBytes<C>) -> Result<bool> {
+    let output = call::<C>(Operation::GtEq, &[a.as_bytes(), b.as_bytes()].concat())?;
+    Ok(bool::decode(&mut &*output)?)
+}
+
+/// Input of multi-scalar muls
+fn msm_input<P: AsRef<[u8]>, S: AsRef<[u8]>>(pairs: &[(P, S)]) -> Vec<u8> {
+    let mut input = Compact(pairs.len() as u32).encode();
+    for (point, scalar) in pairs {
+        input.extend_from_slice(point.as_ref());
+        input.extend_from_slice(scalar.as_ref());
+    }
+    input
+}
+
+/// G1 multi-scalar mul
+pub fn msm_g1<C: GroupOperations>(pairs: &[(G1Bytes<C>, ScalarBytes<C>)]) -> Result<G1Bytes<C>> {
+    msm_g1_with(Operation::MsmG1, pairs)
+}
+
+/// G1 multi-scalar mul with `MsmG1` or `MsmG1Trusted`
+pub(crate) fn msm_g1_with<C: GroupOperations>(
+    op: Operation,
+    pairs: &[(G1Bytes<C>, ScalarBytes<C>)],
+) -> Result<G1Bytes<C>> {
+    G1Bytes::from_trusted(call::<C>(op, &msm_input(pairs))?)
+}
+
+/// G2 multi-scalar mul
+pub fn msm_g2<C: PairingOperations>(pairs: &[(G2Bytes<C>, ScalarBytes<C>)]) -> Result<G2Bytes<C>> {
+    G2Bytes::from_trusted(call::<C>(Operation::MsmG2, &msm_input(pairs))?)
+}
+
+/// Input of sums
+fn sum_input<P: AsRef<[u8]>>(points: &[P]) -> Vec<u8> {
+    points.iter().flat_map(|p| p.as_ref()).copied().collect()
+}
+
+/// Sum of G1 points
+pub fn sum_g1<C: GroupOperations>(points: &[G1Bytes<C>]) -> Result<G1Bytes<C>> {
+    G1Bytes::from_trusted(call::<C>(Operation::SumG1, &sum_input(points))?)
+}
+
+/// Sum of G2 points
+pub fn sum_g2<C: PairingOperations>(points: &[G2Bytes<C>]) -> Result<G2Bytes<C>> {
+    G2Bytes::from_trusted(call::<C>(Operation::SumG2, &sum_input(points))?)
+}
+
+/// Check the product of pairings is one
+pub fn pairing<C: PairingOperations>(pairs: &[(G1Bytes<C>, G2Bytes<C>)]) -> Result<bool> {
+    pairing_with(Operation::Pairing, pairs)
+}
+
+/// Pairing check with `Pairing` or `PairingTrusted`
+pub(crate) fn pairing_with<C: PairingOperations>(
+    op: Operation,
+    pairs: &[(G1Bytes<C>, G2Bytes<C>)],
+) -> Result<bool> {
+    let mut input = Vec::with_capacity(pairs.len() * (C::G1_LEN + C::G2_LEN));
+    for (g1, g2) in pairs {
+        input.extend_from_slice(g1.as_bytes());
+        input.extend_from_slice(g2.as_bytes());
+    }
+    let output = call_id_with(Version::V1, C::CURVE_ID, op, &input)?;
+    Version::V1.decode_bool(&output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_bytes() {
+        use crate::{
+            curve::{Bls12_381, Bn254, Pallas},
+            ops::{AffineCurve, One, Zero},
+        };
+
+        let g1 = G1Bytes::<Bn254>::from_affine(&AffineCurve::prime_subgroup_generator());
+        let g2 = G2Bytes::<Bn254>::from_affine(&AffineCurve::prime_subgroup_generator());
+        let two = ScalarBytes::<Bn254>::from_scalar(
+            &(<Bn254 as PairingEngine>::Fr::one() + <Bn254 as PairingEngine>::Fr::one()),
+        );
+
+        assert_eq!(G1Bytes::<Bn254>::new(g1.as_bytes().to_vec()).unwrap(), g1);
+        assert_eq!(G1Bytes::<Bn254>::decode(&mut &*g1.encode()).unwrap(), g1);
+        assert_eq!(g1.encode(), g1.as_bytes().to_vec().encode());
+        assert_eq!(add(&g1, &g1).unwrap(), mul(&g1, &two).unwrap());
+        assert_eq!(mul_secret(&g1, &two).unwrap(), mul(&g1, &two).unwrap());
+        assert_eq!(g2_add(&g2, &g2).unwrap(), g2_mul(&g2, &two).unwrap());
+        assert_eq!(
+            g2_mul_secret(&g2, &two).unwrap(),
+            g2_mul(&g2, &two).unwrap()
+        );
+        assert_eq!(mul_generator(&two).unwrap(), mul(&g1, &two).unwrap());
+        assert_eq!(g2_mul_generator(&two).unwrap(), g2_mul(&g2, &two).unwrap());
+        assert_eq!(
+            msm_g1(&[(g1.clone(), two.clone())]).unwrap(),
+            mul(&g1, &two).unwrap()
+        );
+        assert!(pairing(&[(g1.clone(), g2.clone()), (g1.neg().unwrap(), g2.clone())]).unwrap());
+        assert_eq!(
+            sum_g1(&[g1.clone(), g1.clone(), g1.clone()]).unwrap(),
+            add(&add(&g1, &g1).unwrap(), &g1).unwrap()
+        );
+        assert_eq!(
+            sum_g2(&[g2.clone(), g2.clone()]).unwrap(),
+            g2_mul(&g2, &two).unwrap()
+        );
+        assert_eq!(
+            sum_g1::<Bn254>(&[]).unwrap(),
+            G1Bytes::from_affine(&Zero::zero())
+        );
+        let h = hash_to_g1::<Bn254>(b"tag", b"msg").unwrap();
+        assert_eq!(G1Bytes::<Bn254>::new(h.as_bytes().to_vec()).unwrap(), h);
+        assert!(hash_to_g2::<Bls12_381>(b"tag", b"msg").is_ok());
+        assert_eq!(
+            hash_to_g2::<Bn254>(b"tag", b"msg"),
+            Err(Error::UnsupportedOperation)
+        );
+        let one = ScalarBytes::<Bn254>::from_scalar(&<Bn254 as PairingEngine>::Fr::one());
+        assert_eq!(fr_add(&one, &one).unwrap(), two);
+        assert_eq!(fr_mul(&two, &fr_inverse(&two).unwrap()).unwrap(), one);
+        let fr = |n: u64| <Bn254 as PairingEngine>::Fr::from(n);
+        assert_eq!(
+            fr_pow(&two, &fr(10).into_repr()).unwrap(),
+            ScalarBytes::from_scalar(&fr(1024))
+        );
+        let e = gt_pairing(&g1, &g2).unwrap();
+        let e2 = gt_pairing(&add(&g1, &g1).unwrap(), &g2).unwrap();
+        assert_eq!(GtBytes::<Bn254>::new(e.as_bytes().to_vec()).unwrap(), e);
+        assert_eq!(gt_mul(&e, &e).unwrap(), e2);
+        assert_eq!(gt_pow(&e, &two).unwrap(), e2);
+        assert!(gt_eq(&gt_pow(&e, &two).unwrap(), &e2).unwrap());
+        assert!(!gt_eq(&e, &e2).unwrap());
+
+        // wrong group, curve or values
+        assert_eq!(
+            G1Bytes::<Bn254>::new(g2.as_bytes().to_vec()),
+            Err(Error::InvalidLength {
+                expected: 65,
+                got: 129
+            })
+        );
+        assert!(G1Bytes::<Bls12_381>::new(g1.as_bytes().to_vec()).is_err());
+        assert_eq!(
+            G1Bytes::<Bn254>::new(vec![0xff; 65]),
+            Err(Error::FieldElementOutOfRange)
+        );
+        assert_eq!(
+            ScalarBytes::<Bn254>::new(vec![0xff; 32]),
+            Err(Error::ScalarOutOfRange)
+        );
+        // zero is not in the target group, which the operations check
+        let zero = GtBytes::<Bn254>::new(vec![0; 384]).unwrap();
+        assert_eq!(gt_mul(&zero, &e), Err(Error::NotInTargetGroup));
+        assert_eq!(
+            GtBytes::<Bn254>::new(vec![0xff; 384]),
+            Err(Error::FieldElementOutOfRange)
+        );
+        // (0, 0) is not on curve
+        assert!(G1Bytes::<Bn254>::decode(&mut &*vec![0u8; 65].encode()).is_err());
+        assert!(G1Bytes::<Bn254>::decode(&mut &*vec![0u8; 64].encode()).is_err());
+
+        // group curves have G1 points and scalars
+        let p = G1Bytes::<Pallas>::from_affine(&AffineCurve::prime_subgroup_generator());
+        let two = ScalarBytes::<Pallas>::from_scalar(
+            &(Scalar::<Pallas>::one() + Scalar::<Pallas>::one()),
+        );
+        assert_eq!(G1Bytes::<Pallas>::new(p.as_bytes().to_vec()).unwrap(), p);
+        assert_eq!(add(&p, &p).unwrap(), mul(&p, &two).unwrap());
+        assert_eq!(mul_secret(&p, &two).unwrap(), mul(&p, &two).unwrap());
+        assert_eq!(mul_generator(&two).unwrap(), mul(&p, &two).unwrap());
+        assert_eq!(
+            sum_g1(&[p.clone(), p.clone()]).unwrap(),
+            msm_g1(&[(p.clone(), two.clone())]).unwrap()
+        );
+    }
+}
@@ -0,0 +1,520 @@
+//! Weights of the curve operations
+//!
+//! Weights are measured in picoseconds as the weights of pallet-contracts.
+//! The cost of an operation is linear in the items of its input, which are
+//! the `(G1, G2)` pairs of pairings, the `(point, scalar)` pairs of
+//! multi-scalar muls, the points of sums, the scalars of batch inverses, the
+//! input bytes of hashes to curve and the significant 64-bit limbs of the
+//! exponent of `fr_pow`. The other operations have a constant cost, the
+//! target group operations are dominated by checking their inputs are in the
+//! target group. Operations a curve doesn't support cost zero.
+//!
+//! The tables are generated by the weights harness of the tests crate, run it
+//! on the reference machine of the chain and replace the tables with its
+//! output:
+//!
+//! ```text
+//! cargo test --release -p tests weights -- --ignored --nocapture
+//! ```
+
+use ark_std::convert::TryFrom;
+use parity_scale_codec::{Decode, Encode};
+
+use crate::{
+    abi,
+    error::Result,
+    evm::Precompile,
+    registry::{CurveId, Operation},
+    serialization::Serialization,
+};
+
+/// Weight in picoseconds
+pub type Weight = u64;
+
+/// Weight of one nanosecond
+pub const WEIGHT_PER_NANOS: Weight = 1_000;
+
+/// Linear cost `base + per_item * items`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub struct Cost {
+    pub base: Weight,
+    pub per_item: Weight,
+}
+
+impl Cost {
+    pub const fn new(base: Weight, per_item: Weight) -> Self {
+        Cost { base, per_item }
+    }
+
+    /// `factor` times this cost
+    pub const fn scaled(self, factor: u64) -> Self {
+        Cost::new(self.base * factor, self.per_item * factor)
+    }
+
+    /// Weight of `items` items
+    pub fn weight(self, items: u64) -> Weight {
+        self.base
+            .saturating_add(self.per_item.saturating_mul(items))
+    }
+}
+
+/// Costs of the operations on a curve
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurveWeights {
+    pub add: Cost,
+    pub mul: Cost,
+    pub pairing: Cost,
+    pub g2_add: Cost,
+    pub g2_mul: Cost,
+    pub msm_g1: Cost,
+    pub msm_g2: Cost,
+    pub add_compressed: Cost,
+    pub mul_compressed: Cost,
+    pub pairing_compressed: Cost,
+    pub msm_g1_trusted: Cost,
+    pub pairing_trusted: Cost,
+    pub hash_to_g1: Cost,
+    pub hash_to_g2: Cost,
+    pub fr_add: Cost,
+    pub fr_sub: Cost,
+    pub fr_mul: Cost,
+    pub fr_inverse: Cost,
+    pub fr_pow: Cost,
+    pub fr_batch_inverse: Cost,
+    pub gt_pairing: Cost,
+    pub gt_mul: Cost,
+    pub gt_pow: Cost,
+    pub gt_eq: Cost,
+    pub sum_g1: Cost,
+    pub sum_g2: Cost,
+    pub mul_generator: Cost,
+    pub g2_mul_generator: Cost,
+}
+
+impl CurveWeights {
+    /// `factor` times these costs
+    pub const fn scaled(&self, factor: u64) -> Self {
+        CurveWeights {
+            add: self.add.scaled(factor),
+            mul: self.mul.scaled(factor),
+            pairing: self.pairing.scaled(factor),
+            g2_add: self.g2_add.scaled(factor),
+            g2_mul: self.g2_mul.scaled(factor),
+            msm_g1: self.msm_g1.scaled(factor),
+            msm_g2: self.msm_g2.scaled(factor),
+            add_compressed: self.add_compressed.scaled(factor),
+            mul_compressed: self.mul_compressed.scaled(factor),
+            pairing_compressed: self.pairing_compressed.scaled(factor),
+            msm_g1_trusted: self.msm_g1_trusted.scaled(factor),
+            pairing_trusted: self.pairing_trusted.scaled(factor),
+            hash_to_g1: self.hash_to_g1.scaled(factor),
+            hash_to_g2: self.hash_to_g2.scaled(factor),
+            fr_add: self.fr_add.scaled(factor),
+            fr_sub: self.fr_sub.scaled(factor),
+            fr_mul: self.fr_mul.scaled(factor),
+            fr_inverse: self.fr_inverse.scaled(factor),
+            fr_pow: self.fr_pow.scaled(factor),
+            fr_batch_inverse: self.fr_batch_inverse.scaled(factor),
+            gt_pairing: self.gt_pairing.scaled(factor),
+            gt_mul: self.gt_mul.scaled(factor),
+            gt_pow: self.gt_pow.scaled(factor),
+            gt_eq: self.gt_eq.scaled(factor),
+            sum_g1: self.sum_g1.scaled(factor),
+            sum_g2: self.sum_g2.scaled(factor),
+            mul_generator: self.mul_generator.scaled(factor),
+            g2_mul_generator: self.g2_mul_generator.scaled(factor),
+        }
+    }
+
+    /// Cost of `op`
+    pub fn cost(&self, op: Operation) -> Cost {
+        match op {
+            Operation::Add => self.add,
+            Operation::Mul => self.mul,
+            Operation::Pairing => self.pairing,
+            Operation::G2Add => self.g2_add,
+            Operation::G2Mul => self.g2_mul,
+            Operation::MsmG1 => self.msm_g1,
+            Operation::MsmG2 => self.msm_g2,
+            Operation::AddCompressed => self.add_compressed,
+            Operation::MulCompressed => self.mul_compressed,
+            Operation::PairingCompressed => self.pairing_compressed,
+            Operation::MsmG1Trusted => self.msm_g1_trusted,
+            Operation::PairingTrusted => self.pairing_trusted,
+            Operation::HashToG1 => self.hash_to_g1,
+            Operation::HashToG2 => self.hash_to_g2,
+            Operation::FrAdd => self.fr_add,
+            Operation::FrSub => self.fr_sub,
+            Operation::FrMul => self.fr_mul,
+            Operation::FrInverse => self.fr_inverse,
+            Operation::FrPow => self.fr_pow,
+            Operation::FrBatchInverse => self.fr_batch_inverse,
+            Operation::GtPairing => self.gt_pairing,
+            Operation::GtMul => self.gt_mul,
+            Operation::GtPow => self.gt_pow,
+            Operation::GtEq => self.gt_eq,
+            Operation::SumG1 => self.sum_g1,
+            Operation::SumG2 => self.sum_g2,
+            Operation::MulGenerator => self.mul_generator,
+            Operation::G2MulGenerator => self.g2_mul_generator,
+        }
+    }
+}
+
+/// Measured as BLS12-381, the fields of BLS12-377 have the same sizes. Hashing
+/// to curve searches `Z` and clears the whole cofactors, estimated as twice
+/// BLS12-381 until measured
+pub const BLS12_377: CurveWeights = CurveWeights {
+    hash_to_g1: BLS12_381.hash_to_g1.scaled(2),
+    hash_to_g2: BLS12_381.hash_to_g2.scaled(2),
+    ..BLS12_381
+};
+
+/// BLS12-381 weights
+pub const BLS12_381: CurveWeights = CurveWeights {
+    add: Cost::new(559277000, 0),
+    mul: Cost::new(820425000, 0),
+    pairing: Cost::new(2415012858, 2693046142),
+    g2_add: Cost::new(2214771000, 0),
+    g2_mul: Cost::new(2333651000, 0),
+    msm_g1: Cost::new(612593143, 605206857),
+    msm_g2: Cost::new(2492142000, 1343163000),
+    add_compressed: Cost::new(811006000, 0),
+    mul_compressed: Cost::new(808223000, 0),
+    pairing_compressed: Cost::new(2043801143, 2992512857),
+    msm_g1_trusted: Cost::new(638940429, 198516571),
+    pairing_trusted: Cost::new(2555423000, 612508000),
+    hash_to_g1: Cost::new(221804000, HASH_PER_BYTE),
+    hash_to_g2: Cost::new(2480418000, HASH_PER_BYTE),
+    fr_add: Cost::new(212000, 0),
+    fr_sub: Cost::new(207000, 0),
+    fr_mul: Cost::new(230000, 0),
+    fr_inverse: Cost::new(2473000, 0),
+    // split from the measurement with a random 4-limb exponent, the base is
+    // the one of `fr_mul`
+    fr_pow: Cost::new(230000, 2303000),
+    fr_batch_inverse: Cost::new(2271429, 146571),
+    gt_pairing: Cost::new(2303489000, 0),
+    gt_mul: Cost::new(3078111000, 0),
+    gt_pow: Cost::new(2985209000, 0),
+    gt_eq: Cost::new(1388000, 0),
+    // estimated as half of an add per point, the validation, until measured
+    sum_g1: Cost::new(0, 279638500),
+    sum_g2: Cost::new(0, 1107385500),
+    // estimated as a quarter of a mul until measured, a mixed add per 4-bit
+    // window instead of a double per bit, the generator tables are built on
+    // the first call of the process and not charged
+    mul_generator: Cost::new(205106250, 0),
+    g2_mul_generator: Cost::new(583412750, 0),
+};
+
+/// BN254 weights
+pub const BN254: CurveWeights = CurveWeights {
+    add: Cost::new(217769000, 0),
+    mul: Cost::new(236439000, 0),
+    pairing: Cost::new(1084767858, 1642369142),
+    g2_add: Cost::new(1289821000, 0),
+    g2_mul: Cost::new(1409087000, 0),
+    msm_g1: Cost::new(133410143, 298227857),
+    msm_g2: Cost::new(895222715, 1002335285),
+    add_compressed: Cost::new(396292000, 0),
+    mul_compressed: Cost::new(370765000, 0),
+    pairing_compressed: Cost::new(1814606143, 1404493857),
+    msm_g1_trusted: Cost::new(176814429, 102634571),
+    pairing_trusted: Cost::new(1534128572, 567442428),
+    hash_to_g1: Cost::new(70846000, HASH_PER_BYTE),
+    hash_to_g2: Cost::new(0, 0),
+    fr_add: Cost::new(211000, 0),
+    fr_sub: Cost::new(211000, 0),
+    fr_mul: Cost::new(234000, 0),
+    fr_inverse: Cost::new(2517000, 0),
+    // split as BLS12-381
+    fr_pow: Cost::new(234000, 1878000),
+    fr_batch_inverse: Cost::new(2536858, 122142),
+    gt_pairing: Cost::new(1612331000, 0),
+    gt_mul: Cost::new(2016007000, 0),
+    gt_pow: Cost::new(2012075000, 0),
+    gt_eq: Cost::new(758000, 0),
+    // estimated as half of an add per point, the validation, until measured
+    sum_g1: Cost::new(0, 108884500),
+    sum_g2: Cost::new(0, 644910500),
+    // estimated as a quarter of a mul until measured, as BLS12-381
+    mul_generator: Cost::new(59109750, 0),
+    g2_mul_generator: Cost::new(352271750, 0),
+};
+
+/// Estimated as 4 times BLS12-381 until measured, the base field of BW6-761
+/// is twice as large
+pub const BW6_761: CurveWeights = BLS12_381.scaled(4);
+
+/// Estimated as 10 times BLS12-381 until measured, the base field of CP6-782
+/// is twice as large and G2 is over a cubic extension
+pub const CP6_782: CurveWeights = BLS12_381.scaled(10);
+
+/// Estimated as twice BLS12-381 until measured, the Miller loops of the MNT
+/// curves run over the whole scalar field
+pub const MNT4_298: CurveWeights = BLS12_381.scaled(2);
+
+/// Estimated as 3 times BLS12-381 until measured, G2 of MNT6-298 is over a
+/// cubic extension
+pub const MNT6_298: CurveWeights = BLS12_381.scaled(3);
+
+/// Estimated as 8 times BLS12-381 until measured, the base field of MNT4-753
+/// is twice as large
+pub const MNT4_753: CurveWeights = BLS12_381.scaled(8);
+
+/// Estimated as 10 times BLS12-381 until measured, as CP6-782
+pub const MNT6_753: CurveWeights = BLS12_381.scaled(10);
+
+/// Estimated as G1 and the scalar field of BN254 until measured, Pallas,
+/// Vesta and the Edwards curves have 4-limb fields too
+const GROUP: CurveWeights = CurveWeights {
+    add: BN254.add,
+    mul: BN254.mul,
+    msm_g1: BN254.msm_g1,
+    add_compressed: BN254.add_compressed,
+    mul_compressed: BN254.mul_compressed,
+    msm_g1_trusted: BN254.msm_g1_trusted,
+    fr_add: BN254.fr_add,
+    fr_sub: BN254.fr_sub,
+    fr_mul: BN254.fr_mul,
+    fr_inverse: BN254.fr_inverse,
+    fr_pow: BN254.fr_pow,
+    fr_batch_inverse: BN254.fr_batch_inverse,
+    sum_g1: BN254.sum_g1,
+    mul_generator: BN254.mul_generator,
+    ..BN254.scaled(0)
+};
+
+/// Pallas weights
+pub const PALLAS: CurveWeights = GROUP;
+
+/// Vesta weights
+pub const VESTA: CurveWeights = GROUP;
+
+/// Estimated as twice the 4-limb groups until measured, the fields of
+/// secp256k1 are represented with 5 limbs and their muls are quadratic in the
+/// limbs
+pub const SECP256K1: CurveWeights = GROUP.scaled(2);
+
+/// Ed-on-BN254 weights
+pub const ED_ON_BN254: CurveWeights = GROUP;
+
+/// Ed-on-BLS12-381 weights
+pub const ED_ON_BLS12_381: CurveWeights = GROUP;
+
+impl Precompile {
+    /// Cost of this precompile
+    pub const fn cost(self) -> Cost {
+        match self {
+            Precompile::Bls12381G1Add => Cost::new(12705000, 0),
+            Precompile::Bls12381G1Mul => Cost::new(823763000, 0),
+            Precompile::Bls12381G1Msm => Cost::new(31053286, 734147714),
+            Precompile::Bls12381G2Add => Cost::new(22902000, 0),
+            Precompile::Bls12381G2Mul => Cost::new(2841994000, 0),
+            Precompile::Bls12381G2Msm => Cost::new(2649125858, 1589709142),
+            Precompile::Bls12381Pairing => Cost::new(2292199715, 2751821285),
+            Precompile::Bls12381MapFpToG1 => Cost::new(273446000, 0),
+            Precompile::Bls12381MapFp2ToG2 => Cost::new(4424460000, 0),
+            Precompile::Bn254Add => Cost::new(7861000, 0),
+            Precompile::Bn254Mul => Cost::new(142435000, 0),
+            Precompile::Bn254Pairing => Cost::new(2274743858, 885164142),
+        }
+    }
+}
+
+/// Cost of hashing a byte of the message to curve, estimated from the
+/// throughput of SHA-256 in `expand_message_xmd` until measured
+pub const HASH_PER_BYTE: Weight = 5 * WEIGHT_PER_NANOS;
+
+/// Cost of the ABI description
+pub const DESCRIBE: Cost = Cost::new(67991000, 0);
+
+/// Cost of copying the input of the chain extension out of the contract
+/// memory, per byte, charged before the input is read. A nanosecond per byte
+/// is above the copy and the SCALE decoding of the `Vec<u8>`
+pub const READ_INPUT: Cost = Cost::new(0, WEIGHT_PER_NANOS);
+
+/// Flat cost of function ids which are no operation and of inputs which don't
+/// decode, charged instead of the weight of the operation, as much as the
+/// cheapest point operation so failing calls are no cheaper than running one
+pub const INVALID_CALL: Weight = BN254.add.base;
+
+impl CurveId {
+    /// Costs of the operations on this curve
+    pub fn weights(self) -> &'static CurveWeights {
+        match self {
+            CurveId::Bls12_377 => &BLS12_377,
+            CurveId::Bls12_381 => &BLS12_381,
+            CurveId::Bn254 => &BN254,
+            CurveId::BW6_761 => &BW6_761,
+            CurveId::CP6_782 => &CP6_782,
+            CurveId::MNT4_298 => &MNT4_298,
+            CurveId::MNT6_298 => &MNT6_298,
+            CurveId::MNT4_753 => &MNT4_753,
+            CurveId::MNT6_753 => &MNT6_753,
+            CurveId::Pallas => &PALLAS,
+            CurveId::Vesta => &VESTA,
+            CurveId::Secp256k1 => &SECP256K1,
+            CurveId::EdOnBn254 => &ED_ON_BN254,
+            CurveId::EdOnBls12_381 => &ED_ON_BLS12_381,
+        }
+    }
+
+    /// Number of items of `op` in `input` on this curve
+    pub fn items(self, op: Operation, input: &[u8]) -> u64 {
+        self.items_serialized(Serialization::Legacy, op, input)
+    }
+
+    /// Number of items of `op` in `input` of `serialization` on this curve
+    pub fn items_serialized(
+        self,
+        serialization: Serialization,
+        op: Operation,
+        input: &[u8],
+    ) -> u64 {
+        let abi = self.describe();
+        let (g1_len, g2_len, scalar_len) = match serialization {
+            Serialization::Legacy => (abi.g1_len, abi.g2_len, abi.scalar_len),
+            Serialization::Canonical => (
+                abi.g1_uncompressed_len,
+                abi.g2_uncompressed_len,
+                abi.scalar_canonical_len,
+            ),
+            Serialization::CanonicalCompressed => (
+                abi.g1_compressed_len,
+                abi.g2_compressed_len,
+                abi.scalar_canonical_len,
+            ),
+        };
+        let item_len = match op {
+            Operation::Pairing | Operation::PairingTrusted => g1_len + g2_len,
+            Operation::PairingCompressed => abi.g1_compressed_len + abi.g2_compressed_len,
+            Operation::MsmG1 | Operation::MsmG1Trusted => g1_len + scalar_len,
+            Operation::MsmG2 => g2_len + scalar_len,
+            Operation::FrBatchInverse => scalar_len,
+            Operation::SumG1 => g1_len,
+            Operation::SumG2 => g2_len,
+            Operation::HashToG1 | Operation::HashToG2 => return input.len() as u64,
+            // the exponent follows the scalar, the pow runs over its bits up
+            // to the highest set one
+            Operation::FrPow => {
+                let exp = &input[(scalar_len as usize).min(input.len())..];
+                return exp
+                    .chunks(8)
+                    .rposition(|limb| limb.iter().any(|byte| *byte != 0))
+                    .map_or(0, |limb| limb as u64 + 1);
+            }
+            _ => return 0,
+        };
+        (input.len() / item_len as usize) as u64
+    }
+}
+
+impl Precompile {
+    /// Number of items in `input_len` bytes
+    pub fn items(self, input_len: usize) -> u64 {
+        let item_len = match self {
+            Precompile::Bls12381G1Msm => 160,
+            Precompile::Bls12381G2Msm => 288,
+            Precompile::Bls12381Pairing => 384,
+            Precompile::Bn254Pairing => 192,
+            _ => return 0,
+        };
+        (input_len / item_len) as u64
+    }
+}
+
+/// Weight of calling `func_id` with `input`
+pub fn weight(func_id: u32, input: &[u8]) -> Result<Weight> {
+    if let Ok(precompile) = Precompile::try_from(func_id) {
+        return Ok(precompile.cost().weight(precompile.items(input.len())));
+    }
+    if func_id == abi::DESCRIBE {
+        return Ok(DESCRIBE.base);
+    }
+
+    let (serialization, func_id) = Serialization::split(func_id)?;
+    let (_, curve, op) = abi::decode(func_id)?;
+    let items = curve.items_serialized(serialization, op, input);
+    // decompression dominates, as in the compressed operations
+    let cost = match (serialization, op) {
+        (Serialization::CanonicalCompressed, Operation::Add) => Operation::AddCompressed,
+        (Serialization::CanonicalCompressed, Operation::Mul) => Operation::MulCompressed,
+        (Serialization::CanonicalCompressed, Operation::Pairing) => Operation::PairingCompressed,
+        _ => op,
+    };
+    Ok(curve.weights().cost(cost).weight(items))
+}
+
+/// Weight of a Groth16 verification with `public_inputs` public inputs
+///
+/// A G1 multi-scalar mul over the inputs and a check of four pairings, the
+/// decoding of the parcel is negligible.
+pub fn verify_weight(curve: CurveId, public_inputs: usize) -> Weight {
+    let weights = curve.weights();
+    weights
+        .msm_g1
+        .weight(public_inputs as u64 + 1)
+        .saturating_add(weights.pairing.weight(4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weight() {
+        for (curve, op) in crate::registry::supported() {
+            for version in abi::Version::ALL {
+                let func_id = version.func_id(op, curve as u32);
+                assert_eq!(weight(func_id, &[]).unwrap(), curve.weights().cost(op).base);
+            }
+        }
+
+        let pairing = CurveId::Bn254.func_id(Operation::Pairing);
+        assert_eq!(
+            weight(pairing, &[0; 2 * (65 + 129)]).unwrap(),
+            BN254.pairing.base + 2 * BN254.pairing.per_item
+        );
+        // canonical points drop the infinity byte, compressed ones `y`
+        assert_eq!(
+            weight(
+                Serialization::Canonical.flag() | pairing,
+                &[0; 2 * (64 + 128)]
+            )
+            .unwrap(),
+            BN254.pairing.weight(2)
+        );
+        assert_eq!(
+            weight(
+                Serialization::CanonicalCompressed.flag() | pairing,
+                &[0; 2 * (32 + 64)]
+            )
+            .unwrap(),
+            BN254.pairing_compressed.weight(2)
+        );
+        assert_eq!(
+            weight(Precompile::Bn254Pairing.func_id(), &[0; 2 * 192]).unwrap(),
+            Precompile::Bn254Pairing.cost().weight(2)
+        );
+        assert!(verify_weight(CurveId::Bn254, 1) > weight(pairing, &[0; 4 * (65 + 129)]).unwrap());
+        assert!(weight(0x0100000f, &[]).is_err());
+
+        // hashes are priced per input byte
+        let hash = CurveId::Bls12_381.func_id(Operation::HashToG1);
+        assert_eq!(
+            weight(hash, &[0; 100]).unwrap(),
+            BLS12_381.hash_to_g1.weight(100)
+        );
+        // and pows per significant limb of the exponent, after the 32-byte scalar
+        let pow = CurveId::Bn254.func_id(Operation::FrPow);
+        let mut input = [0; 64];
+        assert_eq!(weight(pow, &input).unwrap(), BN254.fr_pow.weight(0));
+        input[32] = 1;
+        assert_eq!(weight(pow, &input).unwrap(), BN254.fr_pow.weight(1));
+        input[0] = 0xff;
+        input[32 + 17] = 1;
+        assert_eq!(weight(pow, &input).unwrap(), BN254.fr_pow.weight(3));
+    }
+}
@@ -37,6 +37,20 @@ pub fn etec_negate(input_point: [U256; 4], q: &U256) -> [U256; 4] {
     input_point
 }
 
+// Select `p2` if `choice` is one and `p1` if zero, by masking the limbs
+pub fn etec_select(p1: &[U256; 4], p2: &[U256; 4], choice: u64) -> [U256; 4] {
+    let mask = 0u64.wrapping_sub(choice);
+    let mut output = p1.clone();
+    for (o, p) in output.iter_mut().zip(p2.iter()) {
+        let mut limbs = *o.as_limbs();
+        for (l, r) in limbs.iter_mut().zip(p.as_limbs().iter()) {
+            *l ^= mask & (*l ^ *r);
+        }
+        *o = U256::from_limbs(limbs);
+    }
+    output
+}
+
 // local_a := 0x292FC
 // local_q := 0x30644E72E131A029B85045B68181585D2833E84879B9709143E1F593F0000001
 #[allow(clippy::many_single_char_names)]
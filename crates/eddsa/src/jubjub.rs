@@ -35,3 +35,20 @@ pub fn scalar_mult(x: U256, y: U256, mut value: U256) -> Option<(U256, U256)> {
     }
     etec_to_point(a, Q.clone())
 }
+
+/// Scalar mult for secret scalars, every one of the 256 bits of `value` runs
+/// one double and one add and the sum is selected by masks instead of
+/// branches. The modular arithmetic of zkp-u256 underneath is not audited for
+/// constant time.
+pub fn scalar_mult_secret(x: U256, y: U256, value: U256) -> Option<(U256, U256)> {
+    let p = point_to_etec(x, y, Q.clone());
+
+    let mut a = [U256::zero(), U256::one(), U256::zero(), U256::one()];
+    for i in (0..256).rev() {
+        a = etec_double(&a, &Q, &JUBJUB_A.into());
+        let sum = etec_add(&a, &p, &Q, &JUBJUB_A.into(), &JUBJUB_D.into());
+        let bit = (value.as_limbs()[i / 64] >> (i % 64)) & 1;
+        a = etec_select(&a, &sum, bit);
+    }
+    etec_to_point(a, Q.clone())
+}
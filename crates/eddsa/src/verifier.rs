@@ -83,3 +83,21 @@ fn test_eddsa_verify() {
 
     assert!(verify(message, [pk_x, pk_y], [r_x, r_y], s));
 }
+
+#[test]
+fn test_scalar_mult_secret() {
+    let (x, y) = (GENERATE[0].clone(), GENERATE[1].clone());
+    for value in [
+        U256::from(0),
+        U256::from(1),
+        U256::from(12),
+        hash_to_u256(b"mimc"),
+    ]
+    .iter()
+    {
+        assert_eq!(
+            scalar_mult_secret(x.clone(), y.clone(), value.clone()),
+            scalar_mult(x.clone(), y.clone(), value.clone())
+        );
+    }
+}
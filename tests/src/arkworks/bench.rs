@@ -3,13 +3,16 @@ use ark_bls12_381::Bls12_381;
 use ark_bn254::Bn254;
 use ark_bw6_761::BW6_761;
 use ark_cp6_782::CP6_782;
+use ark_ec::{AffineCurve, PairingEngine};
+use ark_ff::FromBytes;
+use ark_serialize::CanonicalSerialize;
 use arkworks::{
-    groth16::{verify_proof, Groth16Parcel},
-    verify, verify_parcel,
+    groth16::{verify_proof, Groth16Parcel, Proof, VerifyingKey},
+    verify, verify_parcel, verify_parcel_compressed, Error, ScalarBytes,
 };
 use num_bigint::BigUint;
 use num_traits::Num;
-use parity_scale_codec::Encode;
+use parity_scale_codec::{Decode, Encode};
 
 #[test]
 fn test_based_bls12_377_mimc_groth16_parcel() {
@@ -35,9 +38,11 @@ fn test_bls12_377_mimc_groth16() {
     assert!(verify(0x00, vk_ic, vk_encode, proof_encode, public_input).unwrap());
 }
 
+pub(super) const BLS12_381_PARCEL: &str = "088501e60f6032ed7f170ba3481c5d99c0890554c808ad380a7c9aefc9e62eeecf0363ecef5e59ba8eaf6dd0af40e08d7cfd024314f90080a1461a207aa3098308149837bc6322a8f4b499671c507203e798f90a9aa8fafaebb806c8708c3207a93316008501ea7f398187aecf2721441218e54645b17acd35bdf65886255e772a58353ef2e25c92cf7f872bea5afca6081d03e6b115e74befd85720c241ee5ef038621a1cc72e329db9aebec0faf480e83cbd9e4b735650ab0cce2e321d64311bd34bca9e0200910a9094c864d37f890720969430f2682ccc76dc83f9234694590bd334b40dbfa7f28de40e914571d8ae2a88712844f86f0d6dd2f4875120f66304bfcedd55c9a805310155a5345ddc626cbb6fe5ce7026be174a08c798e4d1f1010f97138c30b10a0e23d3cde62852c7b2aabcd7603f87fc54cb0c2fcfa48a1a6e07ea917eec248284a1409dd21ae7bd64b862e7df91fd0af1f36857260a843ea18cd393172b91196ca17bc5f37efb126d585abfcf23b8438d72f90365c382b661256d7caa361d07000c06c5cc02155140b97283cc024daf57023a781a9115bb6e17350264f8b52784a6008921402af8c05dd39f4b789bd50435d2c5aefeb6dc9817af41f45779e3a2d80ab98ef923723b938566747c10a4a169ca5097c5d5073449c3d2e65e16291850a5721416e102df69729ffab67cf948ca51c4ac4c2b54c521552fd4a5a5462d6d3b77ba9932aba0fda8ea8da76e5b0b9166ba600f9c5cc5e442b64ed560c126f8b88e2761c0c6a17a4dcbc1a132a975683ed0b6018ac45d7f4942ad7831790e00408697568bc15871da494a2fe12199531130a8a5b96f07c69020dc01bf9f3ed043a6e1bd08f8d7826c7d5c0fc5c771195ab91bf6eeaa33cbd89cd7dce7930b383c2453d0cca5c227e1b97b1ae253e40cadf4468d6d86f0bf69a0b0bb55774219005b6d86316ea7c1dc4df3e3396a9f78aadf416d5af1619883f5dbbf0a39c6743b194e5adedda54d7cb3ae33e556602e19dd43a31bca7bdb15ad5245dc1ad0affb97edc8f35bb00d1c5b417a132d5be9b72a39870193b6a142198a888c0fe3760fdf1467c7208394270f2aff044c5c002d6cf189a28e34851dc434933e73e07482dad6c7ab931bfbdf29f5d51a6a866d028b91cd89abc9728cf2f8e139f612b5a248ca0758365d1d83c6bc299152b15a89b136e804ae71174d4f35970be570fd14000d06fcafa9753aee681f006d7bf6fc885dcc1a70c33bbfe92bb581ac51e1325b782b1128b1586767cb2ea27555e243abd213634b87418421a65002e06fd9347b23205761d7e35a2a83ffcd35b16b6bf23982700597f2ed9706ae5e039542e8eabd1800443cc8d6ffec17c44207dd41e8ab2f2f2c663f5ed9ab724321de43cbe2ea81908c13643c42a41be1adbc5076e4927b13046d3cdaf5ef775ee48d842599508178276cd41d30e289cbd1d2bb9f92f59b520138fef3e9529139f3961f40f6ea92113ed1701b0eb62a8b21b0852f5ae0d23b4d8cf7a02a69b04abbf0b9213cc1cd16b0afed74a09f1d965f72e0faaad60215c2788b9672a8f53fb90d44b47fb16e4f97f910226d8ad46ddc1dfa6eb4f709a3c95ccfa7f4cd1b9ea5f3cf3d10c5dd0200de094abc5e25fac259efcfd79a97401359069cde3b48eaa9d3a4d848959eb269d788a415d9c22fb418459474f1f0fc16f4572ace2876cf1170d400d1d12d588f816023602a077b8fa66293ac19c951b6c572fad4481fa576e362d9f3a0ffa0120004801d6c7850edbac8a5281ab93d2ed245d47b64f20c21950926d595624b488c291c";
+
 #[test]
 fn test_based_bls12_381_mimc_groth16_parcel() {
-    let parcel = "088501e60f6032ed7f170ba3481c5d99c0890554c808ad380a7c9aefc9e62eeecf0363ecef5e59ba8eaf6dd0af40e08d7cfd024314f90080a1461a207aa3098308149837bc6322a8f4b499671c507203e798f90a9aa8fafaebb806c8708c3207a93316008501ea7f398187aecf2721441218e54645b17acd35bdf65886255e772a58353ef2e25c92cf7f872bea5afca6081d03e6b115e74befd85720c241ee5ef038621a1cc72e329db9aebec0faf480e83cbd9e4b735650ab0cce2e321d64311bd34bca9e0200910a9094c864d37f890720969430f2682ccc76dc83f9234694590bd334b40dbfa7f28de40e914571d8ae2a88712844f86f0d6dd2f4875120f66304bfcedd55c9a805310155a5345ddc626cbb6fe5ce7026be174a08c798e4d1f1010f97138c30b10a0e23d3cde62852c7b2aabcd7603f87fc54cb0c2fcfa48a1a6e07ea917eec248284a1409dd21ae7bd64b862e7df91fd0af1f36857260a843ea18cd393172b91196ca17bc5f37efb126d585abfcf23b8438d72f90365c382b661256d7caa361d07000c06c5cc02155140b97283cc024daf57023a781a9115bb6e17350264f8b52784a6008921402af8c05dd39f4b789bd50435d2c5aefeb6dc9817af41f45779e3a2d80ab98ef923723b938566747c10a4a169ca5097c5d5073449c3d2e65e16291850a5721416e102df69729ffab67cf948ca51c4ac4c2b54c521552fd4a5a5462d6d3b77ba9932aba0fda8ea8da76e5b0b9166ba600f9c5cc5e442b64ed560c126f8b88e2761c0c6a17a4dcbc1a132a975683ed0b6018ac45d7f4942ad7831790e00408697568bc15871da494a2fe12199531130a8a5b96f07c69020dc01bf9f3ed043a6e1bd08f8d7826c7d5c0fc5c771195ab91bf6eeaa33cbd89cd7dce7930b383c2453d0cca5c227e1b97b1ae253e40cadf4468d6d86f0bf69a0b0bb55774219005b6d86316ea7c1dc4df3e3396a9f78aadf416d5af1619883f5dbbf0a39c6743b194e5adedda54d7cb3ae33e556602e19dd43a31bca7bdb15ad5245dc1ad0affb97edc8f35bb00d1c5b417a132d5be9b72a39870193b6a142198a888c0fe3760fdf1467c7208394270f2aff044c5c002d6cf189a28e34851dc434933e73e07482dad6c7ab931bfbdf29f5d51a6a866d028b91cd89abc9728cf2f8e139f612b5a248ca0758365d1d83c6bc299152b15a89b136e804ae71174d4f35970be570fd14000d06fcafa9753aee681f006d7bf6fc885dcc1a70c33bbfe92bb581ac51e1325b782b1128b1586767cb2ea27555e243abd213634b87418421a65002e06fd9347b23205761d7e35a2a83ffcd35b16b6bf23982700597f2ed9706ae5e039542e8eabd1800443cc8d6ffec17c44207dd41e8ab2f2f2c663f5ed9ab724321de43cbe2ea81908c13643c42a41be1adbc5076e4927b13046d3cdaf5ef775ee48d842599508178276cd41d30e289cbd1d2bb9f92f59b520138fef3e9529139f3961f40f6ea92113ed1701b0eb62a8b21b0852f5ae0d23b4d8cf7a02a69b04abbf0b9213cc1cd16b0afed74a09f1d965f72e0faaad60215c2788b9672a8f53fb90d44b47fb16e4f97f910226d8ad46ddc1dfa6eb4f709a3c95ccfa7f4cd1b9ea5f3cf3d10c5dd0200de094abc5e25fac259efcfd79a97401359069cde3b48eaa9d3a4d848959eb269d788a415d9c22fb418459474f1f0fc16f4572ace2876cf1170d400d1d12d588f816023602a077b8fa66293ac19c951b6c572fad4481fa576e362d9f3a0ffa0120004801d6c7850edbac8a5281ab93d2ed245d47b64f20c21950926d595624b488c291c";
+    let parcel = BLS12_381_PARCEL;
     assert!(
         verify_parcel(0x10, hex::decode(parcel).expect("decode hex failed"))
             .expect("verify proof failed")
@@ -59,9 +64,11 @@ fn test_based_bls12_381_mimc_groth16() {
     assert!(verify(0x10, vk_ic, vk_encode, proof_encode, public_input).unwrap());
 }
 
+pub(super) const BN254_PARCEL: &str = "08050103c2e806d904d8789f0caf1276fc4897bbf2bbb2e7243df2e4bf941ce616790479b410872e3c6f73f481d3e37b861fc1521d4a70457ac41efbe874fe0bf6572f0005014d609323ed564943ba7dfd3b362cc85abf7691dd9957c613fc0d282f258f4d17cd4ddfa713c0a6d4d0dcbfc245847bd6a96fa8603a0c0d55389279cdf2967f06001107f00c5d68983d7c029a66ae498a1cc6a2423bf67760bbf922ea7a937a87705e158db79bbff4eb9516d5342ce8cd8d09f68fa094434edceebbb43383fc415d9b235c64a2bc82ea6fa740441af956bc764eefb6be4067948d2d7afc08d7dc114a2e1c0c46208e6590bed192c336b7d89f55c2eca918e6f4eb88e1c729a10612690900f70bfb6ec84456af219bcd1d682133db3db80b0ae54cf31879e74a0ae547ec0ef92f56757f4a7976c50dc3d538cb1b4ed1736d4e8e8734d5cbf85b2ac8a93d0b654ff74df7e792b9cf0fd9d1b07b6ce4d0675f57bc7e9740e64b56c9f866b309eb428ffdf69b419fc53b9d76f1de62559aedc6db7e8b6ac2f3dd0cc963ca041300cc165291944a7b617bad2e6f1560f89f4e7eb42f154333b730e2f2da7828911d7cd0a28314d647bae049c72d4e0b4f31c1f09177fcab13014e3060a70024cb210094b31d9ebcca3957fff3d94960b0eba2197fabaaa68776e767e8785f63b4541d456d755240d72dae2ee0452c0cd705d7312b9a1d17cff916e87c763d459a550438b765cd8877bf753869e1852a7c0d9ac4520abb2b935e2f3ec12423d9edc5289eba05bed12e9fe16017c86791d682d4ae86d94eeab1d7888845c0fb0af85805000d04cf3bb657065cd0878eabc53790c5fa3680cad24db586bab799e3ae790217c61ba5050a0024f8761028a11dd22dfc030227b0a6c353ef5e7e1afad863c13a3304000227ad22191396e6ffe7aff14de91d7801e943f836de399b5fd5c8312086440c036648b695ffa27790f2f2a63fcf4c0b130e6b88ac786e3351fc6f13452d18034bee420c244cf7c63db4761f2feb7c14e409c8c550a68940cc0387e3812ccb10c4eb591ff3a67a87e794e2e76e25126b823ea9f38f95730951f13784e6e10f26007a922013b86567d6d48ff65baf8cdd0558c52db98f27b6c216df96f8aa1d4d2df7b1ead51d93e93ef5abdff83acaabd54736546f6995083127be1ae281ca5200000480829d3d572251103bac02b39e397c657c0b1f372bfc4d1b74f6c23de4993b4824";
+
 #[test]
 fn test_based_bn254_mimc_groth16_parcel() {
-    let parcel = "08050103c2e806d904d8789f0caf1276fc4897bbf2bbb2e7243df2e4bf941ce616790479b410872e3c6f73f481d3e37b861fc1521d4a70457ac41efbe874fe0bf6572f0005014d609323ed564943ba7dfd3b362cc85abf7691dd9957c613fc0d282f258f4d17cd4ddfa713c0a6d4d0dcbfc245847bd6a96fa8603a0c0d55389279cdf2967f06001107f00c5d68983d7c029a66ae498a1cc6a2423bf67760bbf922ea7a937a87705e158db79bbff4eb9516d5342ce8cd8d09f68fa094434edceebbb43383fc415d9b235c64a2bc82ea6fa740441af956bc764eefb6be4067948d2d7afc08d7dc114a2e1c0c46208e6590bed192c336b7d89f55c2eca918e6f4eb88e1c729a10612690900f70bfb6ec84456af219bcd1d682133db3db80b0ae54cf31879e74a0ae547ec0ef92f56757f4a7976c50dc3d538cb1b4ed1736d4e8e8734d5cbf85b2ac8a93d0b654ff74df7e792b9cf0fd9d1b07b6ce4d0675f57bc7e9740e64b56c9f866b309eb428ffdf69b419fc53b9d76f1de62559aedc6db7e8b6ac2f3dd0cc963ca041300cc165291944a7b617bad2e6f1560f89f4e7eb42f154333b730e2f2da7828911d7cd0a28314d647bae049c72d4e0b4f31c1f09177fcab13014e3060a70024cb210094b31d9ebcca3957fff3d94960b0eba2197fabaaa68776e767e8785f63b4541d456d755240d72dae2ee0452c0cd705d7312b9a1d17cff916e87c763d459a550438b765cd8877bf753869e1852a7c0d9ac4520abb2b935e2f3ec12423d9edc5289eba05bed12e9fe16017c86791d682d4ae86d94eeab1d7888845c0fb0af85805000d04cf3bb657065cd0878eabc53790c5fa3680cad24db586bab799e3ae790217c61ba5050a0024f8761028a11dd22dfc030227b0a6c353ef5e7e1afad863c13a3304000227ad22191396e6ffe7aff14de91d7801e943f836de399b5fd5c8312086440c036648b695ffa27790f2f2a63fcf4c0b130e6b88ac786e3351fc6f13452d18034bee420c244cf7c63db4761f2feb7c14e409c8c550a68940cc0387e3812ccb10c4eb591ff3a67a87e794e2e76e25126b823ea9f38f95730951f13784e6e10f26007a922013b86567d6d48ff65baf8cdd0558c52db98f27b6c216df96f8aa1d4d2df7b1ead51d93e93ef5abdff83acaabd54736546f6995083127be1ae281ca5200000480829d3d572251103bac02b39e397c657c0b1f372bfc4d1b74f6c23de4993b4824";
+    let parcel = BN254_PARCEL;
     assert!(
         verify_parcel(0x20, hex::decode(parcel).expect("decode hex failed"))
             .expect("verify proof failed")
@@ -112,3 +119,122 @@ fn test_based_bw6_761_mimc_groth16() {
 //     let public_input:Vec<Vec<u8>> = hex::decode("28933fc1b649ded455bd7739026be2bf6f7e1c0acde6dee164724218bbf1391e318017e5a6ab8248bda0bdd1f40b8301").unwrap();
 //     assert!(verify_proof::<CP6_782>(vk_ic, vk_encode, proof_encode, public_input).expect("verify proof fail"));
 // }
+
+/// Re-encode the points of an uncompressed parcel compressed
+fn compress_parcel<E: PairingEngine>(parcel: &str) -> Vec<u8> {
+    fn compress<G: AffineCurve>(input: &mut &[u8], output: &mut Vec<u8>) {
+        G::read(&mut *input).unwrap().serialize(output).unwrap();
+    }
+
+    let parcel = Groth16Parcel::decode(&mut &*hex::decode(parcel).unwrap()).unwrap();
+    let vk_gamma_abc = parcel
+        .vk_gamma_abc
+        .iter()
+        .map(|point| {
+            let mut output = Vec::new();
+            compress::<E::G1Affine>(&mut point.as_slice(), &mut output);
+            output
+        })
+        .collect();
+
+    let mut input = parcel.vk.as_slice();
+    let mut vk = Vec::new();
+    compress::<E::G2Affine>(&mut input, &mut vk);
+    compress::<E::G2Affine>(&mut input, &mut vk);
+    compress::<E::G1Affine>(&mut input, &mut vk);
+    compress::<E::G2Affine>(&mut input, &mut vk);
+
+    let mut input = parcel.proof.as_slice();
+    let mut proof = Vec::new();
+    compress::<E::G1Affine>(&mut input, &mut proof);
+    compress::<E::G2Affine>(&mut input, &mut proof);
+    compress::<E::G1Affine>(&mut input, &mut proof);
+
+    Groth16Parcel {
+        vk_gamma_abc,
+        vk,
+        proof,
+        public_inputs: parcel.public_inputs,
+    }
+    .encode()
+}
+
+#[test]
+fn test_bls12_381_mimc_groth16_parcel_compressed() {
+    let parcel = compress_parcel::<Bls12_381>(BLS12_381_PARCEL);
+    assert!(parcel.len() < BLS12_381_PARCEL.len() / 2);
+    assert!(verify_parcel_compressed(0x10, parcel.clone()).unwrap());
+    assert!(verify_parcel(0x10, parcel).is_err());
+}
+
+#[test]
+fn test_bn254_mimc_groth16_parcel_compressed() {
+    let parcel = compress_parcel::<Bn254>(BN254_PARCEL);
+    assert!(parcel.len() < BN254_PARCEL.len() / 2);
+    assert!(verify_parcel_compressed(0x20, parcel).unwrap());
+}
+
+#[test]
+fn test_bn254_mimc_groth16_typed() {
+    let parcel = Groth16Parcel::decode(&mut &*hex::decode(BN254_PARCEL).unwrap()).unwrap();
+    let vk = VerifyingKey::<Bn254>::from_raw(parcel.vk_gamma_abc, parcel.vk).unwrap();
+    let proof = Proof::<Bn254>::from_raw(parcel.proof).unwrap();
+    let public_inputs = parcel
+        .public_inputs
+        .into_iter()
+        .map(|input| ScalarBytes::new(input).unwrap())
+        .collect::<Vec<_>>();
+    assert!(vk.verify(&proof, &public_inputs).unwrap());
+    assert!(vk.verify_trusted(&proof, &public_inputs).unwrap());
+
+    assert_eq!(VerifyingKey::decode(&mut &*vk.encode()).unwrap(), vk);
+    assert_eq!(Proof::decode(&mut &*proof.encode()).unwrap(), proof);
+    // the points of another curve don't fit
+    assert!(Proof::<Bls12_381>::decode(&mut &*proof.encode()).is_err());
+}
+
+#[test]
+fn test_groth16_errors() {
+    let parcel = Groth16Parcel::decode(&mut &*hex::decode(BN254_PARCEL).unwrap()).unwrap();
+    // the points are read unchecked and validated once by the curve operations
+    let mut proof = parcel.proof.clone();
+    proof[0] ^= 1;
+    assert_eq!(
+        verify(
+            0x20,
+            parcel.vk_gamma_abc.clone(),
+            parcel.vk.clone(),
+            proof.clone(),
+            parcel.public_inputs.clone()
+        ),
+        Err(Error::PointNotOnCurve)
+    );
+    assert!(Proof::<Bn254>::from_raw(proof.clone()).is_ok());
+    assert_eq!(
+        Proof::<Bn254>::from_raw_validated(proof),
+        Err(Error::PointNotOnCurve)
+    );
+    assert_eq!(
+        verify(
+            0x20,
+            parcel.vk_gamma_abc.clone(),
+            parcel.vk.clone(),
+            parcel.proof.clone(),
+            vec![]
+        ),
+        Err(Error::PublicInputCountMismatch {
+            expected: 1,
+            got: 0
+        })
+    );
+    assert_eq!(
+        verify(
+            0xf0,
+            parcel.vk_gamma_abc,
+            parcel.vk,
+            parcel.proof,
+            parcel.public_inputs
+        ),
+        Err(Error::UnsupportedCurve(0xf0))
+    );
+}
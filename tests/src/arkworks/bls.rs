@@ -0,0 +1,222 @@
+use ark_std::test_rng;
+use arkworks::{
+    bls::{self, threshold, MinPk, MinSig, Scheme, Variant},
+    curve::{Bls12_377, Bls12_381},
+    CurveBasicOperations, Error, ScalarBytes,
+};
+
+fn bls_test<C: CurveBasicOperations, V: Variant<C>>() {
+    let rng = &mut test_rng();
+    let sks = (0..3).map(|_| bls::keygen::<C, _>(rng)).collect::<Vec<_>>();
+    let pks = sks
+        .iter()
+        .map(|sk| bls::sk_to_pk::<C, V>(sk).unwrap())
+        .collect::<Vec<_>>();
+    let msgs: [&[u8]; 3] = [b"alice", b"bob", b"carol"];
+
+    for scheme in [
+        Scheme::Basic,
+        Scheme::MessageAugmentation,
+        Scheme::ProofOfPossession,
+    ]
+    .iter()
+    {
+        let sig = bls::sign::<C, V>(*scheme, &sks[0], msgs[0]).unwrap();
+        assert!(bls::verify::<C, V>(*scheme, &pks[0], msgs[0], &sig).unwrap());
+        assert!(!bls::verify::<C, V>(*scheme, &pks[0], msgs[1], &sig).unwrap());
+        assert!(!bls::verify::<C, V>(*scheme, &pks[1], msgs[0], &sig).unwrap());
+
+        let sigs = sks
+            .iter()
+            .zip(msgs.iter())
+            .map(|(sk, msg)| bls::sign::<C, V>(*scheme, sk, msg).unwrap())
+            .collect::<Vec<_>>();
+        let agg = bls::aggregate::<C, V>(&sigs).unwrap();
+        assert!(bls::aggregate_verify::<C, V>(*scheme, &pks, &msgs, &agg).unwrap());
+        assert!(!bls::aggregate_verify::<C, V>(*scheme, &pks[1..], &msgs[1..], &agg).unwrap());
+        assert!(!bls::aggregate_verify::<C, V>(*scheme, &pks, &msgs[1..], &agg).unwrap());
+    }
+
+    // same message, distinct only required in basic
+    let sigs = sks
+        .iter()
+        .map(|sk| bls::sign::<C, V>(Scheme::ProofOfPossession, sk, msgs[0]).unwrap())
+        .collect::<Vec<_>>();
+    let agg = bls::aggregate::<C, V>(&sigs).unwrap();
+    assert!(bls::fast_aggregate_verify::<C, V>(&pks, msgs[0], &agg).unwrap());
+    assert!(!bls::fast_aggregate_verify::<C, V>(&pks[1..], msgs[0], &agg).unwrap());
+    assert!(!bls::fast_aggregate_verify::<C, V>(&[], msgs[0], &agg).unwrap());
+    let same = [msgs[0]; 3];
+    assert!(bls::aggregate_verify::<C, V>(Scheme::ProofOfPossession, &pks, &same, &agg).unwrap());
+    let sigs = sks
+        .iter()
+        .map(|sk| bls::sign::<C, V>(Scheme::Basic, sk, msgs[0]).unwrap())
+        .collect::<Vec<_>>();
+    let agg = bls::aggregate::<C, V>(&sigs).unwrap();
+    assert!(!bls::aggregate_verify::<C, V>(Scheme::Basic, &pks, &same, &agg).unwrap());
+
+    // proofs of possession
+    let proof = bls::pop_prove::<C, V>(&sks[0]).unwrap();
+    assert!(bls::pop_verify::<C, V>(&pks[0], &proof).unwrap());
+    assert!(!bls::pop_verify::<C, V>(&pks[1], &proof).unwrap());
+    let sig = bls::sign::<C, V>(Scheme::ProofOfPossession, &sks[0], pks[0].as_ref()).unwrap();
+    assert!(!bls::pop_verify::<C, V>(&pks[0], &sig).unwrap());
+
+    // the identity public key is rejected
+    let zero =
+        bls::aggregate_public_keys::<C, V>(&[pks[0].clone(), V::neg_public_key(&pks[0]).unwrap()])
+            .unwrap();
+    let sig = bls::aggregate::<C, V>(&[]);
+    assert!(sig.is_err());
+    let sig = bls::sign::<C, V>(Scheme::Basic, &sks[0], msgs[0]).unwrap();
+    assert!(!bls::verify::<C, V>(Scheme::Basic, &zero, msgs[0], &sig).unwrap());
+}
+
+#[test]
+fn test_bls12_381_bls() {
+    bls_test::<Bls12_381, MinPk<Bls12_381>>();
+    bls_test::<Bls12_381, MinSig<Bls12_381>>();
+}
+
+/// Known answers of the `POP` ciphersuite of the draft with `MinPk` on
+/// BLS12-381, from the Ethereum consensus tests, keys and signatures in the
+/// compressed encoding of the draft
+#[test]
+fn test_bls12_381_known_answers() {
+    type V = MinPk<Bls12_381>;
+
+    let sk = |hex: &str| {
+        let mut bytes = hex::decode(hex).unwrap();
+        bytes.reverse();
+        ScalarBytes::<Bls12_381>::new(bytes).unwrap()
+    };
+    let octets = |pk| hex::encode(V::public_key_octets(pk).unwrap());
+
+    for &(secret, public) in [
+        (
+            "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3",
+            "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a",
+        ),
+        (
+            "47b8192d77bf871b62e87859d653922725724a5c031afeabc60bcef5ff665138",
+            "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81",
+        ),
+        (
+            "328388aff0d4a5b7dc9205abd374e7e98f3cd9f3418edb4eafda5fb16473d216",
+            "b53d21a4cfd562c469cc81514d4ce5a6b577d8403d32a394dc265dd190b47fa9f829fdd7963afdf972e5e77854051f6f",
+        ),
+    ]
+    .iter()
+    {
+        assert_eq!(
+            octets(&bls::sk_to_pk::<Bls12_381, V>(&sk(secret)).unwrap()),
+            public
+        );
+    }
+
+    let sig = bls::sign::<Bls12_381, V>(
+        Scheme::ProofOfPossession,
+        &sk("263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3"),
+        &[0x56; 32],
+    )
+    .unwrap();
+    assert_eq!(
+        hex::encode(bls::point_to_octets(&sig.to_affine().unwrap()).unwrap()),
+        "882730e5d03f6b42c3abc26d3372625034e1d871b65a8a6b900a56dae22da98abbe1b68f85e49fe7652a55ec3d0591c20767677e33e5cbb1207315c41a9ac03be39c2e7668edc043d6cb1d9fd93033caa8a1c5b0e84bedaeb6c64972503a43eb"
+    );
+
+    // the identity is the compression and infinity bits
+    let zero = bls::aggregate_public_keys::<Bls12_381, V>(&[
+        V::generator(),
+        V::neg_public_key(&V::generator()).unwrap(),
+    ])
+    .unwrap();
+    assert_eq!(octets(&zero), format!("c0{}", "00".repeat(47)));
+}
+
+#[test]
+fn test_bls12_377_bls() {
+    bls_test::<Bls12_377, MinPk<Bls12_377>>();
+    bls_test::<Bls12_377, MinSig<Bls12_377>>();
+}
+
+fn threshold_test<C: CurveBasicOperations, V: Variant<C>>(expected: &str) {
+    // f(x) = 7 + 11x + 13x^2, 3-of-5
+    let coefficients = [7u64, 11, 13]
+        .iter()
+        .map(|c| ScalarBytes::<C>::from_scalar(&(*c).into()))
+        .collect::<Vec<_>>();
+    let shares = threshold::shares(&coefficients, 5).unwrap();
+    let group_sk = &coefficients[0];
+    let group_pk = bls::sk_to_pk::<C, V>(group_sk).unwrap();
+    let msg = b"round 42: 1234.56";
+    let scheme = Scheme::ProofOfPossession;
+
+    let pk_shares = shares
+        .iter()
+        .map(|(i, sk)| (*i, bls::sk_to_pk::<C, V>(sk).unwrap()))
+        .collect::<Vec<_>>();
+    let partials = shares
+        .iter()
+        .map(|(i, sk)| (*i, bls::sign::<C, V>(scheme, sk, msg).unwrap()))
+        .collect::<Vec<_>>();
+    for ((_, pk), (_, partial)) in pk_shares.iter().zip(partials.iter()) {
+        assert!(threshold::verify_share::<C, V>(scheme, pk, msg, partial).unwrap());
+    }
+
+    // any 3 shares give the signature of the group secret key
+    let sig = bls::sign::<C, V>(scheme, group_sk, msg).unwrap();
+    assert_eq!(hex::encode(sig.as_ref()), expected);
+    for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]].iter() {
+        let picked = subset
+            .iter()
+            .map(|i| partials[*i].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(threshold::combine::<C, V>(&picked).unwrap(), sig);
+        assert!(threshold::verify_combined::<C, V>(scheme, &group_pk, msg, &picked).unwrap());
+
+        let picked = subset
+            .iter()
+            .map(|i| pk_shares[*i].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            threshold::combine_public_keys::<C, V>(&picked).unwrap(),
+            group_pk
+        );
+    }
+
+    // 2 shares are not enough, a bad share breaks the combination
+    assert!(!threshold::verify_combined::<C, V>(scheme, &group_pk, msg, &partials[..2]).unwrap());
+    let mut bad = partials[..3].to_vec();
+    bad[1].1 = partials[3].1.clone();
+    assert!(!threshold::verify_share::<C, V>(scheme, &pk_shares[1].1, msg, &bad[1].1).unwrap());
+    assert!(!threshold::verify_combined::<C, V>(scheme, &group_pk, msg, &bad).unwrap());
+    bad[1].0 = bad[0].0;
+    assert_eq!(
+        threshold::combine::<C, V>(&bad),
+        Err(Error::InvalidShareIndex(1))
+    );
+
+    // random dealing
+    let (group_sk, shares) = threshold::deal::<C, _>(&mut test_rng(), 2, 3).unwrap();
+    let partials = shares[1..]
+        .iter()
+        .map(|(i, sk)| (*i, bls::sign::<C, V>(scheme, sk, msg).unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        threshold::combine::<C, V>(&partials).unwrap(),
+        bls::sign::<C, V>(scheme, &group_sk, msg).unwrap()
+    );
+}
+
+#[test]
+fn test_bls12_381_threshold() {
+    threshold_test::<Bls12_381, MinPk<Bls12_381>>("a9621b438b28bdeb39b6bc21ec6c3e5eeef249c8851f050657735dd636816c6863e34716327ce83d62400b52b3d25002e749415ba0ec4855f655e847332d98674889ce906ada79ffc2a47fc7fbb11c5bae37a56d9c24247699e1fbd6a2b8160b28927eb406cb3bcd6b88382d32cf4eb6e4e764dc135fb4111771b374546a5f68dd618e044a02b1b294a098df29fab20debde39dc2629d8b2761586ba9d0656fefe447195447b665d638a4cc153f2760bc9c3bd6bad4629a222622419fb73820a00");
+    threshold_test::<Bls12_381, MinSig<Bls12_381>>("061679565e043994ea192877382356825e9cb27db8a12aafafdfff3d69be79f753676d80ef63378a9432219b183dab08af5962b80e5888e3c49e1da2a3fc5056d77f3dbf1ce2bae3d882b2380dee6da76d47394d75d168dfc76b3402f569df0900");
+}
+
+#[test]
+fn test_bls12_377_threshold() {
+    threshold_test::<Bls12_377, MinPk<Bls12_377>>("826050d67c6fdb11b97c8b1283e1e3598339fd9703a33025cde4ff4b0f1b2dee429f308c762b6fd9018c09556580740060f2b6665fa017041405e9d230c114bf5f665004ec55566f5af1d0763bbea9fd95cc89edb93f066579e1c64276989800c8fdc2f7b9ec76075c6140cf0407d03a9172cf17a206735327ee1b5780afe4ec04fbe4517a78ac95dc096707a54f05019cfe4386a8bae9e4b52b93d3d2924f83d235519c662a8659ed27bad91e07ad7429847707e1a99689ced79061236cb10000");
+    threshold_test::<Bls12_377, MinSig<Bls12_377>>("810869a5aad9569fe463a062b0f8405c194e7654d005993ae3ccd27e71a2190ec7e9d1cf57a7318041813c8d158a2d00d6cd90dcc57fe449aee92832b39b39365394d915c86c6db08a92b7d4505b443229e3988d5d5121d38722f834830f5e0000");
+}
@@ -1,19 +1,21 @@
 mod bench;
+mod bls;
 mod ops;
 mod verify;
+mod wasm;
+mod weights;
 
 use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
-use ark_ff::{FromBytes, One, PrimeField, ToBytes, Zero};
+use ark_ff::{FpParameters, FromBytes, One, PrimeField, ToBytes, Zero};
 use ark_serialize::SerializationError;
 use ark_std::{
-    io::{Error, ErrorKind},
     ops::{MulAssign, Neg},
     str::FromStr,
     test_rng,
     vec::Vec,
     UniformRand,
 };
-use arkworks::CurveBasicOperations;
+use arkworks::{ops::Scalar, CurveBasicOperations, Error, GroupOperations};
 use rand::Rng;
 
 type G1Affine<T> = <T as PairingEngine>::G1Affine;
@@ -21,6 +23,7 @@ type G2Affine<T> = <T as PairingEngine>::G2Affine;
 type G1Projective<T> = <T as PairingEngine>::G1Projective;
 type G2Projective<T> = <T as PairingEngine>::G2Projective;
 type Fr<T> = <T as PairingEngine>::Fr;
+type Affine<T> = <T as GroupOperations>::Affine;
 
 fn all_curve_three_operations_test<T>()
 where
@@ -178,3 +181,568 @@ where
         println!("test pairings e(a1*b1)*e(a2*b2)*e(-a1*b1)*e(-a2*b2) success!");
     }
 }
+
+fn all_group_operations_test<T>()
+where
+    T: GroupOperations,
+{
+    use ark_serialize::CanonicalSerialize;
+    use parity_scale_codec::{Compact, Encode};
+
+    let write = |points: &[Affine<T>], scalars: &[Scalar<T>]| {
+        let mut bytes = Vec::new();
+        points.iter().for_each(|p| p.write(&mut bytes).unwrap());
+        scalars.iter().for_each(|s| s.write(&mut bytes).unwrap());
+        bytes
+    };
+    let mut rng = test_rng();
+    let g = <Affine<T> as AffineCurve>::prime_subgroup_generator();
+    let a = g
+        .mul(<Scalar<T> as UniformRand>::rand(&mut rng))
+        .into_affine();
+    let s = <Scalar<T> as UniformRand>::rand(&mut rng);
+    let zero = <Affine<T> as Zero>::zero();
+
+    // zero-points additions
+    assert_eq!(
+        T::add(&write(&[zero, zero], &[])).unwrap(),
+        write(&[zero], &[])
+    );
+
+    // g + g = g * 2
+    let double = g.into_projective().double().into_affine();
+    assert_eq!(T::add(&write(&[g, g], &[])).unwrap(), write(&[double], &[]));
+    assert_eq!(
+        T::mul(&write(&[g], &[<Scalar<T> as From<u64>>::from(2)])).unwrap(),
+        write(&[double], &[])
+    );
+    assert_eq!(
+        T::add(&write(&[a, g], &[])).unwrap(),
+        write(
+            &[(a.into_projective() + g.into_projective()).into_affine()],
+            &[]
+        )
+    );
+    assert_eq!(
+        T::mul(&write(&[a], &[s])).unwrap(),
+        write(&[a.mul(s).into_affine()], &[])
+    );
+
+    // generator table
+    let minus_one = -<Scalar<T> as One>::one();
+    for k in [s, <Scalar<T> as Zero>::zero(), minus_one] {
+        assert_eq!(
+            T::mul_generator(&write(&[], &[k])).unwrap(),
+            T::mul(&write(&[g], &[k])).unwrap()
+        );
+    }
+    assert_eq!(
+        T::mul_generator(&[0; 1]),
+        Err(Error::InvalidLength {
+            expected: T::SCALAR_LEN as u32,
+            got: 1,
+        })
+    );
+
+    // msm
+    let mut input = Compact(2u32).encode();
+    input.extend(write(&[a], &[s]));
+    input.extend(write(&[g], &[s]));
+    let expected = write(&[(a.mul(s) + g.mul(s)).into_affine()], &[]);
+    assert_eq!(T::msm_g1(&input).unwrap(), expected);
+    assert_eq!(T::msm_g1_trusted(&input).unwrap(), expected);
+
+    // sum
+    assert_eq!(
+        T::sum_g1(&write(&[a, g, g], &[])).unwrap(),
+        write(
+            &[(a.into_projective() + double.into_projective()).into_affine()],
+            &[]
+        )
+    );
+
+    // compressed
+    let mut input = Vec::new();
+    a.serialize(&mut input).unwrap();
+    g.serialize(&mut input).unwrap();
+    let mut expected = Vec::new();
+    (a.into_projective() + g.into_projective())
+        .into_affine()
+        .serialize(&mut expected)
+        .unwrap();
+    assert_eq!(T::add_compressed(&input).unwrap(), expected);
+
+    // scalar field
+    assert_eq!(
+        T::fr_mul(&write(&[], &[s, s])).unwrap(),
+        write(&[], &[s * s])
+    );
+
+    // point not on curve, flip the lowest bit of y
+    let mut input = write(&[g, g], &[]);
+    input[T::G1_LEN / 2] ^= 1;
+    assert_eq!(T::add(&input), Err(Error::PointNotOnCurve));
+    assert_eq!(
+        T::add(&[0; 1]),
+        Err(Error::InvalidLength {
+            expected: T::G1_LEN as u32 * 2,
+            got: 1,
+        })
+    );
+
+    // there is no pairing
+    assert_eq!(
+        arkworks::pairing(T::CURVE_ID, &write(&[g], &[])),
+        Err(Error::UnsupportedOperation)
+    );
+}
+
+/// The ladder of `mul_secret` agrees with `mul` on random scalars, on the
+/// scalars passing the identity branches and on the identity
+fn all_mul_secret_test<T>()
+where
+    T: GroupOperations,
+{
+    let write = |point: Affine<T>, scalar: Scalar<T>| {
+        let mut bytes = Vec::new();
+        point.write(&mut bytes).unwrap();
+        scalar.write(&mut bytes).unwrap();
+        bytes
+    };
+    let mut rng = test_rng();
+    let g = <Affine<T> as AffineCurve>::prime_subgroup_generator();
+    let a = g
+        .mul(<Scalar<T> as UniformRand>::rand(&mut rng))
+        .into_affine();
+
+    let one = <Scalar<T> as One>::one();
+    let edges = [
+        <Scalar<T> as Zero>::zero(),
+        one,
+        one + one,
+        -one,
+        -one - one,
+    ];
+    let random = (0..16).map(|_| <Scalar<T> as UniformRand>::rand(&mut rng));
+    for k in edges.iter().copied().chain(random) {
+        for point in [g, a, <Affine<T> as Zero>::zero()] {
+            assert_eq!(
+                T::mul_secret(&write(point, k)).unwrap(),
+                T::mul(&write(point, k)).unwrap()
+            );
+        }
+    }
+}
+
+fn all_curve_g2_operations_test<T>()
+where
+    T: CurveBasicOperations + PairingEngine,
+{
+    // zero-points additions
+    {
+        let mut input = Vec::new();
+        <G2Affine<T> as Zero>::zero().write(&mut input);
+        <G2Affine<T> as Zero>::zero().write(&mut input);
+
+        let mut expected = Vec::new();
+        <G2Affine<T> as Zero>::zero().write(&mut expected);
+
+        let res = T::g2_add(&input[..]).unwrap();
+        assert_eq!(&expected[..], &res[..]);
+    }
+
+    // Prime subgroup generator additions check prime subgroup generator * 2(scalar_mul)
+    {
+        let mut input1 = Vec::new();
+        <G2Affine<T> as AffineCurve>::prime_subgroup_generator().write(&mut input1);
+
+        let mut input2 = Vec::new();
+        <G2Affine<T> as AffineCurve>::prime_subgroup_generator().write(&mut input2);
+        <Fr<T> as FromStr>::from_str("2").map(|x| x.write(&mut input2));
+
+        let res1 = T::g2_add(&input1.repeat(2)[..]).expect("G2 generator add failed");
+        let res2 = T::g2_mul(&input2[..]).expect("G2 generator scalar_mul 2 failed");
+
+        let mut res3 = Vec::new();
+        <G2Affine<T> as AffineCurve>::prime_subgroup_generator()
+            .into_projective()
+            .double()
+            .into_affine()
+            .write(&mut res3);
+
+        // prime_subgroup_generator + prime_subgroup_generator = prime_subgroup_generator * 2
+        assert_eq!(res1, res3);
+        assert_eq!(res2, res3);
+        assert_eq!(
+            T::g2_mul_generator(&input2[T::G2_LEN..]).expect("G2 generator table mul failed"),
+            res3
+        );
+    }
+
+    // random points
+    {
+        let mut rng = test_rng();
+        let a = <G2Projective<T> as UniformRand>::rand(&mut rng);
+        let b = <G2Projective<T> as UniformRand>::rand(&mut rng);
+        let s = <Fr<T> as UniformRand>::rand(&mut rng);
+
+        let mut input1 = Vec::new();
+        a.into_affine().write(&mut input1);
+        b.into_affine().write(&mut input1);
+
+        let mut input2 = Vec::new();
+        a.into_affine().write(&mut input2);
+        s.write(&mut input2);
+
+        let mut expected1 = Vec::new();
+        (a + b).into_affine().write(&mut expected1);
+
+        let mut sa = a;
+        sa.mul_assign(s);
+        let mut expected2 = Vec::new();
+        sa.into_affine().write(&mut expected2);
+
+        assert_eq!(T::g2_add(&input1[..]).unwrap(), expected1);
+        assert_eq!(T::g2_mul(&input2[..]).unwrap(), expected2);
+        assert_eq!(T::g2_mul_secret(&input2[..]).unwrap(), expected2);
+    }
+
+    // invalid length
+    assert_eq!(
+        T::g2_add(&[0; 1]),
+        Err(Error::InvalidLength {
+            expected: T::G2_LEN as u32 * 2,
+            got: 1,
+        })
+    );
+    assert!(T::g2_mul(&[0; 1]).is_err());
+}
+
+fn all_curve_msm_test<T>()
+where
+    T: CurveBasicOperations + PairingEngine,
+{
+    use parity_scale_codec::{Compact, Encode};
+
+    let mut rng = test_rng();
+    for n in [0usize, 1, 2, 40].iter() {
+        let mut input1 = Compact(*n as u32).encode();
+        let mut input2 = Compact(*n as u32).encode();
+        let mut acc1 = <G1Projective<T> as Zero>::zero();
+        let mut acc2 = <G2Projective<T> as Zero>::zero();
+        for _ in 0..*n {
+            let a = <G1Projective<T> as UniformRand>::rand(&mut rng);
+            let b = <G2Projective<T> as UniformRand>::rand(&mut rng);
+            let s = <Fr<T> as UniformRand>::rand(&mut rng);
+
+            a.into_affine().write(&mut input1);
+            s.write(&mut input1);
+            b.into_affine().write(&mut input2);
+            s.write(&mut input2);
+
+            let mut sa = a;
+            sa.mul_assign(s);
+            acc1 += &sa;
+            let mut sb = b;
+            sb.mul_assign(s);
+            acc2 += &sb;
+        }
+
+        let mut expected1 = Vec::new();
+        acc1.into_affine().write(&mut expected1);
+        let mut expected2 = Vec::new();
+        acc2.into_affine().write(&mut expected2);
+
+        assert_eq!(T::msm_g1(&input1[..]).unwrap(), expected1);
+        assert_eq!(T::msm_g2(&input2[..]).unwrap(), expected2);
+
+        // count mismatch
+        input1.push(0);
+        assert!(T::msm_g1(&input1[..]).is_err());
+    }
+}
+
+fn all_curve_sum_test<T>()
+where
+    T: CurveBasicOperations + PairingEngine,
+{
+    let mut rng = test_rng();
+    for n in [0usize, 1, 2, 40].iter() {
+        let mut input1 = Vec::new();
+        let mut input2 = Vec::new();
+        let mut acc1 = <G1Projective<T> as Zero>::zero();
+        let mut acc2 = <G2Projective<T> as Zero>::zero();
+        for _ in 0..*n {
+            let a = <G1Projective<T> as UniformRand>::rand(&mut rng);
+            let b = <G2Projective<T> as UniformRand>::rand(&mut rng);
+
+            a.into_affine().write(&mut input1);
+            b.into_affine().write(&mut input2);
+            acc1 += &a;
+            acc2 += &b;
+        }
+
+        let mut expected1 = Vec::new();
+        acc1.into_affine().write(&mut expected1);
+        let mut expected2 = Vec::new();
+        acc2.into_affine().write(&mut expected2);
+
+        assert_eq!(T::sum_g1(&input1[..]).unwrap(), expected1);
+        assert_eq!(T::sum_g2(&input2[..]).unwrap(), expected2);
+
+        // partial point
+        input1.push(0);
+        assert!(T::sum_g1(&input1[..]).is_err());
+    }
+}
+
+fn all_curve_compressed_test<T>()
+where
+    T: CurveBasicOperations + PairingEngine,
+{
+    use ark_serialize::CanonicalSerialize;
+
+    let mut rng = test_rng();
+    let a = <G1Projective<T> as UniformRand>::rand(&mut rng).into_affine();
+    let b = <G2Projective<T> as UniformRand>::rand(&mut rng).into_affine();
+    let s = <Fr<T> as UniformRand>::rand(&mut rng);
+
+    // add
+    {
+        let mut input = Vec::new();
+        a.serialize(&mut input).unwrap();
+        a.serialize(&mut input).unwrap();
+
+        let mut expected = Vec::new();
+        a.into_projective()
+            .double()
+            .into_affine()
+            .serialize(&mut expected)
+            .unwrap();
+        assert_eq!(input.len(), expected.len() * 2);
+        assert_eq!(T::add_compressed(&input[..]).unwrap(), expected);
+    }
+
+    // mul
+    {
+        let mut input = Vec::new();
+        a.serialize(&mut input).unwrap();
+        s.write(&mut input).unwrap();
+
+        let mut expected = Vec::new();
+        a.mul(s).into_affine().serialize(&mut expected).unwrap();
+        assert_eq!(T::mul_compressed(&input[..]).unwrap(), expected);
+    }
+
+    // e(sa, b) * e(-a, sb) == 1
+    {
+        let mut input = Vec::new();
+        a.mul(s).into_affine().serialize(&mut input).unwrap();
+        b.serialize(&mut input).unwrap();
+        a.neg().serialize(&mut input).unwrap();
+        b.mul(s).into_affine().serialize(&mut input).unwrap();
+
+        assert!(T::pairings_compressed(&input[..]).unwrap());
+        assert!(T::pairings_compressed(&input[1..]).is_err());
+    }
+
+    // uncompressed points are rejected
+    let mut input = Vec::new();
+    a.write(&mut input).unwrap();
+    a.write(&mut input).unwrap();
+    assert!(T::add_compressed(&input[..]).is_err());
+}
+
+fn all_curve_validation_test<T>()
+where
+    T: CurveBasicOperations + PairingEngine,
+{
+    use ark_serialize::CanonicalSerialize;
+
+    let g1 = <G1Affine<T> as AffineCurve>::prime_subgroup_generator();
+    let g2 = <G2Affine<T> as AffineCurve>::prime_subgroup_generator();
+
+    // point not on curve, flip the lowest bit of y
+    {
+        let mut input = Vec::new();
+        g1.write(&mut input).unwrap();
+        input[T::G1_LEN / 2] ^= 1;
+        g1.write(&mut input).unwrap();
+
+        assert_eq!(T::add(&input[..]), Err(Error::PointNotOnCurve));
+    }
+
+    // point on curve but not in the prime order subgroup
+    let mut rng = test_rng();
+    let outside = (0..1000)
+        .filter_map(|_| {
+            let bytes = (0..T::G2_LEN).map(|_| rng.gen::<u8>()).collect::<Vec<_>>();
+            <G2Affine<T> as AffineCurve>::from_random_bytes(&bytes)
+        })
+        .find(|p| {
+            !p.mul(<<Fr<T> as PrimeField>::Params as FpParameters>::MODULUS)
+                .is_zero()
+        })
+        .expect("G2 cofactor is not one");
+
+    let mut input = Vec::new();
+    outside.write(&mut input).unwrap();
+    g2.write(&mut input).unwrap();
+    assert_eq!(T::g2_add(&input[..]), Err(Error::PointNotInSubgroup));
+
+    let mut input = Vec::new();
+    g1.write(&mut input).unwrap();
+    outside.write(&mut input).unwrap();
+    assert_eq!(T::pairings(&input[..]), Err(Error::PointNotInSubgroup));
+    // trusted input is not validated
+    assert!(T::pairings_trusted(&input[..]).is_ok());
+
+    // compressed points are validated after decompression
+    let mut input = Vec::new();
+    g1.serialize(&mut input).unwrap();
+    outside.serialize(&mut input).unwrap();
+    assert_eq!(
+        T::pairings_compressed(&input[..]),
+        Err(Error::PointNotInSubgroup)
+    );
+}
+
+fn all_curve_fr_test<T>()
+where
+    T: CurveBasicOperations + PairingEngine,
+{
+    use ark_ff::{BigInteger, Field};
+    use parity_scale_codec::{Compact, Encode};
+
+    let write = |scalars: &[Fr<T>]| {
+        let mut bytes = Vec::new();
+        scalars.iter().for_each(|s| s.write(&mut bytes).unwrap());
+        bytes
+    };
+    let mut rng = test_rng();
+    let a = <Fr<T> as UniformRand>::rand(&mut rng);
+    let b = <Fr<T> as UniformRand>::rand(&mut rng);
+
+    assert_eq!(T::fr_add(&write(&[a, b])).unwrap(), write(&[a + b]));
+    assert_eq!(T::fr_sub(&write(&[a, b])).unwrap(), write(&[a - b]));
+    assert_eq!(T::fr_mul(&write(&[a, b])).unwrap(), write(&[a * b]));
+    assert_eq!(
+        T::fr_inverse(&write(&[a])).unwrap(),
+        write(&[a.inverse().unwrap()])
+    );
+
+    // the exponent is an integer, not reduced modulo the order
+    let exp = <Fr<T> as PrimeField>::Params::MODULUS;
+    let mut input = write(&[a]);
+    exp.write(&mut input).unwrap();
+    assert_eq!(T::fr_pow(&input).unwrap(), write(&[a]));
+    assert_eq!(
+        T::fr_pow(&write(&[a, b])).unwrap(),
+        write(&[a.pow(b.into_repr())])
+    );
+
+    for n in [0usize, 1, 5].iter() {
+        let scalars = (0..*n)
+            .map(|_| <Fr<T> as UniformRand>::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let inverses = scalars
+            .iter()
+            .map(|s| s.inverse().unwrap())
+            .collect::<Vec<_>>();
+        let mut input = Compact(*n as u32).encode();
+        input.extend(write(&scalars));
+        assert_eq!(T::fr_batch_inverse(&input).unwrap(), write(&inverses));
+    }
+
+    // zero has no inverse
+    let zero = <Fr<T> as Zero>::zero();
+    assert_eq!(T::fr_inverse(&write(&[zero])), Err(Error::NotInvertible));
+    let mut input = Compact(2u32).encode();
+    input.extend(write(&[a, zero]));
+    assert_eq!(T::fr_batch_inverse(&input), Err(Error::NotInvertible));
+
+    // non-canonical scalars
+    let mut modulus = Vec::new();
+    <Fr<T> as PrimeField>::Params::MODULUS
+        .write(&mut modulus)
+        .unwrap();
+    assert_eq!(
+        T::fr_add(&[modulus.clone(), write(&[b])].concat()),
+        Err(Error::ScalarOutOfRange)
+    );
+    assert_eq!(T::fr_inverse(&modulus), Err(Error::ScalarOutOfRange));
+    assert_eq!(
+        T::fr_mul(&[0; 1]),
+        Err(Error::InvalidLength {
+            expected: T::SCALAR_LEN as u32 * 2,
+            got: 1,
+        })
+    );
+}
+
+fn all_curve_gt_test<T>()
+where
+    T: CurveBasicOperations + PairingEngine,
+{
+    use ark_ff::Field;
+
+    let mut rng = test_rng();
+    let g1 = <G1Projective<T> as UniformRand>::rand(&mut rng).into_affine();
+    let g2 = <G2Projective<T> as UniformRand>::rand(&mut rng).into_affine();
+    let s = <Fr<T> as UniformRand>::rand(&mut rng);
+    let e = T::pairing(g1, g2);
+
+    let mut input = Vec::new();
+    g1.write(&mut input).unwrap();
+    g2.write(&mut input).unwrap();
+    let mut expected = Vec::new();
+    e.write(&mut expected).unwrap();
+    assert_eq!(T::gt_pairing(&input).unwrap(), expected);
+    assert_eq!(expected.len(), T::GT_LEN);
+
+    // e(sP, Q) = e(P, Q)^s
+    let mut input = Vec::new();
+    g1.mul(s.into_repr())
+        .into_affine()
+        .write(&mut input)
+        .unwrap();
+    g2.write(&mut input).unwrap();
+    let es = T::gt_pairing(&input).unwrap();
+    let mut input = expected.clone();
+    s.write(&mut input).unwrap();
+    assert_eq!(T::gt_pow(&input).unwrap(), es);
+
+    let mut e2 = Vec::new();
+    (e * e).write(&mut e2).unwrap();
+    assert_eq!(
+        T::gt_mul(&[expected.clone(), expected.clone()].concat()).unwrap(),
+        e2
+    );
+    assert!(T::gt_eq(&[es.clone(), es.clone()].concat()).unwrap());
+    assert!(!T::gt_eq(&[expected.clone(), e2.clone()].concat()).unwrap());
+
+    // e + 1 and zero are not in the target group
+    let mut invalid = Vec::new();
+    (e + <T as PairingEngine>::Fqk::one())
+        .write(&mut invalid)
+        .unwrap();
+    assert_eq!(
+        T::gt_mul(&[expected.clone(), invalid].concat()),
+        Err(Error::NotInTargetGroup)
+    );
+    assert_eq!(
+        T::gt_pow(&[vec![0; T::GT_LEN], vec![0; T::SCALAR_LEN]].concat()),
+        Err(Error::NotInTargetGroup)
+    );
+    assert_eq!(
+        T::gt_eq(&[vec![0xff; T::GT_LEN], expected.clone()].concat()),
+        Err(Error::FieldElementOutOfRange)
+    );
+    assert_eq!(
+        T::gt_pairing(&expected),
+        Err(Error::InvalidLength {
+            expected: (T::G1_LEN + T::G2_LEN) as u32,
+            got: T::GT_LEN as u32,
+        })
+    );
+}
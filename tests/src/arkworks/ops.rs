@@ -1,27 +1,562 @@
-use super::all_curve_three_operations_test;
-use arkworks::curve::{Bls12_377, Bls12_381, Bn254, BW6_761, CP6_782};
+use super::{
+    all_curve_compressed_test, all_curve_fr_test, all_curve_g2_operations_test, all_curve_gt_test,
+    all_curve_msm_test, all_curve_sum_test, all_curve_three_operations_test,
+    all_curve_validation_test, all_group_operations_test, all_mul_secret_test,
+};
+use arkworks::curve::{
+    Bls12_377, Bls12_381, Bn254, EdOnBls12_381, EdOnBn254, Pallas, Secp256k1, Vesta, BW6_761,
+    CP6_782, MNT4_298, MNT4_753, MNT6_298, MNT6_753,
+};
 
 #[test]
 fn test_bls12_381() {
     all_curve_three_operations_test::<Bls12_381>();
 }
 
+#[test]
+fn test_bls12_381_g2() {
+    all_curve_g2_operations_test::<Bls12_381>();
+}
+
+#[test]
+fn test_bls12_381_msm() {
+    all_curve_msm_test::<Bls12_381>();
+}
+
+#[test]
+fn test_bls12_381_sum() {
+    all_curve_sum_test::<Bls12_381>();
+}
+
+#[test]
+fn test_bls12_381_compressed() {
+    all_curve_compressed_test::<Bls12_381>();
+}
+
+#[test]
+fn test_bls12_381_validation() {
+    all_curve_validation_test::<Bls12_381>();
+}
+
+#[test]
+fn test_bls12_381_fr() {
+    all_curve_fr_test::<Bls12_381>();
+}
+
+#[test]
+fn test_bls12_381_gt() {
+    all_curve_gt_test::<Bls12_381>();
+}
+
 #[test]
 fn test_bls12_377() {
     all_curve_three_operations_test::<Bls12_377>();
 }
 
+#[test]
+fn test_bls12_377_g2() {
+    all_curve_g2_operations_test::<Bls12_377>();
+}
+
+#[test]
+fn test_bls12_377_msm() {
+    all_curve_msm_test::<Bls12_377>();
+}
+
+#[test]
+fn test_bls12_377_sum() {
+    all_curve_sum_test::<Bls12_377>();
+}
+
+#[test]
+fn test_bls12_377_compressed() {
+    all_curve_compressed_test::<Bls12_377>();
+}
+
+#[test]
+fn test_bls12_377_validation() {
+    all_curve_validation_test::<Bls12_377>();
+}
+
+#[test]
+fn test_bls12_377_fr() {
+    all_curve_fr_test::<Bls12_377>();
+}
+
+#[test]
+fn test_bls12_377_gt() {
+    all_curve_gt_test::<Bls12_377>();
+}
+
 #[test]
 fn test_bn254() {
     all_curve_three_operations_test::<Bn254>();
 }
 
+#[test]
+fn test_bn254_g2() {
+    all_curve_g2_operations_test::<Bn254>();
+}
+
+#[test]
+fn test_bn254_msm() {
+    all_curve_msm_test::<Bn254>();
+}
+
+#[test]
+fn test_bn254_sum() {
+    all_curve_sum_test::<Bn254>();
+}
+
+#[test]
+fn test_bn254_compressed() {
+    all_curve_compressed_test::<Bn254>();
+}
+
+#[test]
+fn test_bn254_validation() {
+    all_curve_validation_test::<Bn254>();
+}
+
+#[test]
+fn test_bn254_fr() {
+    all_curve_fr_test::<Bn254>();
+}
+
+#[test]
+fn test_bn254_gt() {
+    all_curve_gt_test::<Bn254>();
+}
+
 #[test]
 fn test_bw6_761() {
     all_curve_three_operations_test::<BW6_761>();
 }
 
+#[test]
+fn test_bw6_761_g2() {
+    all_curve_g2_operations_test::<BW6_761>();
+}
+
+#[test]
+fn test_bw6_761_msm() {
+    all_curve_msm_test::<BW6_761>();
+}
+
+#[test]
+fn test_bw6_761_sum() {
+    all_curve_sum_test::<BW6_761>();
+}
+
+#[test]
+fn test_bw6_761_compressed() {
+    all_curve_compressed_test::<BW6_761>();
+}
+
+#[test]
+fn test_bw6_761_validation() {
+    all_curve_validation_test::<BW6_761>();
+}
+
+#[test]
+fn test_bw6_761_fr() {
+    all_curve_fr_test::<BW6_761>();
+}
+
+#[test]
+fn test_bw6_761_gt() {
+    all_curve_gt_test::<BW6_761>();
+}
+
 #[test]
 fn test_cp6_782() {
     all_curve_three_operations_test::<CP6_782>();
 }
+
+#[test]
+fn test_cp6_782_g2() {
+    all_curve_g2_operations_test::<CP6_782>();
+}
+
+#[test]
+fn test_cp6_782_msm() {
+    all_curve_msm_test::<CP6_782>();
+}
+
+#[test]
+fn test_cp6_782_sum() {
+    all_curve_sum_test::<CP6_782>();
+}
+
+#[test]
+fn test_cp6_782_compressed() {
+    all_curve_compressed_test::<CP6_782>();
+}
+
+#[test]
+fn test_cp6_782_validation() {
+    all_curve_validation_test::<CP6_782>();
+}
+
+#[test]
+fn test_cp6_782_fr() {
+    all_curve_fr_test::<CP6_782>();
+}
+
+#[test]
+fn test_cp6_782_gt() {
+    all_curve_gt_test::<CP6_782>();
+}
+
+#[test]
+fn test_cp6_782_call() {
+    use super::{AffineCurve, G1Affine, ToBytes};
+
+    let mut input = Vec::new();
+    <G1Affine<CP6_782> as AffineCurve>::prime_subgroup_generator().write(&mut input);
+    <G1Affine<CP6_782> as AffineCurve>::prime_subgroup_generator().write(&mut input);
+
+    assert_eq!(
+        arkworks::add(0x40, &input).unwrap(),
+        <CP6_782 as arkworks::GroupOperations>::add(&input).unwrap()
+    );
+    // the id before the registry
+    assert_eq!(
+        arkworks::add(0x4, &input).unwrap(),
+        arkworks::add(0x40, &input).unwrap()
+    );
+}
+
+#[test]
+fn test_mnt4_298() {
+    all_curve_three_operations_test::<MNT4_298>();
+}
+
+#[test]
+fn test_mnt4_298_g2() {
+    all_curve_g2_operations_test::<MNT4_298>();
+}
+
+#[test]
+fn test_mnt4_298_msm() {
+    all_curve_msm_test::<MNT4_298>();
+}
+
+#[test]
+fn test_mnt4_298_sum() {
+    all_curve_sum_test::<MNT4_298>();
+}
+
+#[test]
+fn test_mnt4_298_compressed() {
+    all_curve_compressed_test::<MNT4_298>();
+}
+
+#[test]
+fn test_mnt4_298_validation() {
+    all_curve_validation_test::<MNT4_298>();
+}
+
+#[test]
+fn test_mnt4_298_fr() {
+    all_curve_fr_test::<MNT4_298>();
+}
+
+#[test]
+fn test_mnt4_298_gt() {
+    all_curve_gt_test::<MNT4_298>();
+}
+
+#[test]
+fn test_mnt4_298_call() {
+    use super::{AffineCurve, G1Affine, ToBytes};
+
+    let mut input = Vec::new();
+    <G1Affine<MNT4_298> as AffineCurve>::prime_subgroup_generator().write(&mut input);
+    <G1Affine<MNT4_298> as AffineCurve>::prime_subgroup_generator().write(&mut input);
+
+    assert_eq!(
+        arkworks::add(0x50, &input).unwrap(),
+        <MNT4_298 as arkworks::GroupOperations>::add(&input).unwrap()
+    );
+}
+
+#[test]
+fn test_mnt6_298() {
+    all_curve_three_operations_test::<MNT6_298>();
+}
+
+#[test]
+fn test_mnt6_298_g2() {
+    all_curve_g2_operations_test::<MNT6_298>();
+}
+
+#[test]
+fn test_mnt6_298_msm() {
+    all_curve_msm_test::<MNT6_298>();
+}
+
+#[test]
+fn test_mnt6_298_sum() {
+    all_curve_sum_test::<MNT6_298>();
+}
+
+#[test]
+fn test_mnt6_298_compressed() {
+    all_curve_compressed_test::<MNT6_298>();
+}
+
+#[test]
+fn test_mnt6_298_validation() {
+    all_curve_validation_test::<MNT6_298>();
+}
+
+#[test]
+fn test_mnt6_298_fr() {
+    all_curve_fr_test::<MNT6_298>();
+}
+
+#[test]
+fn test_mnt6_298_gt() {
+    all_curve_gt_test::<MNT6_298>();
+}
+
+#[test]
+fn test_mnt6_298_call() {
+    use super::{AffineCurve, G1Affine, ToBytes};
+
+    let mut input = Vec::new();
+    <G1Affine<MNT6_298> as AffineCurve>::prime_subgroup_generator().write(&mut input);
+    <G1Affine<MNT6_298> as AffineCurve>::prime_subgroup_generator().write(&mut input);
+
+    assert_eq!(
+        arkworks::add(0x60, &input).unwrap(),
+        <MNT6_298 as arkworks::GroupOperations>::add(&input).unwrap()
+    );
+}
+
+#[test]
+fn test_mnt4_753() {
+    all_curve_three_operations_test::<MNT4_753>();
+}
+
+#[test]
+fn test_mnt4_753_g2() {
+    all_curve_g2_operations_test::<MNT4_753>();
+}
+
+#[test]
+fn test_mnt4_753_msm() {
+    all_curve_msm_test::<MNT4_753>();
+}
+
+#[test]
+fn test_mnt4_753_sum() {
+    all_curve_sum_test::<MNT4_753>();
+}
+
+#[test]
+fn test_mnt4_753_compressed() {
+    all_curve_compressed_test::<MNT4_753>();
+}
+
+#[test]
+fn test_mnt4_753_validation() {
+    all_curve_validation_test::<MNT4_753>();
+}
+
+#[test]
+fn test_mnt4_753_fr() {
+    all_curve_fr_test::<MNT4_753>();
+}
+
+#[test]
+fn test_mnt4_753_gt() {
+    all_curve_gt_test::<MNT4_753>();
+}
+
+#[test]
+fn test_mnt4_753_call() {
+    use super::{AffineCurve, G1Affine, ToBytes};
+
+    let mut input = Vec::new();
+    <G1Affine<MNT4_753> as AffineCurve>::prime_subgroup_generator().write(&mut input);
+    <G1Affine<MNT4_753> as AffineCurve>::prime_subgroup_generator().write(&mut input);
+
+    assert_eq!(
+        arkworks::add(0x70, &input).unwrap(),
+        <MNT4_753 as arkworks::GroupOperations>::add(&input).unwrap()
+    );
+}
+
+#[test]
+fn test_mnt6_753() {
+    all_curve_three_operations_test::<MNT6_753>();
+}
+
+#[test]
+fn test_mnt6_753_g2() {
+    all_curve_g2_operations_test::<MNT6_753>();
+}
+
+#[test]
+fn test_mnt6_753_msm() {
+    all_curve_msm_test::<MNT6_753>();
+}
+
+#[test]
+fn test_mnt6_753_sum() {
+    all_curve_sum_test::<MNT6_753>();
+}
+
+#[test]
+fn test_mnt6_753_compressed() {
+    all_curve_compressed_test::<MNT6_753>();
+}
+
+#[test]
+fn test_mnt6_753_validation() {
+    all_curve_validation_test::<MNT6_753>();
+}
+
+#[test]
+fn test_mnt6_753_fr() {
+    all_curve_fr_test::<MNT6_753>();
+}
+
+#[test]
+fn test_mnt6_753_gt() {
+    all_curve_gt_test::<MNT6_753>();
+}
+
+#[test]
+fn test_mnt6_753_call() {
+    use super::{AffineCurve, G1Affine, ToBytes};
+
+    let mut input = Vec::new();
+    <G1Affine<MNT6_753> as AffineCurve>::prime_subgroup_generator().write(&mut input);
+    <G1Affine<MNT6_753> as AffineCurve>::prime_subgroup_generator().write(&mut input);
+
+    assert_eq!(
+        arkworks::add(0x80, &input).unwrap(),
+        <MNT6_753 as arkworks::GroupOperations>::add(&input).unwrap()
+    );
+}
+
+#[test]
+fn test_pallas() {
+    all_group_operations_test::<Pallas>();
+}
+
+#[test]
+fn test_pallas_call() {
+    use super::{AffineCurve, ToBytes};
+    use arkworks::GroupOperations;
+
+    let mut input = Vec::new();
+    <Pallas as GroupOperations>::Affine::prime_subgroup_generator().write(&mut input);
+    <Pallas as GroupOperations>::Affine::prime_subgroup_generator().write(&mut input);
+
+    assert_eq!(
+        arkworks::add(0x90, &input).unwrap(),
+        Pallas::add(&input).unwrap()
+    );
+}
+
+#[test]
+fn test_vesta() {
+    all_group_operations_test::<Vesta>();
+}
+
+#[test]
+fn test_vesta_call() {
+    use super::{AffineCurve, ToBytes};
+    use arkworks::GroupOperations;
+
+    let mut input = Vec::new();
+    <Vesta as GroupOperations>::Affine::prime_subgroup_generator().write(&mut input);
+    <Vesta as GroupOperations>::Affine::prime_subgroup_generator().write(&mut input);
+
+    assert_eq!(
+        arkworks::add(0xa0, &input).unwrap(),
+        Vesta::add(&input).unwrap()
+    );
+}
+
+#[test]
+fn test_secp256k1() {
+    all_group_operations_test::<Secp256k1>();
+}
+
+#[test]
+fn test_secp256k1_call() {
+    use super::{AffineCurve, ToBytes};
+    use arkworks::GroupOperations;
+
+    let mut input = Vec::new();
+    <Secp256k1 as GroupOperations>::Affine::prime_subgroup_generator().write(&mut input);
+    <Secp256k1 as GroupOperations>::Affine::prime_subgroup_generator().write(&mut input);
+
+    assert_eq!(
+        arkworks::add(0xb0, &input).unwrap(),
+        Secp256k1::add(&input).unwrap()
+    );
+}
+
+#[test]
+fn test_ed_on_bn254() {
+    all_group_operations_test::<EdOnBn254>();
+}
+
+#[test]
+fn test_ed_on_bn254_call() {
+    use super::{AffineCurve, ToBytes};
+    use arkworks::GroupOperations;
+
+    let mut input = Vec::new();
+    <EdOnBn254 as GroupOperations>::Affine::prime_subgroup_generator().write(&mut input);
+    <EdOnBn254 as GroupOperations>::Affine::prime_subgroup_generator().write(&mut input);
+
+    assert_eq!(
+        arkworks::add(0xc0, &input).unwrap(),
+        EdOnBn254::add(&input).unwrap()
+    );
+}
+
+#[test]
+fn test_ed_on_bls12_381() {
+    all_group_operations_test::<EdOnBls12_381>();
+}
+
+#[test]
+fn test_ed_on_bls12_381_call() {
+    use super::{AffineCurve, ToBytes};
+    use arkworks::GroupOperations;
+
+    let mut input = Vec::new();
+    <EdOnBls12_381 as GroupOperations>::Affine::prime_subgroup_generator().write(&mut input);
+    <EdOnBls12_381 as GroupOperations>::Affine::prime_subgroup_generator().write(&mut input);
+
+    assert_eq!(
+        arkworks::add(0xd0, &input).unwrap(),
+        EdOnBls12_381::add(&input).unwrap()
+    );
+}
+
+#[test]
+fn test_mul_secret() {
+    all_mul_secret_test::<Bls12_377>();
+    all_mul_secret_test::<Bls12_381>();
+    all_mul_secret_test::<Bn254>();
+    all_mul_secret_test::<BW6_761>();
+    all_mul_secret_test::<CP6_782>();
+    all_mul_secret_test::<MNT4_298>();
+    all_mul_secret_test::<MNT6_298>();
+    all_mul_secret_test::<MNT4_753>();
+    all_mul_secret_test::<MNT6_753>();
+    all_mul_secret_test::<Pallas>();
+    all_mul_secret_test::<Vesta>();
+    all_mul_secret_test::<Secp256k1>();
+    all_mul_secret_test::<EdOnBn254>();
+    all_mul_secret_test::<EdOnBls12_381>();
+}
@@ -0,0 +1,229 @@
+//! In-wasm benches, the curve operations in wasm32 on wasmi, the interpreter
+//! of the runtime, against the native operations of the chain extension
+//!
+//! cargo build --release --target wasm32-unknown-unknown --manifest-path tests/wasm/Cargo.toml
+//! cargo test --release -p tests --features wasm wasm -- --ignored --nocapture
+//!
+//! The module is metered with the gas counter of pallet-contracts at one unit
+//! per instruction, so the counts are the instructions executed.
+#![cfg(feature = "wasm")]
+
+use std::{convert::TryFrom, fs, time::Instant};
+
+use ark_ec::PairingEngine;
+use arkworks::{
+    curve::{
+        Bls12_377, Bls12_381, Bn254, BW6_761, CP6_782, MNT4_298, MNT4_753, MNT6_298, MNT6_753,
+    },
+    registry::dispatch,
+    verify_parcel, CurveBasicOperations, CurveId, Operation,
+};
+use pwasm_utils::rules::Set;
+use wasmi::{
+    Externals, FuncInstance, FuncRef, ImportsBuilder, Module, ModuleImportResolver, ModuleInstance,
+    RuntimeArgs, RuntimeValue, Signature, Trap, ValueType,
+};
+
+use super::{
+    bench::{BLS12_381_PARCEL, BN254_PARCEL},
+    weights::input,
+};
+
+/// Runs of each measurement, the fastest one is taken
+const RUNS: usize = 3;
+
+/// Pairs of the pairings, as a Groth16 verification
+const PAIRS: usize = 4;
+
+/// The module built by `tests/wasm`
+const WASM: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/wasm/target/wasm32-unknown-unknown/release/zkmega_wasm.wasm"
+);
+
+/// Index of the host function `env.gas`
+const GAS: usize = 0;
+
+/// Instructions executed, counted by the injected `env.gas` calls
+#[derive(Default)]
+struct Counter(u64);
+
+impl Externals for Counter {
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        assert_eq!(index, GAS);
+        self.0 += args.nth_checked::<u32>(0)? as u64;
+        Ok(None)
+    }
+}
+
+/// Resolve `env.gas`, the only import of the metered module
+struct Env;
+
+impl ModuleImportResolver for Env {
+    fn resolve_func(
+        &self,
+        field_name: &str,
+        _signature: &Signature,
+    ) -> Result<FuncRef, wasmi::Error> {
+        match field_name {
+            "gas" => Ok(FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32][..], None),
+                GAS,
+            )),
+            _ => Err(wasmi::Error::Instantiation(format!(
+                "unknown import env.{}",
+                field_name
+            ))),
+        }
+    }
+}
+
+/// The module of `tests/wasm` metered one unit per instruction
+fn module() -> Module {
+    let wasm = fs::read(WASM).unwrap_or_else(|_| {
+        panic!(
+            "{} not found, build it with `cargo build --release --target \
+             wasm32-unknown-unknown --manifest-path tests/wasm/Cargo.toml`",
+            WASM
+        )
+    });
+    let module = parity_wasm::deserialize_buffer(&wasm).unwrap();
+    let metered = pwasm_utils::inject_gas_counter(module, &Set::default(), "env")
+        .expect("the module has no gas import");
+    Module::from_parity_wasm_module(metered).unwrap()
+}
+
+/// Run the export `name` with the input written by `alloc`, in a new instance
+/// as nothing is freed, returns the result, instructions and nanoseconds
+fn run(module: &Module, name: &str, arg: u32, input: &[u8]) -> (RuntimeValue, u64, u128) {
+    let instance = ModuleInstance::new(module, &ImportsBuilder::new().with_resolver("env", &Env))
+        .unwrap()
+        .assert_no_start();
+    let memory = instance
+        .export_by_name("memory")
+        .and_then(|e| e.as_memory().cloned())
+        .unwrap();
+
+    let mut counter = Counter::default();
+    let ptr = match instance
+        .invoke_export(
+            "alloc",
+            &[RuntimeValue::I32(input.len() as i32)],
+            &mut counter,
+        )
+        .unwrap()
+    {
+        Some(RuntimeValue::I32(ptr)) => ptr,
+        _ => panic!("alloc returns the pointer"),
+    };
+    memory.set(ptr as u32, input).unwrap();
+
+    let mut counter = Counter::default();
+    let start = Instant::now();
+    let result = instance
+        .invoke_export(
+            name,
+            &[
+                RuntimeValue::I32(arg as i32),
+                RuntimeValue::I32(ptr),
+                RuntimeValue::I32(input.len() as i32),
+            ],
+            &mut counter,
+        )
+        .unwrap()
+        .unwrap();
+    (result, counter.0, start.elapsed().as_nanos())
+}
+
+/// Fastest of the runs of `f`, the instructions don't change
+fn fastest_wasm(f: impl Fn() -> (u64, u128)) -> (u64, u128) {
+    (0..RUNS)
+        .map(|_| f())
+        .min_by_key(|(_, nanos)| *nanos)
+        .unwrap()
+}
+
+/// Fastest native run of `f` in nanoseconds
+fn fastest_native(f: impl Fn()) -> u128 {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed().as_nanos()
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(curve: CurveId, name: &str, (instructions, wasm): (u64, u128), native: u128) {
+    println!(
+        "{:<10} {:<8} {:>14} {:>12} {:>12} {:>8.1}",
+        curve.name(),
+        name,
+        instructions,
+        wasm / 1000,
+        native / 1000,
+        wasm as f64 / native as f64
+    );
+}
+
+/// Bench add, mul and pairings of curve `E`
+fn bench_curve<E: CurveBasicOperations + PairingEngine>(module: &Module) {
+    let curve = CurveId::try_from(E::CURVE_ID).unwrap();
+    for (op, items) in [
+        (Operation::Add, 1),
+        (Operation::Mul, 1),
+        (Operation::Pairing, PAIRS),
+    ] {
+        let func_id = curve.func_id(op);
+        let input = input::<E>(op, items);
+        let wasm = fastest_wasm(|| match run(module, "call", func_id, &input) {
+            (RuntimeValue::I64(output), instructions, nanos) if output != 0 => {
+                (instructions, nanos)
+            }
+            _ => panic!("{} {} failed in wasm", curve.name(), op.name()),
+        });
+        let native = fastest_native(|| {
+            dispatch(func_id, &input).unwrap();
+        });
+        report(curve, op.name(), wasm, native);
+    }
+}
+
+/// Bench Groth16 verify of the parcel on `curve`
+fn bench_verify(module: &Module, curve: CurveId, parcel: &str) {
+    let parcel = hex::decode(parcel).unwrap();
+    let wasm = fastest_wasm(|| match run(module, "verify", curve as u32, &parcel) {
+        (RuntimeValue::I32(1), instructions, nanos) => (instructions, nanos),
+        _ => panic!("{} verify failed in wasm", curve.name()),
+    });
+    let native = fastest_native(|| {
+        assert!(verify_parcel(curve as u32, parcel.clone()).unwrap());
+    });
+    report(curve, "verify", wasm, native);
+}
+
+#[test]
+#[ignore]
+fn bench_wasm() {
+    let module = module();
+    println!(
+        "{:<10} {:<8} {:>14} {:>12} {:>12} {:>8}",
+        "curve", "op", "instructions", "wasm (us)", "native (us)", "ratio"
+    );
+    bench_curve::<Bls12_377>(&module);
+    bench_curve::<Bls12_381>(&module);
+    bench_curve::<Bn254>(&module);
+    bench_curve::<BW6_761>(&module);
+    bench_curve::<CP6_782>(&module);
+    bench_curve::<MNT4_298>(&module);
+    bench_curve::<MNT6_298>(&module);
+    bench_curve::<MNT4_753>(&module);
+    bench_curve::<MNT6_753>(&module);
+    bench_verify(&module, CurveId::Bls12_381, BLS12_381_PARCEL);
+    bench_verify(&module, CurveId::Bn254, BN254_PARCEL);
+}
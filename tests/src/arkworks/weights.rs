@@ -0,0 +1,389 @@
+//! Weights harness, prints the tables of `arkworks::weights`
+//!
+//! cargo test --release -p tests weights -- --ignored --nocapture
+use std::time::Instant;
+
+use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::ToBytes;
+use ark_serialize::CanonicalSerialize;
+use ark_std::{test_rng, UniformRand};
+use arkworks::{
+    abi,
+    curve::{
+        Bls12_377, Bls12_381, Bn254, EdOnBls12_381, EdOnBn254, Pallas, Secp256k1, Vesta, BW6_761,
+        CP6_782, MNT4_298, MNT4_753, MNT6_298, MNT6_753,
+    },
+    evm::Precompile,
+    ops::Scalar,
+    registry::dispatch,
+    verify_parcel,
+    weights::{self, Cost, Weight, WEIGHT_PER_NANOS},
+    CurveId, GroupOperations, Operation, PairingOperations,
+};
+use parity_scale_codec::{Compact, Encode};
+use rand::Rng;
+
+use super::bench::{BLS12_381_PARCEL, BN254_PARCEL};
+
+/// Runs of each measurement, the fastest one is taken
+const RUNS: usize = 5;
+
+/// Items of the large inputs of linear costs
+const ITEMS: usize = 8;
+
+/// BN254 G2 generator in the EIP-197 encoding
+const BN254_G2: &str = "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c21800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+
+fn measure(func_id: u32, input: &[u8]) -> Weight {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            dispatch(func_id, input).expect("bench inputs are valid");
+            start.elapsed().as_nanos() as Weight * WEIGHT_PER_NANOS
+        })
+        .min()
+        .unwrap()
+}
+
+/// Fit the cost of `func_id` from the inputs of one and `ITEMS` items, the
+/// cost is constant if both inputs have as many items
+fn fit(func_id: u32, items: impl Fn(&[u8]) -> u64, input: impl Fn(usize) -> Vec<u8>) -> Cost {
+    let (one, many) = (input(1), input(ITEMS));
+    let base = measure(func_id, &one);
+    let (one_items, many_items) = (items(&one), items(&many));
+    if many_items <= one_items {
+        return Cost::new(base, 0);
+    }
+
+    let per_item =
+        measure(func_id, &many).saturating_sub(base) / (many_items - one_items) as Weight;
+    Cost::new(
+        base.saturating_sub(per_item * one_items as Weight),
+        per_item,
+    )
+}
+
+fn g1<T: GroupOperations>(rng: &mut impl Rng) -> T::Affine {
+    T::Affine::prime_subgroup_generator()
+        .mul(<Scalar<T> as UniformRand>::rand(rng))
+        .into_affine()
+}
+
+fn g2<E: PairingEngine>(rng: &mut impl Rng) -> E::G2Affine {
+    E::G2Projective::rand(rng).into_affine()
+}
+
+fn gt<E: PairingOperations>(rng: &mut impl Rng) -> E::Fqk {
+    E::pairing(g1::<E>(rng), g2::<E>(rng))
+}
+
+/// Input of the hashes to curve, a message of 64 bytes per item
+fn hash_input(rng: &mut impl Rng, items: usize) -> Vec<u8> {
+    let mut input = b"QUUX-V01-CS02-with-expander"[..].encode();
+    input.extend((0..items * 64).map(|_| rng.gen::<u8>()));
+    input
+}
+
+/// Input of `op` of group `T` with `items` items
+pub(super) fn group_input<T: GroupOperations>(op: Operation, items: usize) -> Vec<u8> {
+    let rng = &mut test_rng();
+    let mut input = Vec::new();
+    match op {
+        Operation::Add => {
+            g1::<T>(rng).write(&mut input).unwrap();
+            g1::<T>(rng).write(&mut input).unwrap();
+        }
+        Operation::Mul => {
+            g1::<T>(rng).write(&mut input).unwrap();
+            Scalar::<T>::rand(rng).write(&mut input).unwrap();
+        }
+        Operation::MsmG1 | Operation::MsmG1Trusted => {
+            Compact(items as u32).encode_to(&mut input);
+            for _ in 0..items {
+                g1::<T>(rng).write(&mut input).unwrap();
+                Scalar::<T>::rand(rng).write(&mut input).unwrap();
+            }
+        }
+        Operation::AddCompressed => {
+            g1::<T>(rng).serialize(&mut input).unwrap();
+            g1::<T>(rng).serialize(&mut input).unwrap();
+        }
+        Operation::MulCompressed => {
+            g1::<T>(rng).serialize(&mut input).unwrap();
+            Scalar::<T>::rand(rng).write(&mut input).unwrap();
+        }
+        Operation::HashToG1 => input = hash_input(rng, items),
+        Operation::FrAdd | Operation::FrSub | Operation::FrMul => {
+            Scalar::<T>::rand(rng).write(&mut input).unwrap();
+            Scalar::<T>::rand(rng).write(&mut input).unwrap();
+        }
+        // an exponent of `items` random limbs, the top one nonzero
+        Operation::FrPow => {
+            Scalar::<T>::rand(rng).write(&mut input).unwrap();
+            let mut exp = vec![0; T::SCALAR_LEN];
+            let len = (items * 8).min(T::SCALAR_LEN);
+            rng.fill(&mut exp[..len]);
+            exp[len - 1] |= 1;
+            input.extend(exp);
+        }
+        Operation::FrInverse | Operation::MulGenerator => {
+            Scalar::<T>::rand(rng).write(&mut input).unwrap();
+        }
+        Operation::FrBatchInverse => {
+            Compact(items as u32).encode_to(&mut input);
+            for _ in 0..items {
+                Scalar::<T>::rand(rng).write(&mut input).unwrap();
+            }
+        }
+        Operation::SumG1 => {
+            for _ in 0..items {
+                g1::<T>(rng).write(&mut input).unwrap();
+            }
+        }
+        _ => panic!("{:?} needs a pairing", op),
+    }
+    input
+}
+
+/// Input of `op` of pairing curve `E` with `items` items
+pub(super) fn input<E: PairingOperations>(op: Operation, items: usize) -> Vec<u8> {
+    let rng = &mut test_rng();
+    let mut input = Vec::new();
+    match op {
+        Operation::Pairing | Operation::PairingTrusted => {
+            for _ in 0..items {
+                g1::<E>(rng).write(&mut input).unwrap();
+                g2::<E>(rng).write(&mut input).unwrap();
+            }
+        }
+        Operation::G2Add => {
+            g2::<E>(rng).write(&mut input).unwrap();
+            g2::<E>(rng).write(&mut input).unwrap();
+        }
+        Operation::G2Mul => {
+            g2::<E>(rng).write(&mut input).unwrap();
+            E::Fr::rand(rng).write(&mut input).unwrap();
+        }
+        Operation::MsmG2 => {
+            Compact(items as u32).encode_to(&mut input);
+            for _ in 0..items {
+                g2::<E>(rng).write(&mut input).unwrap();
+                E::Fr::rand(rng).write(&mut input).unwrap();
+            }
+        }
+        Operation::PairingCompressed => {
+            for _ in 0..items {
+                g1::<E>(rng).serialize(&mut input).unwrap();
+                g2::<E>(rng).serialize(&mut input).unwrap();
+            }
+        }
+        Operation::HashToG2 => input = hash_input(rng, items),
+        Operation::GtPairing => {
+            g1::<E>(rng).write(&mut input).unwrap();
+            g2::<E>(rng).write(&mut input).unwrap();
+        }
+        Operation::G2MulGenerator => {
+            E::Fr::rand(rng).write(&mut input).unwrap();
+        }
+        Operation::GtMul | Operation::GtEq => {
+            gt::<E>(rng).write(&mut input).unwrap();
+            gt::<E>(rng).write(&mut input).unwrap();
+        }
+        Operation::GtPow => {
+            gt::<E>(rng).write(&mut input).unwrap();
+            E::Fr::rand(rng).write(&mut input).unwrap();
+        }
+        Operation::SumG2 => {
+            for _ in 0..items {
+                g2::<E>(rng).write(&mut input).unwrap();
+            }
+        }
+        _ => input = group_input::<E>(op, items),
+    }
+    input
+}
+
+/// Print the weights table of `curve` from the inputs of `input`
+fn bench_curve(curve: CurveId, input: impl Fn(Operation, usize) -> Vec<u8>) {
+    println!(
+        "pub const {}: CurveWeights = CurveWeights {{",
+        curve.name().to_uppercase()
+    );
+    for op in Operation::ALL {
+        if !curve.supports(*op) {
+            println!("    {}: Cost::new(0, 0),", op.name());
+            continue;
+        }
+        let cost = fit(
+            curve.func_id(*op),
+            |input| curve.items(*op, input),
+            |items| input(*op, items),
+        );
+        println!(
+            "    {}: Cost::new({}, {}),",
+            op.name(),
+            cost.base,
+            cost.per_item
+        );
+    }
+    println!("}};\n");
+}
+
+/// EIP-2537 field element of `n`
+fn bls12_fp(n: u8) -> Vec<u8> {
+    let mut fp = vec![0; 64];
+    fp[63] = n;
+    fp
+}
+
+/// Input of `precompile` with `items` items
+fn precompile_input(precompile: Precompile, items: usize) -> Vec<u8> {
+    let rng = &mut test_rng();
+    let bls12_g1 = |n: u8| arkworks::bls12_map_fp_to_g1(&bls12_fp(n)).unwrap();
+    let bls12_g2 = |n: u8| arkworks::bls12_map_fp2_to_g2(&bls12_fp(n).repeat(2)).unwrap();
+    let bn254_g1 = |scalar: [u8; 32]| {
+        let mut input = vec![0; 64];
+        input[31] = 1;
+        input[63] = 2;
+        input.extend_from_slice(&scalar);
+        arkworks::ec_mul(&input).unwrap()
+    };
+    let bn254_g2 = hex::decode(BN254_G2).unwrap();
+
+    let mut input = Vec::new();
+    for n in 1..=items as u8 {
+        let scalar = rng.gen::<[u8; 32]>();
+        match precompile {
+            Precompile::Bls12381G1Add => {
+                input.extend(bls12_g1(n));
+                input.extend(bls12_g1(n + 1));
+            }
+            Precompile::Bls12381G1Mul | Precompile::Bls12381G1Msm => {
+                input.extend(bls12_g1(n));
+                input.extend_from_slice(&scalar);
+            }
+            Precompile::Bls12381G2Add => {
+                input.extend(bls12_g2(n));
+                input.extend(bls12_g2(n + 1));
+            }
+            Precompile::Bls12381G2Mul | Precompile::Bls12381G2Msm => {
+                input.extend(bls12_g2(n));
+                input.extend_from_slice(&scalar);
+            }
+            Precompile::Bls12381Pairing => {
+                input.extend(bls12_g1(n));
+                input.extend(bls12_g2(n));
+            }
+            Precompile::Bls12381MapFpToG1 => input.extend(bls12_fp(n)),
+            Precompile::Bls12381MapFp2ToG2 => input.extend(bls12_fp(n).repeat(2)),
+            Precompile::Bn254Add => {
+                input.extend(bn254_g1(scalar));
+                input.extend(bn254_g1(rng.gen()));
+            }
+            Precompile::Bn254Mul => {
+                input.extend(bn254_g1(scalar));
+                input.extend_from_slice(&scalar);
+            }
+            Precompile::Bn254Pairing => {
+                input.extend(bn254_g1(scalar));
+                input.extend_from_slice(&bn254_g2);
+            }
+        }
+    }
+    input
+}
+
+/// Print the weights table of the EVM precompiles
+fn bench_precompiles() {
+    println!("impl Precompile {{");
+    println!("    /// Cost of this precompile");
+    println!("    pub const fn cost(self) -> Cost {{");
+    println!("        match self {{");
+    for precompile in Precompile::ALL {
+        let cost = fit(
+            precompile.func_id(),
+            |input| precompile.items(input.len()),
+            |items| precompile_input(*precompile, items),
+        );
+        println!(
+            "            Precompile::{:?} => Cost::new({}, {}),",
+            precompile, cost.base, cost.per_item
+        );
+    }
+    println!("        }}\n    }}\n}}\n");
+}
+
+/// Compare the verify weights with the measured verifications
+fn bench_verify(curve: CurveId, parcel: &str) {
+    let parcel = hex::decode(parcel).unwrap();
+    let weight = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            assert!(verify_parcel(curve as u32, parcel.clone()).unwrap());
+            start.elapsed().as_nanos() as Weight * WEIGHT_PER_NANOS
+        })
+        .min()
+        .unwrap();
+    println!(
+        "// {} verify_parcel: {}, verify_weight: {}",
+        curve.name(),
+        weight,
+        weights::verify_weight(curve, 1)
+    );
+}
+
+#[test]
+#[ignore]
+fn bench_weights() {
+    bench_curve(CurveId::Bls12_377, input::<Bls12_377>);
+    bench_curve(CurveId::Bls12_381, input::<Bls12_381>);
+    bench_curve(CurveId::Bn254, input::<Bn254>);
+    bench_curve(CurveId::BW6_761, input::<BW6_761>);
+    bench_curve(CurveId::CP6_782, input::<CP6_782>);
+    bench_curve(CurveId::MNT4_298, input::<MNT4_298>);
+    bench_curve(CurveId::MNT6_298, input::<MNT6_298>);
+    bench_curve(CurveId::MNT4_753, input::<MNT4_753>);
+    bench_curve(CurveId::MNT6_753, input::<MNT6_753>);
+    bench_curve(CurveId::Pallas, group_input::<Pallas>);
+    bench_curve(CurveId::Vesta, group_input::<Vesta>);
+    bench_curve(CurveId::Secp256k1, group_input::<Secp256k1>);
+    bench_curve(CurveId::EdOnBn254, group_input::<EdOnBn254>);
+    bench_curve(CurveId::EdOnBls12_381, group_input::<EdOnBls12_381>);
+    bench_precompiles();
+
+    let describe = fit(abi::DESCRIBE, |_| 0, |_| Vec::new());
+    println!(
+        "pub const DESCRIBE: Cost = Cost::new({}, 0);\n",
+        describe.base
+    );
+
+    bench_verify(CurveId::Bls12_381, BLS12_381_PARCEL);
+    bench_verify(CurveId::Bn254, BN254_PARCEL);
+}
+
+/// Check the inputs of the supported operations of `curve` run
+fn check_inputs(curve: CurveId, input: impl Fn(Operation, usize) -> Vec<u8>) {
+    for op in Operation::ALL.iter().filter(|op| curve.supports(**op)) {
+        for items in [1, 2].iter() {
+            let input = input(*op, *items);
+            assert!(dispatch(curve.func_id(*op), &input).is_ok(), "{:?}", op);
+            assert!(
+                curve.items(*op, &input) > 0 || curve.weights().cost(*op).per_item == 0,
+                "{:?}",
+                op
+            );
+        }
+    }
+}
+
+#[test]
+fn test_bench_inputs() {
+    check_inputs(CurveId::Bn254, input::<Bn254>);
+    check_inputs(CurveId::Pallas, group_input::<Pallas>);
+    check_inputs(CurveId::Secp256k1, group_input::<Secp256k1>);
+    check_inputs(CurveId::EdOnBn254, group_input::<EdOnBn254>);
+    for precompile in Precompile::ALL {
+        let input = precompile_input(*precompile, 1);
+        assert!(dispatch(precompile.func_id(), &input).is_ok());
+    }
+}
@@ -7,3 +7,5 @@
 #![allow(non_snake_case)]
 #![allow(dead_code)]
 mod arkworks;
+mod runtime;
+mod timing;
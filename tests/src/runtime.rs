@@ -0,0 +1,272 @@
+//! Mock runtime of the chain extension
+//!
+//! cargo test -p tests --features runtime runtime
+#![cfg(feature = "runtime")]
+
+use ark_ec::AffineCurve;
+use arkworks::{
+    abi::Version,
+    chain_extension::ZkMegaExtension,
+    curve::Bn254,
+    weights::{self, Weight},
+    CurveId, Error, G1Bytes, G2Bytes, GroupOperations, Operation,
+};
+use frame_support::{parameter_types, weights::constants::WEIGHT_PER_SECOND};
+use parity_scale_codec::{Decode, Encode};
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, Convert, Hash, IdentityLookup},
+    AccountId32, Perbill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Module, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+        Timestamp: pallet_timestamp::{Module, Call, Storage, Inherent},
+        Randomness: pallet_randomness_collective_flip::{Module, Call, Storage},
+        Contracts: pallet_contracts::{Module, Call, Config<T>, Storage, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub BlockWeights: frame_system::limits::BlockWeights =
+        frame_system::limits::BlockWeights::simple_max(2 * WEIGHT_PER_SECOND);
+    pub const ExistentialDeposit: u64 = 1;
+    pub const MinimumPeriod: u64 = 1;
+    pub const SignedClaimHandicap: u64 = 2;
+    pub const TombstoneDeposit: u64 = 16;
+    pub const DepositPerContract: u64 = 8 * DepositPerStorageByte::get();
+    pub const DepositPerStorageByte: u64 = 10_000;
+    pub const DepositPerStorageItem: u64 = 10_000;
+    pub RentFraction: Perbill = Perbill::from_rational_approximation(4u32, 10_000u32);
+    pub const SurchargeReward: u64 = 150_000_000;
+    pub const MaxDepth: u32 = 100;
+    pub const MaxValueSize: u32 = 16_384;
+    pub const DeletionQueueDepth: u32 = 1024;
+    pub const DeletionWeightLimit: Weight = 500_000_000_000;
+    pub const MaxCodeSize: u32 = 2 * 1024;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = ();
+    type BlockWeights = BlockWeights;
+    type BlockLength = ();
+    type DbWeight = ();
+    type Origin = Origin;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Call = Call;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId32;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type Balance = u64;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
+impl pallet_randomness_collective_flip::Config for Test {}
+
+impl Convert<Weight, u64> for Test {
+    fn convert(weight: Weight) -> u64 {
+        weight
+    }
+}
+
+impl pallet_contracts::Config for Test {
+    type Time = Timestamp;
+    type Randomness = Randomness;
+    type Currency = Balances;
+    type Event = Event;
+    type RentPayment = ();
+    type SignedClaimHandicap = SignedClaimHandicap;
+    type TombstoneDeposit = TombstoneDeposit;
+    type DepositPerContract = DepositPerContract;
+    type DepositPerStorageByte = DepositPerStorageByte;
+    type DepositPerStorageItem = DepositPerStorageItem;
+    type RentFraction = RentFraction;
+    type SurchargeReward = SurchargeReward;
+    type MaxDepth = MaxDepth;
+    type MaxValueSize = MaxValueSize;
+    type WeightPrice = Self;
+    type WeightInfo = ();
+    type ChainExtension = ZkMegaExtension;
+    type DeletionQueueDepth = DeletionQueueDepth;
+    type DeletionWeightLimit = DeletionWeightLimit;
+    type MaxCodeSize = MaxCodeSize;
+}
+
+const ALICE: AccountId32 = AccountId32::new([1; 32]);
+
+const GAS_LIMIT: Weight = 100_000_000_000;
+
+/// Contract forwarding `func_id (u32) | input` to the chain extension and
+/// returning its output
+const FORWARDER: &str = r#"
+(module
+    (import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+    (import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+    (import "seal0" "seal_call_chain_extension"
+        (func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32)))
+    (import "env" "memory" (memory 16 16))
+
+    ;; [0, 4) input buffer length
+    (data (i32.const 0) "\00\00\01\00")
+    ;; [4, 8) output buffer length
+    (data (i32.const 4) "\00\00\01\00")
+
+    (func $assert (param i32)
+        (block $ok
+            (br_if $ok (get_local 0))
+            (unreachable)
+        )
+    )
+
+    (func (export "deploy"))
+
+    (func (export "call")
+        ;; the input at 16, the output at 0x20000
+        (call $seal_input (i32.const 16) (i32.const 0))
+        (call $assert (i32.eqz (call $seal_call_chain_extension
+            (i32.load (i32.const 16))
+            (i32.const 20)
+            (i32.sub (i32.load (i32.const 0)) (i32.const 4))
+            (i32.const 0x20000)
+            (i32.const 4)
+        )))
+        (call $seal_return (i32.const 0) (i32.const 0x20000) (i32.load (i32.const 4)))
+    )
+)
+"#;
+
+/// Externalities with the forwarder deployed by `ALICE`
+fn new_test_ext() -> (sp_io::TestExternalities, AccountId32) {
+    let mut storage = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(ALICE, 1_000_000_000_000_000)],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+    pallet_contracts::GenesisConfig::<Test> {
+        current_schedule: Default::default(),
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    let code = wat::parse_str(FORWARDER).unwrap();
+    let code_hash = <Test as frame_system::Config>::Hashing::hash(&code);
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Contracts::instantiate_with_code(
+            Origin::signed(ALICE),
+            1_000_000_000_000,
+            GAS_LIMIT,
+            code,
+            vec![],
+            vec![],
+        )
+        .unwrap();
+    });
+
+    (ext, Contracts::contract_address(&ALICE, &code_hash, &[]))
+}
+
+/// Call `func_id` through the forwarder as `arkworks::call` does, returns the
+/// result and the gas consumed
+fn call(contract: &AccountId32, func_id: u32, input: &[u8]) -> (Result<Vec<u8>, Error>, Weight) {
+    let mut data = func_id.to_le_bytes().to_vec();
+    input.encode_to(&mut data);
+    let result = Contracts::bare_call(ALICE, contract.clone(), 0, GAS_LIMIT, data);
+    let output = result.exec_result.unwrap();
+    (
+        Decode::decode(&mut &*output.data).unwrap(),
+        result.gas_consumed,
+    )
+}
+
+#[test]
+fn test_chain_extension() {
+    let (mut ext, contract) = new_test_ext();
+    ext.execute_with(|| {
+        let g1 = G1Bytes::<Bn254>::from_affine(&AffineCurve::prime_subgroup_generator());
+        let g2 = G2Bytes::<Bn254>::from_affine(&AffineCurve::prime_subgroup_generator());
+        let add = [g1.as_bytes(), g1.as_bytes()].concat();
+        assert_eq!(
+            call(&contract, CurveId::Bn254.func_id(Operation::Add), &add).0,
+            Bn254::add(&add)
+        );
+
+        // e(P, Q) * e(-P, Q) = 1
+        let pairing = Version::V1.func_id(Operation::Pairing, CurveId::Bn254 as u32);
+        let input = [
+            g1.as_bytes(),
+            g2.as_bytes(),
+            g1.neg().unwrap().as_bytes(),
+            g2.as_bytes(),
+        ]
+        .concat();
+        let (output, gas) = call(&contract, pairing, &input);
+        assert_eq!(output, Ok(true.encode()));
+        assert!(gas > weights::weight(pairing, &input).unwrap());
+        let (_, describe_gas) = call(&contract, arkworks::abi::DESCRIBE, &[]);
+        assert!(gas > describe_gas);
+
+        // errors of the operations are returned to the contract
+        assert_eq!(
+            call(&contract, CurveId::Bn254.func_id(Operation::HashToG2), &[]).0,
+            Err(Error::UnsupportedOperation)
+        );
+        let (output, invalid_gas) = call(&contract, 0x0100000f, &[]);
+        assert_eq!(output, Err(Error::InvalidFunctionId));
+        assert!(invalid_gas > weights::INVALID_CALL);
+
+        // the input is charged per byte before it is read
+        let (output, long_gas) = call(&contract, 0x0100000f, &[0; 4096]);
+        assert_eq!(output, Err(Error::InvalidFunctionId));
+        assert!(long_gas - invalid_gas >= weights::READ_INPUT.weight(4096));
+        assert_eq!(
+            call(&contract, CurveId::Bn254.func_id(Operation::Add), &add[1..]).0,
+            Err(Error::InvalidLength {
+                expected: 130,
+                got: 129,
+            })
+        );
+    });
+}
@@ -0,0 +1,147 @@
+//! dudect-style timing harness of the scalar muls for secret scalars, run it
+//! locally on a quiet machine:
+//!
+//! cargo test --release -p tests timing -- --ignored --nocapture
+//!
+//! Each measurement runs the scalar mul of a fixed scalar or of a random one,
+//! the class is picked at random. The fixed scalar is a random constant, not
+//! one of the scalars passing the identity branches of
+//! [`arkworks::ops::mul_secret`]. Welch's t-test of the timings of the two
+//! classes stays under the threshold of dudect if the timing doesn't depend on
+//! the scalar. The variable time `mul` is measured too for comparison, it is
+//! expected to exceed the threshold.
+use std::time::Instant;
+
+use ark_ec::AffineCurve;
+use ark_ff::{PrimeField, ToBytes};
+use ark_std::{test_rng, UniformRand};
+use arkworks::{
+    curve::{Bls12_381, Bn254, EdOnBn254, Secp256k1},
+    ops::Scalar,
+    GroupOperations,
+};
+use rand::Rng;
+use zkp_u256::U256;
+
+/// The fixed scalar, little-endian, reduced modulo the group order
+const FIXED: [u8; 32] = [
+    0x3c, 0x8e, 0x1f, 0x72, 0xa5, 0x09, 0xd4, 0x6b, 0xe1, 0x57, 0x90, 0x2d, 0xc8, 0x44, 0xb3, 0x1a,
+    0x6f, 0xd2, 0x85, 0x3e, 0x0b, 0x97, 0x61, 0xfa, 0x28, 0xcd, 0x13, 0x7e, 0x59, 0xa0, 0xe6, 0x04,
+];
+
+/// Measurements of each t-test
+const MEASUREMENTS: usize = 20_000;
+
+/// |t| above which the timing depends on the class, as dudect
+const THRESHOLD: f64 = 4.5;
+
+/// Welch's t-test of two classes, with the online mean and variance
+#[derive(Default)]
+struct Welch {
+    n: [f64; 2],
+    mean: [f64; 2],
+    m2: [f64; 2],
+}
+
+impl Welch {
+    fn push(&mut self, class: usize, x: f64) {
+        self.n[class] += 1.0;
+        let delta = x - self.mean[class];
+        self.mean[class] += delta / self.n[class];
+        self.m2[class] += delta * (x - self.mean[class]);
+    }
+
+    fn t(&self) -> f64 {
+        let var = |c: usize| self.m2[c] / (self.n[c] - 1.0);
+        (self.mean[0] - self.mean[1]) / (var(0) / self.n[0] + var(1) / self.n[1]).sqrt()
+    }
+}
+
+/// t statistic of `op` on the `fixed` input and the `random` ones
+fn t_test<I: Clone>(
+    fixed: I,
+    mut random: impl FnMut(&mut rand::rngs::StdRng) -> I,
+    op: impl Fn(I),
+) -> f64 {
+    let rng = &mut test_rng();
+    let mut welch = Welch::default();
+    for _ in 0..MEASUREMENTS {
+        let class = rng.gen_range(0..2);
+        let input = if class == 0 {
+            fixed.clone()
+        } else {
+            random(rng)
+        };
+
+        let start = Instant::now();
+        op(input);
+        welch.push(class, start.elapsed().as_nanos() as f64);
+    }
+    welch.t()
+}
+
+/// t statistics of `mul_secret` and `mul` on group `T`
+fn group<T: GroupOperations>(name: &str) {
+    let point = <T::Affine as AffineCurve>::prime_subgroup_generator();
+    let input = |scalar: Scalar<T>| {
+        let mut input = Vec::new();
+        point.write(&mut input).unwrap();
+        scalar.write(&mut input).unwrap();
+        input
+    };
+    let fixed = input(Scalar::<T>::from_le_bytes_mod_order(&FIXED));
+    let random = |rng: &mut rand::rngs::StdRng| input(Scalar::<T>::rand(rng));
+
+    let secret = t_test(fixed.clone(), random, |input| {
+        T::mul_secret(&input).unwrap();
+    });
+    let public = t_test(fixed, random, |input| {
+        T::mul(&input).unwrap();
+    });
+    println!(
+        "{}: |t| of mul_secret {:.2}, of mul {:.2}",
+        name,
+        secret.abs(),
+        public.abs()
+    );
+    assert!(secret.abs() < THRESHOLD, "{} mul_secret leaks timing", name);
+}
+
+#[test]
+#[ignore]
+fn timing_curves() {
+    group::<Bn254>("BN254");
+    group::<Bls12_381>("BLS12-381");
+    group::<Secp256k1>("secp256k1");
+    group::<EdOnBn254>("Ed-on-BN254");
+}
+
+#[test]
+#[ignore]
+fn timing_eddsa() {
+    let x = U256::from_decimal_str(
+        "17777552123799933955779906779655732241715742912184938656739573121738514868268",
+    )
+    .unwrap();
+    let y = U256::from_decimal_str(
+        "2626589144620713026669568689430873010625803728049924121243784502389097019475",
+    )
+    .unwrap();
+    let random = |rng: &mut rand::rngs::StdRng| U256::from_bytes_be(&rng.gen::<[u8; 32]>());
+    let mut fixed = FIXED;
+    fixed.reverse();
+    let fixed = U256::from_bytes_be(&fixed);
+
+    let secret = t_test(fixed.clone(), random, |value| {
+        eddsa::scalar_mult_secret(x.clone(), y.clone(), value);
+    });
+    let public = t_test(fixed, random, |value| {
+        eddsa::scalar_mult(x.clone(), y.clone(), value);
+    });
+    println!(
+        "eddsa: |t| of scalar_mult_secret {:.2}, of scalar_mult {:.2}",
+        secret.abs(),
+        public.abs()
+    );
+    assert!(secret.abs() < THRESHOLD, "scalar_mult_secret leaks timing");
+}
@@ -0,0 +1,79 @@
+//! Curve operations in wasm32, the `no_std` build of the runtime
+//!
+//! The host writes the input to the memory of `alloc`, then calls `call` or
+//! `verify`. Nothing is freed, the host runs each call in a new instance.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{alloc::Layout, vec::Vec};
+use core::{alloc::GlobalAlloc, arch::wasm32};
+
+/// Bytes of a wasm page
+const PAGE: usize = 65536;
+
+/// Bump allocator from the end of the initial memory, growing it on demand
+struct Bump;
+
+/// Next free address, zero before the first allocation
+static mut NEXT: usize = 0;
+
+unsafe impl GlobalAlloc for Bump {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if NEXT == 0 {
+            NEXT = wasm32::memory_size(0) * PAGE;
+        }
+        let addr = (NEXT + layout.align() - 1) & !(layout.align() - 1);
+        let end = addr + layout.size();
+        let size = wasm32::memory_size(0) * PAGE;
+        if end > size && wasm32::memory_grow(0, (end - size + PAGE - 1) / PAGE) == usize::MAX {
+            return core::ptr::null_mut();
+        }
+        NEXT = end;
+        addr as *mut u8
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: Bump = Bump;
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    wasm32::unreachable()
+}
+
+/// Allocate `len` bytes of input
+#[no_mangle]
+pub extern "C" fn alloc(len: u32) -> *mut u8 {
+    let mut input = Vec::<u8>::with_capacity(len as usize);
+    let ptr = input.as_mut_ptr();
+    core::mem::forget(input);
+    ptr
+}
+
+/// Run `func_id` on the `len` bytes of input at `ptr`, returns the output as
+/// `ptr | len << 32`, or zero on errors
+#[no_mangle]
+pub extern "C" fn call(func_id: u32, ptr: *const u8, len: u32) -> u64 {
+    let input = unsafe { core::slice::from_raw_parts(ptr, len as usize) };
+    match arkworks::call(func_id, input) {
+        Ok(output) => {
+            let output = output.leak();
+            output.as_ptr() as u64 | (output.len() as u64) << 32
+        }
+        Err(_) => 0,
+    }
+}
+
+/// Groth16 verify of the parcel of `len` bytes at `ptr`, returns 1 if the
+/// proof is valid, 0 if not and -1 on errors
+#[no_mangle]
+pub extern "C" fn verify(curve_id: u32, ptr: *const u8, len: u32) -> i32 {
+    let parcel = unsafe { core::slice::from_raw_parts(ptr, len as usize) };
+    match arkworks::verify_parcel(curve_id, parcel.to_vec()) {
+        Ok(valid) => valid as i32,
+        Err(_) => -1,
+    }
+}